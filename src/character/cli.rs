@@ -3,21 +3,31 @@ use clap::Subcommand;
 #[derive(Subcommand)]
 pub enum CharacterCommands {
     /// Create a new character with unified --set for all fields
+    #[command(after_help = crate::relations::relation_set_help("character"))]
     Create {
         /// Character name (unique identifier)
         name: String,
         /// Set any field (--set display_name="Name" --set status="Active" --set age=25 --set faction=rebels)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
     },
     
     /// List characters in current world
-    List,
+    List {
+        #[command(flatten)]
+        options: crate::listing::ListArgs,
+    },
     
     /// Show character details
     Info {
         /// Character name
         name: String,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
     },
     
     /// Delete a character
@@ -27,16 +37,48 @@ pub enum CharacterCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+        /// What to do with existing relations: refuse, detach or cascade
+        #[arg(long, value_enum, default_value_t = crate::relations::DeleteMode::Refuse)]
+        on_relations: crate::relations::DeleteMode,
     },
 
     /// Update an existing character
+    #[command(after_help = crate::relations::relation_set_help("character"))]
     Update {
         /// Character name
         name: String,
         /// Set any field (--set display_name="Name" --set status="Active" --set age=25 --set description="...")
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
+        /// Remove a metadata field (--unset age --unset stats.strength)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+    /// Rename a character, keeping its relations
+    Rename {
+        /// Current character name
+        name: String,
+        /// New character name
+        new_name: String,
+    },
+    /// Merge a duplicate character into another one, moving its relations
+    Merge {
+        /// Character to keep
+        keep: String,
+        /// Duplicate character to merge in and delete (its name becomes an alias)
+        drop: String,
+        /// Metadata set differently on both: keep the survivor's value, take the duplicate's, or fail
+        #[arg(long, value_enum, default_value_t = crate::relations::MergePolicy::Keep)]
+        on_conflict: crate::relations::MergePolicy,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
     },
+    /// Show the field schema of characters (built-in fields and [schema.character] in config.toml)
+    Schema,
 }
 
 /// Parse a single key-value pair for --set flag
@@ -1,49 +1,60 @@
 use super::cli::CharacterCommands;
 use super::models::{Character, CharacterStatus};
-use crate::relations::{process_relations, EntityType, separate_relation_fields};
+use crate::relations::{process_relations, EntityType, separate_relation_fields, relation_keys_for, show_entity_relations, DeleteMode, check_entity_delete, release_entity, entity_relations, MergePolicy, metadata_conflicts};
 use anyhow::Result;
 use serde_json::{json, Value as JsonValue};
 use crate::world::WorldConfig;
+use crate::say;
 
 pub fn handle_character_command(command: CharacterCommands) -> Result<()> {
     match command {
-        CharacterCommands::Create { name, set } => {
-            handle_create(name, set)
+        CharacterCommands::Create { name, set, set_json } => {
+            handle_create(name, set, set_json)
         }
-        CharacterCommands::List => handle_list(),
-        CharacterCommands::Info { name } => handle_info(name),
-        CharacterCommands::Delete { name, force } => handle_delete(name, force),
-        CharacterCommands::Update { name, set } => handle_update(name, set),
+        CharacterCommands::List { options } => handle_list(options),
+        CharacterCommands::Schema => Character::show_schema(),
+        CharacterCommands::Info { name, at } => handle_info(name, at),
+        CharacterCommands::Delete { name, force, on_relations } => handle_delete(name, force, on_relations),
+        CharacterCommands::Rename { name, new_name } => handle_rename(name, new_name),
+        CharacterCommands::Merge { keep, drop, on_conflict, force } => handle_merge(keep, drop, on_conflict, force),
+        CharacterCommands::Update { name, set, set_json, unset } => handle_update(name, set, set_json, unset),
     }
 }
 
-fn handle_update(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("🔄 Updating character '{name}'");
+fn handle_update(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>, unset: Vec<String>) -> Result<()> {
+    say!("🔄 Updating character '{name}'");
 
     let mut character = Character::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Character '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Character", &name))?;
 
     // Process relations and get back non-relation fields
     let regular_fields = process_relations(EntityType::Character(name.clone()), set_args)?;
     
+    character.unset_fields(&unset)?;
+    character.process_json_args(set_json)?;
     // Update regular fields
     character.update(regular_fields)?;
 
-    println!("✅ Character '{}' updated!", name);
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Character(character.name.clone()), None)?;
+        return crate::output::emit_entity("character", Some("updated"), &character, Some(relations));
+    }
+
+    say!("✅ Character '{}' updated!", name);
     show_created_character(&character)?;
 
     Ok(())
 }
 
-fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("👤 Creating character '{name}'");
+fn handle_create(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>) -> Result<()> {
+    say!("👤 Creating character '{name}'");
     
     // Separate relation fields from regular fields  
-    let relation_keys = ["episode", "location", "faction", "race", "system"];
+    let relation_keys = relation_keys_for("character");
     let (relation_fields, regular_fields) = separate_relation_fields(set_args, &relation_keys);
     
     // Create character with regular fields FIRST
-    let mut character = Character::create_new(name.clone(), regular_fields)?;
+    let mut character = Character::create_new(name.clone(), regular_fields, set_json)?;
     character.create()?;
     
     // THEN process relations after character exists in database
@@ -51,101 +62,190 @@ fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
         process_relations(EntityType::Character(name.clone()), relation_fields)?;
     }
     
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Character(character.name.clone()), None)?;
+        return crate::output::emit_entity("character", Some("created"), &character, Some(relations));
+    }
+    
     show_created_character(&character)?;
     
     Ok(())
 }
 
 fn show_created_character(character: &Character) -> Result<()> {
-    println!("✅ Character '{}' created!", character.name);
-    println!("   Display name: {}", character.display_name);
-    println!("   Status: {:?}", character.status);
+    say!("✅ Character '{}' created!", character.name);
+    say!("   Display name: {}", character.display_name);
+    say!("   Status: {:?}", character.status);
     // Show metadata
     if !character.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &character.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
     Ok(())
 }
 
-fn handle_list() -> Result<()> {
-    let characters = Character::list()?;
+fn handle_list(options: crate::listing::ListArgs) -> Result<()> {
+    let characters = Character::list_with(&options)?;
+    
+    if crate::output::is_structured() {
+        return crate::output::emit_list(&characters, &options.fields);
+    }
+    
+    if !options.fields.is_empty() {
+        return crate::listing::print_fields(&characters, &options.fields);
+    }
     
     if characters.is_empty() {
-        println!("👤 No characters found in this world");
-        println!("   Use 'multiverse character create <name> --set display_name=\"<name>\"' to create one");
+        say!("👤 No characters found in this world");
+        say!("   Use 'multiverse character create <name> --set display_name=\"<name>\"' to create one");
         return Ok(());
     }
     
-    println!("👤 Characters in current world:");
+    say!("👤 Characters in current world:");
     
     for character in characters {
-        let status_emoji = match character.status {
+        let status_emoji = crate::status::status_emoji("character", character.status.as_str(), match character.status {
             CharacterStatus::Active => "🟢",
             CharacterStatus::Inactive => "🟡",
             CharacterStatus::Deceased => "💀",
             CharacterStatus::Archived => "📦",
-        };
-        println!("   {} {} - \"{}\"", 
+            CharacterStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
+        say!("   {} {} - \"{}\"", 
             status_emoji, 
             character.name, 
             character.display_name
         );
         // Show key metadata fields
         if let Some(age) = character.metadata.get("age") {
-            println!("      Age: {}", age.as_str().unwrap_or("Unknown"));
+            say!("      Age: {}", age.as_str().unwrap_or("Unknown"));
         }
         if let Some(faction) = character.metadata.get("faction") {
-            println!("      Faction: {}", faction.as_str().unwrap_or("Unknown"));
+            say!("      Faction: {}", faction.as_str().unwrap_or("Unknown"));
         }
         if let Some(desc) = character.metadata.get("description") {
-            println!("      {}", desc.as_str().unwrap_or(""));
+            say!("      {}", desc.as_str().unwrap_or(""));
         }
     }
     Ok(())
 }
 
-fn handle_info(name: String) -> Result<()> {
+fn handle_info(name: String, at: Option<String>) -> Result<()> {
     let character = Character::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Character '{}' not found", name))?;
-    println!("👤 Character: {} - \"{}\"", character.name, character.display_name);
-    println!("   Status: {:?}", character.status);
-    println!("   Created: {}", character.created_at.format("%Y-%m-%d %H:%M"));
+        .ok_or_else(|| crate::output::not_found("Character", &name))?;
+    
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Character(character.name.clone()), at.as_deref())?;
+        return crate::output::emit_entity("character", None, &character, Some(relations));
+    }
+    
+    say!("👤 Character: {} - \"{}\"", character.name, character.display_name);
+    say!("   Status: {:?}", character.status);
+    say!("   Created: {}", character.created_at.format("%Y-%m-%d %H:%M"));
     if let Some(desc) = character.metadata.get("description") {
-        println!("   Description: {}", desc.as_str().unwrap_or(""));
+        say!("   Description: {}", desc.as_str().unwrap_or(""));
     }
     // Show metadata
     if !character.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &character.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
-    // TODO: Show episodes where character appears with roles
-    println!("   Episodes: (to be implemented)");
+    show_entity_relations(&EntityType::Character(character.name.clone()), at.as_deref())?;
     Ok(())
 }
 
-fn handle_delete(name: String, force: bool) -> Result<()> {
+fn handle_delete(name: String, force: bool, on_relations: DeleteMode) -> Result<()> {
     let character = Character::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Character '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Character", &name))?;
     
+    let entity = EntityType::Character(name.clone());
+    let relations = check_entity_delete(&entity, on_relations)?;
+
     if !force {
-        println!("⚠️  Are you sure you want to delete character '{name}'?");
-        println!("   This will permanently delete the character and remove them from all episodes");
-        println!("   Use --force to skip this confirmation");
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("delete character '{}'", name)));
+        }
+        say!("⚠️  Are you sure you want to delete character '{name}'?");
+        say!("   This will permanently delete the character and remove them from all episodes");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will be removed as well", relations.len());
+        }
+        say!("   Use --force to skip this confirmation");
         return Ok(());
     }
     
-    println!("🗑️  Deleting character '{name}'...");
+    say!("🗑️  Deleting character '{name}'...");
     
+    release_entity(&entity, on_relations)?;
     character.delete(force)?;
     
-    println!("✅ Character '{name}' deleted!");
+    say!("✅ Character '{name}' deleted!");
+
+    if crate::output::is_structured() {
+        return crate::output::emit_entity("character", Some("deleted"), &character, Some(relations));
+    }
     
     Ok(())
 }
 
+fn handle_rename(name: String, new_name: String) -> Result<()> {
+    let mut character = Character::get(&name)?
+        .ok_or_else(|| crate::output::not_found("Character", &name))?;
+
+    say!("✏️  Renaming character '{name}' to '{new_name}'...");
+
+    character.rename(new_name.clone())?;
+
+    say!("✅ Character '{name}' is now '{new_name}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Character(character.name.clone()), None)?;
+        return crate::output::emit_entity("character", Some("renamed"), &character, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_merge(keep: String, drop: String, on_conflict: MergePolicy, force: bool) -> Result<()> {
+    let mut survivor = Character::get(&keep)?
+        .ok_or_else(|| crate::output::not_found("Character", &keep))?;
+    let duplicate = Character::get(&drop)?
+        .ok_or_else(|| crate::output::not_found("Character", &drop))?;
+
+    if !force {
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("merge character '{}' into '{}'", drop, keep)));
+        }
+        let relations = entity_relations(&EntityType::Character(drop.clone()), None)?;
+        let conflicts = metadata_conflicts(&survivor.metadata, &duplicate.metadata);
+        say!("⚠️  Are you sure you want to merge character '{drop}' into '{keep}'?");
+        say!("   '{drop}' will be deleted and kept as an alias of '{keep}'");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will move to '{keep}'", relations.len());
+        }
+        if !conflicts.is_empty() {
+            say!("   Conflicting metadata ({:?}): {}", on_conflict, conflicts.join(", "));
+        }
+        say!("   Use --force to skip this confirmation");
+        return Ok(());
+    }
+
+    say!("🔀 Merging character '{drop}' into '{keep}'...");
+
+    survivor.merge(duplicate, on_conflict)?;
+
+    say!("✅ Character '{drop}' merged into '{keep}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Character(survivor.name.clone()), None)?;
+        return crate::output::emit_entity("character", Some("merged"), &survivor, Some(relations));
+    }
+
+    Ok(())
+}
+
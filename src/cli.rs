@@ -9,7 +9,10 @@ use crate::{
     event::EventCommands,
     faction::FactionCommands,
     system::SystemCommands,
-    race::RaceCommands
+    race::RaceCommands,
+    relations::RelationCommands,
+    output::OutputFormat,
+    queries::QueryCommands,
     // TODO: Re-enable as we implement them:
 };
 
@@ -18,6 +21,10 @@ use crate::{
 #[command(about = "Professional tooling for complex narrative universes")]
 #[command(version)]
 pub struct Cli {
+    /// Structured output for list/info/create/update/delete/rename/merge (prose then goes to stderr)
+    #[arg(long, global = true, value_enum)]
+    pub format: Option<OutputFormat>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -27,7 +34,7 @@ pub enum Commands {
     /// Initialize a new multiverse project or manage world settings
     World {
         #[command(subcommand)]
-        command: WorldCommands,
+        command: WorldCommand,
     },
     /// Manage characters (requires being in a multiverse project)
     Character {
@@ -88,6 +95,12 @@ pub enum Commands {
        command: RaceCommands,
    },
 
+    /// Inspect and manage relations between entities (requires being in a multiverse project)
+    Relation {
+        #[command(subcommand)]
+        command: RelationCommands,
+    },
+
     /// Manage timeline configuration and dates (requires being in a multiverse project)
     Timeline {
         #[command(subcommand)]
@@ -95,14 +108,39 @@ pub enum Commands {
     },
 
     
-    /// Execute SQL SELECT queries on the database
+    /// Execute read-only SQL queries on the database, or saved queries (query run/list)
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
     Query {
-        /// The SQL SELECT query to execute
-        sql: String,
+        /// The SQL query to execute
+        #[arg(required_unless_present = "repl")]
+        sql: Option<String>,
+        /// Start an interactive SQL shell (.help lists its dot-commands)
+        #[arg(long, conflicts_with = "sql")]
+        repl: bool,
+        #[command(subcommand)]
+        command: Option<QueryCommands>,
+    },
+
+    /// Apply pending database schema migrations, or list them with --status
+    Migrate {
+        /// Only report applied and pending migrations
+        #[arg(long)]
+        status: bool,
     },
 
     /// Show project information
     Info,
 }
 
-
+/// `world` subcommands: those of the world module plus the checks spanning every entity
+#[derive(Subcommand)]
+pub enum WorldCommand {
+    /// Find relation rows and episodes pointing to deleted entities (same as relation doctor)
+    Doctor {
+        /// Remove the orphaned rows
+        #[arg(long)]
+        fix: bool,
+    },
+    #[command(flatten)]
+    World(WorldCommands),
+}
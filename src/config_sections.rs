@@ -0,0 +1,64 @@
+//! Extension sections of .multiverse/config.toml: `[schema.*]`, `[status.*]` and `[relations.*]`
+//!
+//! The file is read and parsed once per process. Each module asks for its section and gets
+//! one entry per `[<section>.<name>]` table; entries that do not deserialize or validate are
+//! reported and skipped, so a mistake in one entry never disables the others.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use crate::world::WorldConfig;
+
+/// The current world's config.toml; empty outside a world or when it cannot be read
+fn world_config() -> &'static toml::Table {
+    static CONFIG: OnceLock<toml::Table> = OnceLock::new();
+    CONFIG.get_or_init(|| read_world_config().unwrap_or_else(|e| {
+        eprintln!("Warning: {:#}", e);
+        toml::Table::new()
+    }))
+}
+
+fn read_world_config() -> Result<toml::Table> {
+    let Ok(world_root) = WorldConfig::get_world_root() else {
+        return Ok(toml::Table::new());
+    };
+    let config_path = world_root.join(".multiverse/config.toml");
+    if !config_path.exists() {
+        return Ok(toml::Table::new());
+    }
+
+    let content = std::fs::read_to_string(&config_path)
+        .context("Failed to read world config")?;
+    toml::from_str(&content)
+        .context("Failed to parse .multiverse/config.toml")
+}
+
+/// Entries of the `[<section>.<name>]` tables by name, keeping those that deserialize
+/// and pass `validate`; the others print "Warning: ignoring [<section>.<name>] ..."
+pub fn load_section<T: DeserializeOwned>(section: &str, validate: impl Fn(&str, &T) -> Result<()>) -> BTreeMap<String, T> {
+    let mut entries = BTreeMap::new();
+    let Some(value) = world_config().get(section) else {
+        return entries;
+    };
+    let Some(table) = value.as_table() else {
+        eprintln!("Warning: ignoring [{}] in config.toml: expected [{}.<name>] tables", section, section);
+        return entries;
+    };
+
+    for (name, value) in table {
+        let entry = value.clone().try_into::<T>()
+            .map_err(anyhow::Error::from)
+            .and_then(|entry| validate(name, &entry).map(|()| entry));
+        match entry {
+            Ok(entry) => {
+                entries.insert(name.clone(), entry);
+            }
+            Err(e) => {
+                let message = e.to_string().split_whitespace().collect::<Vec<_>>().join(" ");
+                eprintln!("Warning: ignoring [{}.{}] in config.toml: {}", section, name, message);
+            }
+        }
+    }
+    entries
+}
@@ -1,13 +1,30 @@
-use rusqlite::{Connection, Result as SqliteResult};
+use rusqlite::hooks::{AuthAction, AuthContext, Authorization};
+use rusqlite::{Connection, OpenFlags, Result as SqliteResult};
+use std::cell::RefCell;
 use std::path::Path;
+use std::rc::Rc;
 use anyhow::{Result, Context};
-use serde_json::{json, Value as JsonValue};
+use serde_json::Value as JsonValue;
 
-/// Get a database connection for a specific database file
+/// Open a database file without touching its schema (`multiverse migrate --status`)
+pub fn open_database(db_path: &Path) -> Result<Connection> {
+    Connection::open(db_path)
+        .with_context(|| format!("Failed to open database at {}", db_path.display()))
+}
+
+/// Get a database connection for a specific database file, with pending schema
+/// migrations applied (see `crate::migrations`), config-defined relation tables created
+/// and the query views of `crate::views` up to date
 pub fn get_connection(db_path: &Path) -> Result<Connection> {
-    let conn = Connection::open(db_path)
-        .with_context(|| format!("Failed to open database at {}", db_path.display()))?;
-    
+    let conn = open_database(db_path)?;
+
+    crate::migrations::migrate(&conn)
+        .with_context(|| format!("Failed to migrate database at {}", db_path.display()))?;
+    crate::relations::init_config_relation_tables(&conn)
+        .context("Failed to create the relation tables declared in config.toml")?;
+    crate::views::refresh_query_views(&conn)
+        .context("Failed to create the query views")?;
+
     // Enable foreign keys
     conn.execute("PRAGMA foreign_keys = ON", [])
         .context("Failed to enable foreign keys")?;
@@ -15,7 +32,7 @@ pub fn get_connection(db_path: &Path) -> Result<Connection> {
     Ok(conn)
 }
 
-/// Initialize a new database file with basic setup
+/// Initialize a new database file with the current schema
 pub fn init_database(db_path: &Path) -> Result<()> {
     // Create parent directory if it doesn't exist
     if let Some(parent) = db_path.parent() {
@@ -23,61 +40,234 @@ pub fn init_database(db_path: &Path) -> Result<()> {
             .with_context(|| format!("Failed to create directory {}", parent.display()))?;
     }
     
-    let conn = get_connection(db_path)?;
-    
-    // Run initial migrations
-    run_initial_migrations(&conn)
-        .context("Failed to run initial database migrations")?;
-    
+    get_connection(db_path)?;
     Ok(())
 }
 
-/// Run initial database migrations (creates core tables)
-fn run_initial_migrations(conn: &Connection) -> SqliteResult<()> {
-    // Create migrations tracking table
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS schema_migrations (
-            version INTEGER PRIMARY KEY,
-            applied_at DATETIME DEFAULT CURRENT_TIMESTAMP
-        )",
-        [],
-    )?;
-    
-    Ok(())
+thread_local! {
+    /// The command context of the running CLI command, see `with_command_context`
+    static COMMAND_CONTEXT: RefCell<Option<CommandContext>> = const { RefCell::new(None) };
 }
 
-/// Validate that a SQL query is a SELECT-only statement (security check)
-fn validate_select_only(sql: &str) -> Result<()> {
-    let sql_trimmed = sql.trim().to_lowercase();
-    
-    // Must start with SELECT
-    if !sql_trimmed.starts_with("select") {
-        return Err(anyhow::anyhow!("Only SELECT queries are allowed"));
+/// A change to the world's files, held back until the command's transaction commits
+type FileChange = Box<dyn FnOnce() -> Result<()>>;
+
+/// One connection and one transaction shared by everything a CLI command does. The
+/// connection is opened, and the transaction begun, on the first `world_connection` call,
+/// so commands that never touch the database (or create it, like `world init`) open nothing.
+#[derive(Default)]
+struct CommandContext {
+    conn: Option<Rc<Connection>>,
+    file_changes: Vec<FileChange>,
+}
+
+/// Run a CLI command as one unit: every world database access inside it goes through the
+/// same connection and transaction, committed when the command succeeds and rolled back
+/// when it fails, so a failing `character create` leaves no half-created character behind.
+/// File changes queued with `after_commit` run once the commit went through, and are
+/// dropped with the rollback.
+pub fn with_command_context<T>(command: impl FnOnce() -> Result<T>) -> Result<T> {
+    COMMAND_CONTEXT.with(|context| *context.borrow_mut() = Some(CommandContext::default()));
+    let result = command();
+    let Some(context) = COMMAND_CONTEXT.with(|context| context.borrow_mut().take()) else {
+        return result;
+    };
+
+    let value = match (result, context.conn) {
+        (Ok(value), Some(conn)) => {
+            // New metadata keys become view columns in the same transaction
+            crate::views::refresh_query_views(&conn).context("Failed to update the query views")?;
+            conn.execute_batch("COMMIT").context("Failed to commit changes to the world database")?;
+            value
+        }
+        (Ok(value), None) => value,
+        (Err(error), conn) => {
+            // The command's error is the one worth reporting
+            if let Some(conn) = conn {
+                let _ = conn.execute_batch("ROLLBACK");
+            }
+            return Err(error);
+        }
+    };
+
+    for change in context.file_changes {
+        change().context("The database change was saved, but updating the world's files failed")?;
     }
-    
-    // Check for dangerous keywords (basic security)
-    let forbidden = ["insert", "update", "delete", "drop", "create", "alter", "pragma"];
-    for keyword in forbidden {
-        if sql_trimmed.contains(keyword) {
-            return Err(anyhow::anyhow!("Query contains forbidden keyword: {}", keyword));
+    Ok(value)
+}
+
+/// Change the world's files only if the running command succeeds: inside
+/// `with_command_context` the change waits for the commit, otherwise it runs now. Checks
+/// that can fail (target already exists) belong before the call, in the command itself.
+pub fn after_commit(change: impl FnOnce() -> Result<()> + 'static) -> Result<()> {
+    let change = COMMAND_CONTEXT.with(|context| match context.borrow_mut().as_mut() {
+        Some(context) => {
+            context.file_changes.push(Box::new(change));
+            None
+        }
+        None => Some(change),
+    });
+    change.map_or(Ok(()), |change| change())
+}
+
+/// Connection to the current world database: inside `with_command_context` the command's
+/// shared connection with its open transaction, otherwise a connection of its own
+pub fn world_connection() -> Result<Rc<Connection>> {
+    let db_path = crate::world::WorldConfig::get_database_path()?;
+    COMMAND_CONTEXT.with(|context| {
+        let mut context = context.borrow_mut();
+        let Some(context) = context.as_mut() else {
+            return Ok(Rc::new(get_connection(&db_path)?));
+        };
+        if let Some(conn) = &context.conn {
+            return Ok(Rc::clone(conn));
         }
+
+        let conn = get_connection(&db_path)?;
+        conn.execute_batch("BEGIN").context("Failed to begin a transaction")?;
+        let conn = Rc::new(conn);
+        context.conn = Some(Rc::clone(&conn));
+        Ok(conn)
+    })
+}
+
+/// A nested transaction: unlike `unchecked_transaction` it also works inside the command
+/// transaction. Changes are kept by `commit` and rolled back when it is dropped without one.
+pub struct Savepoint<'c> {
+    conn: &'c Connection,
+    committed: bool,
+}
+
+pub fn savepoint(conn: &Connection) -> Result<Savepoint<'_>> {
+    conn.execute_batch("SAVEPOINT multiverse")?;
+    Ok(Savepoint { conn, committed: false })
+}
+
+impl Savepoint<'_> {
+    pub fn commit(mut self) -> Result<()> {
+        self.conn.execute_batch("RELEASE multiverse")?;
+        self.committed = true;
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for Savepoint<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn
+    }
+}
+
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = self.conn.execute_batch("ROLLBACK TO multiverse; RELEASE multiverse");
+        }
+    }
+}
+
+/// Check whether a table exists in the database
+pub fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        [table],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// Add a column to an existing table when it is missing (tables created by older versions)
+pub fn ensure_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt.query_map([], |row| row.get::<_, String>(1))?
+        .collect::<SqliteResult<Vec<String>>>()?;
+
+    if !columns.iter().any(|existing| existing == column) {
+        conn.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
     }
-    
     Ok(())
 }
 
-/// Execute a SELECT query and return results as JSON
+/// Pragmas that only inspect the schema and may be queried with an argument
+const INSPECTION_PRAGMAS: &[&str] = &[
+    "table_info", "table_xinfo", "index_list", "index_info", "index_xinfo", "foreign_key_list",
+];
+
+/// Open the world database for `multiverse query`. The file is opened read-only and an
+/// authorizer refuses anything but reading, so SELECT, WITH, EXPLAIN QUERY PLAN and
+/// schema pragmas work while writes fail when the statement is prepared.
+pub fn get_readonly_connection(db_path: &Path) -> Result<Connection> {
+    // A read-only connection cannot migrate or write views: bring the schema up to date first
+    drop(get_connection(db_path)?);
+
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+        .with_context(|| format!("Failed to open database at {}", db_path.display()))?;
+    conn.authorizer(Some(authorize_read));
+    Ok(conn)
+}
+
+fn authorize_read(context: AuthContext<'_>) -> Authorization {
+    match context.action {
+        AuthAction::Select | AuthAction::Read { .. } | AuthAction::Function { .. } | AuthAction::Recursive => Authorization::Allow,
+        AuthAction::Pragma { pragma_name, pragma_value } if pragma_value.is_none() || INSPECTION_PRAGMAS.contains(&pragma_name) => {
+            Authorization::Allow
+        }
+        _ => Authorization::Deny,
+    }
+}
+
+/// Execute a read-only query and print the result set in the --format chosen (JSON by default)
 pub fn execute_query(sql: &str) -> Result<()> {
-    // Validate query is SELECT-only
-    validate_select_only(sql)?;
-    
-    // Get database connection
     let db_path = crate::world::WorldConfig::get_database_path()?;
-    let conn = get_connection(&db_path)?;
+    let conn = get_readonly_connection(&db_path)?;
+    run_query(&conn, sql, &[])
+}
+
+fn prepare_read_only<'c>(conn: &'c Connection, sql: &str) -> Result<rusqlite::Statement<'c>> {
+    // Writes are refused by the authorizer while preparing
+    match conn.prepare(sql) {
+        Ok(stmt) => Ok(stmt),
+        Err(rusqlite::Error::SqliteFailure(error, _)) if error.code == rusqlite::ErrorCode::AuthorizationForStatementDenied => {
+            anyhow::bail!("Only read-only queries are allowed (SELECT, WITH, EXPLAIN QUERY PLAN)");
+        }
+        Err(error) => Err(error).with_context(|| format!("Failed to prepare query: {}", sql)),
+    }
+}
+
+/// Named parameters (:name) of a query, in order of appearance
+pub fn query_parameters(conn: &Connection, sql: &str) -> Result<Vec<String>> {
+    let stmt = prepare_read_only(conn, sql)?;
+    Ok((1..=stmt.parameter_count())
+        .filter_map(|index| stmt.parameter_name(index).map(str::to_string))
+        .collect())
+}
+
+/// Run one query on a connection from `get_readonly_connection` and print its result set.
+/// `params` bind :name parameters; unknown names are refused and missing ones are NULL
+/// (with a warning unless the query checks `:name IS NULL` itself).
+pub fn run_query(conn: &Connection, sql: &str, params: &[(String, String)]) -> Result<()> {
+    let mut stmt = prepare_read_only(conn, sql)?;
     
-    // Prepare and execute query
-    let mut stmt = conn.prepare(sql)
-        .with_context(|| format!("Failed to prepare query: {}", sql))?;
+    let mut bindings: Vec<(String, &dyn rusqlite::ToSql)> = Vec::new();
+    for (name, value) in params {
+        let key = format!(":{}", name.trim_start_matches(':'));
+        if stmt.parameter_index(&key)?.is_none() {
+            anyhow::bail!("The query has no parameter {}", key);
+        }
+        bindings.push((key, value));
+    }
+    // Parameters the query tests with `:name IS NULL` are optional
+    let sql_upper = sql.to_uppercase();
+    for index in 1..=stmt.parameter_count() {
+        if let Some(name) = stmt.parameter_name(index) {
+            let optional = sql_upper.contains(&format!("{} IS NULL", name.to_uppercase()));
+            if !optional && !bindings.iter().any(|(key, _)| key == name) {
+                eprintln!("⚠️  {} is not set, using NULL", name);
+            }
+        }
+    }
+    let named: Vec<(&str, &dyn rusqlite::ToSql)> = bindings.iter().map(|(key, value)| (key.as_str(), *value)).collect();
     
     // Get column names
     let column_names: Vec<String> = stmt.column_names()
@@ -85,23 +275,12 @@ pub fn execute_query(sql: &str) -> Result<()> {
         .map(|name| name.to_string())
         .collect();
     
-    if column_names.is_empty() {
-        let result = json!({
-            "query": sql,
-            "columns": [],
-            "rows": [],
-            "count": 0
-        });
-        println!("{}", serde_json::to_string_pretty(&result)?);
-        return Ok(());
-    }
-    
     // Execute query - rusqlite non ha conversione automatica, tocca farlo a mano
     let mut all_rows = Vec::new();
-    let rows = stmt.query_map([], |row| {
-        let mut row_map = serde_json::Map::new();
+    let rows = stmt.query_map(named.as_slice(), |row| {
+        let mut values = Vec::with_capacity(column_names.len());
         
-        for (i, col_name) in column_names.iter().enumerate() {
+        for i in 0..column_names.len() {
             // Rusqlite value handling - prova i tipi più comuni
             let value = match row.get_ref(i)? {
                 rusqlite::types::ValueRef::Null => JsonValue::Null,
@@ -122,28 +301,17 @@ pub fn execute_query(sql: &str) -> Result<()> {
                     JsonValue::String(format!("<blob:{} bytes>", b.len()))
                 }
             };
-            row_map.insert(col_name.clone(), value);
+            values.push(value);
         }
         
-        Ok(JsonValue::Object(row_map))
+        Ok(values)
     })?;
     
     for row_result in rows {
         all_rows.push(row_result?);
     }
     
-    // Create structured JSON response
-    let result = json!({
-        "query": sql,
-        "columns": column_names,
-        "rows": all_rows,
-        "count": all_rows.len()
-    });
-    
-    // Pretty-print JSON
-    println!("{}", serde_json::to_string_pretty(&result)?);
-    
-    Ok(())
+    crate::output::emit_query(sql, &column_names, all_rows)
 }
 
 
@@ -22,9 +22,34 @@ macro_rules! define_entity_struct {
             pub status: $status,
         }
 
-        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[derive(Clone, PartialEq, Serialize, Deserialize)]
         pub enum $status {
-            $($variant),+
+            $($variant,)+
+            /// State added by a [status.<entity>] workflow in config.toml
+            #[serde(untagged)]
+            Custom(String),
+        }
+
+        impl $status {
+            /// Status name as stored in the database
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => stringify!($variant),)+
+                    Self::Custom(name) => name,
+                }
+            }
+        }
+
+        // Prints the bare name, so custom states read like the built-in ones
+        impl std::fmt::Debug for $status {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl $entity {
+            /// Accepted status values
+            pub const STATUS_VALUES: &'static [&'static str] = &[$(stringify!($variant)),+];
         }
 
         impl Default for $status {
@@ -57,6 +82,12 @@ macro_rules! define_entity_database {
         pub struct $db_struct;
 
         impl $db_struct {
+            /// Table holding this entity
+            pub const TABLE: &'static str = $table;
+
+            /// CREATE TABLE statement of the current schema
+            pub const CREATE_SQL: &'static str = $sql;
+
             /// Initialize table
             pub fn init_table(conn: &rusqlite::Connection) -> anyhow::Result<()> {
                 conn.execute($sql, [])?;
@@ -120,6 +151,31 @@ macro_rules! define_entity_database {
                 }
             }
 
+            /// Get the entity listing `alias` in its metadata aliases
+            pub fn get_by_alias(conn: &rusqlite::Connection, alias: &str) -> anyhow::Result<Option<$entity>> {
+                let mut columns = vec!["id".to_string()];
+                $(columns.push(stringify!($key_field).to_string());)+
+                $(columns.push(stringify!($field).to_string());)*
+                columns.extend(vec!["metadata".to_string(), "created_at".to_string(), "status".to_string()]);
+
+                let sql = format!(
+                    "SELECT {} FROM {} WHERE json_valid(metadata) \
+                     AND EXISTS (SELECT 1 FROM json_each(metadata, '$.aliases') WHERE value = ?1)",
+                    columns.join(", "),
+                    $table
+                );
+
+                let mut stmt = conn.prepare(&sql)?;
+                let mut rows = stmt.query_map([alias], |row| {
+                    Self::row_to_entity(row)
+                })?;
+
+                match rows.next() {
+                    Some(entity) => Ok(Some(entity?)),
+                    None => Ok(None),
+                }
+            }
+
             /// Get ID by logical key(s) - for relations
             pub fn get_id_by_key(conn: &rusqlite::Connection, $($key_field: &$key_type),+) -> anyhow::Result<Option<i32>> {
                 let mut where_clauses = Vec::new();
@@ -144,19 +200,21 @@ macro_rules! define_entity_database {
 
             /// List all entities
             pub fn list(conn: &rusqlite::Connection) -> anyhow::Result<Vec<$entity>> {
-                let mut columns = vec!["id".to_string()];
-                $(columns.push(stringify!($key_field).to_string());)+
-                $(columns.push(stringify!($field).to_string());)*
-                columns.extend(vec!["metadata".to_string(), "created_at".to_string(), "status".to_string()]);
+                Self::list_filtered(conn, &$crate::listing::ListArgs::default())
+            }
 
-                let sql = format!(
-                    "SELECT {} FROM {} ORDER BY created_at DESC",
-                    columns.join(", "),
-                    $table
-                );
+            /// List entities matching --where filters, in --sort order, up to --limit
+            pub fn list_filtered(conn: &rusqlite::Connection, options: &$crate::listing::ListArgs) -> anyhow::Result<Vec<$entity>> {
+                let mut columns = vec!["id"];
+                $(columns.push(stringify!($key_field));)+
+                $(columns.push(stringify!($field));)*
+                columns.extend(["metadata", "created_at", "status"]);
+
+                let kind = stringify!($entity).to_lowercase();
+                let (sql, params) = options.select_sql(conn, &kind, $table, &columns.join(", "), &columns, "created_at DESC")?;
 
                 let mut stmt = conn.prepare(&sql)?;
-                let entity_iter = stmt.query_map([], |row| Self::row_to_entity(row))?;
+                let entity_iter = stmt.query_map(rusqlite::params_from_iter(params), |row| Self::row_to_entity(row))?;
 
                 let mut entities = Vec::new();
                 for entity in entity_iter {
@@ -190,6 +248,21 @@ macro_rules! define_entity_database {
                 Ok(())
             }
 
+            /// Change the logical key(s) of an entity, keeping its ID (and so its relations)
+            pub fn update_key(conn: &rusqlite::Connection, id: i32, $($key_field: &$key_type),+) -> anyhow::Result<()> {
+                let mut key_assignments = Vec::new();
+                $(key_assignments.push(format!("{} = ?", stringify!($key_field)));)+
+
+                let sql = format!(
+                    "UPDATE {} SET {} WHERE id = ?",
+                    $table,
+                    key_assignments.join(", ")
+                );
+
+                conn.execute(&sql, rusqlite::params![$($key_field,)+ id])?;
+                Ok(())
+            }
+
             /// Delete entity by ID
             pub fn delete(conn: &rusqlite::Connection, id: i32) -> anyhow::Result<()> {
                 let sql = format!("DELETE FROM {} WHERE id = ?1", $table);
@@ -257,7 +330,12 @@ macro_rules! define_entity_methods {
     ) => {
         impl $entity {
             /// Create new entity with set args
-            pub fn create_new($($key_field: $key_type,)+ set_args: Vec<(String, String)>) -> anyhow::Result<Self> {
+            pub fn create_new($($key_field: $key_type,)+ set_args: Vec<(String, String)>, set_json: Vec<(String, String)>) -> anyhow::Result<Self> {
+                let provided: Vec<&str> = set_args.iter().chain(&set_json)
+                    .map(|(key, _)| $crate::metadata::top_level_key(key))
+                    .collect();
+                $crate::schema::check_required(&Self::schema_kind(), &provided)?;
+
                 let mut entity = Self {
                     id: 0,  // Will be set by database
                     $($key_field,)+
@@ -267,54 +345,138 @@ macro_rules! define_entity_methods {
                     status: Default::default(),
                 };
 
+                entity.apply_initial_status()?;
                 entity.process_set_args(set_args)?;
+                entity.process_json_args(set_json)?;
                 Ok(entity)
             }
 
+            /// Start from the [status.<entity>] initial state, when configured
+            pub fn apply_initial_status(&mut self) -> anyhow::Result<()> {
+                if let Some(initial) = $crate::status::initial_status(&Self::schema_kind()) {
+                    self.status = serde_json::from_value(serde_json::Value::String(initial.to_string()))?;
+                }
+                Ok(())
+            }
+
             /// Process --set arguments
             pub fn process_set_args(&mut self, set_args: Vec<(String, String)>) -> anyhow::Result<()> {
+                let kind = Self::schema_kind();
                 for (key, value) in set_args {
+                    // List edits (themes+=power, themes-=betrayal) and nested keys (stats.strength=12)
+                    if let Some((path, op)) = $crate::metadata::structured_key(&key) {
+                        let path = if path == "alias" { $crate::relations::ALIASES_KEY } else { path };
+                        Self::check_metadata_key(&kind, path)?;
+                        let json_value = $crate::schema::coerce_field(&kind, path, &value)?
+                            .unwrap_or(serde_json::Value::String(value));
+                        $crate::metadata::apply(&mut self.metadata, path, op, json_value)?;
+                        continue;
+                    }
+
+                    // Keys declared in [schema.<entity>] are validated and typed
+                    let typed = $crate::schema::coerce_field(&kind, &key, &value)?;
                     match key.as_str() {
                         $(stringify!($field) => {
-                            // Try to parse as number first, then as string
-                            let json_value = if let Ok(num) = value.parse::<i32>() {
+                            // Without a schema, try to parse as number first, then as string
+                            let json_value = typed.unwrap_or_else(|| if let Ok(num) = value.parse::<i32>() {
                                 serde_json::Value::Number(serde_json::Number::from(num))
                             } else if let Ok(num) = value.parse::<f64>() {
                                 serde_json::Value::Number(serde_json::Number::from_f64(num).unwrap())
                             } else {
                                 serde_json::Value::String(value)
-                            };
-                            self.$field = serde_json::from_value(json_value)?;
+                            });
+                            self.$field = serde_json::from_value(json_value)
+                                .map_err(|e| anyhow::anyhow!("Invalid value for field '{}': {}", key, e))?;
                         })*
                         "status" => {
-                            let json_str = format!("\"{}\"", value);
-                            self.status = serde_json::from_str(&json_str)?;
+                            // New entities only check the vocabulary, existing ones the transition too
+                            let current = (self.id != 0).then(|| self.status.as_str().to_string());
+                            $crate::status::check_status(&kind, Self::STATUS_VALUES, current.as_deref(), &value)?;
+                            self.status = serde_json::from_value(serde_json::Value::String(value))?;
+                        }
+                        "alias" | "aliases" => {
+                            $crate::relations::set_aliases(&mut self.metadata, $crate::relations::parse_aliases(&value));
                         }
                         // Relations handled in extension macro
                         _ => {
-                            self.metadata.insert(key, serde_json::Value::String(value));
+                            self.metadata.insert(key, typed.unwrap_or(serde_json::Value::String(value)));
+                        }
+                    }
+                }
+                Ok(())
+            }
+
+            /// Process --set-json arguments: built-in fields are deserialized, other keys
+            /// (dotted paths included) store the JSON value in metadata
+            pub fn process_json_args(&mut self, set_json: Vec<(String, String)>) -> anyhow::Result<()> {
+                let kind = Self::schema_kind();
+                for (key, text) in set_json {
+                    let json_value = $crate::metadata::parse_json(&key, &text)?;
+                    match key.as_str() {
+                        $(stringify!($field) => {
+                            self.$field = serde_json::from_value(json_value)
+                                .map_err(|e| anyhow::anyhow!("Invalid value for field '{}': {}", key, e))?;
+                        })*
+                        _ => {
+                            Self::check_metadata_key(&kind, &key)?;
+                            $crate::metadata::apply(&mut self.metadata, &key, $crate::metadata::SetOp::Assign, json_value)?;
                         }
                     }
                 }
                 Ok(())
             }
 
+            /// Process --unset arguments, refusing built-in and required fields
+            pub fn unset_fields(&mut self, keys: &[String]) -> anyhow::Result<()> {
+                let kind = Self::schema_kind();
+                for key in keys {
+                    Self::check_metadata_key(&kind, key)?;
+                    let required = $crate::schema::entity_schema(&kind)
+                        .and_then(|schema| schema.get(key.as_str()))
+                        .is_some_and(|field| field.required);
+                    if required {
+                        anyhow::bail!("Field '{}' is required for {} and cannot be unset", key, kind);
+                    }
+                    if !$crate::metadata::unset(&mut self.metadata, key)? {
+                        $crate::say!("⚠️  '{}' is not set on {} '{}'", key, kind, self.display_key());
+                    }
+                }
+                Ok(())
+            }
+
+            /// Structured edits only apply to metadata: not to columns, status or relations
+            fn check_metadata_key(kind: &str, key: &str) -> anyhow::Result<()> {
+                let top = $crate::metadata::top_level_key(key);
+                if [$(stringify!($field),)* "status"].contains(&top) {
+                    anyhow::bail!("'{}' is a built-in {} field: use --set {}=<value>", top, kind, top);
+                }
+                if $crate::relations::relation_keys_for(kind).contains(&top) {
+                    anyhow::bail!("'{}' is a relation: use --set {}=<name> or the relation command", top, top);
+                }
+                Ok(())
+            }
+
             /// Save to database
             pub fn create(&mut self) -> anyhow::Result<()> {
                 let _world_root = Self::ensure_world_context()?;
                 let conn = Self::get_database_connection()?;
-                $db_struct::init_table(&conn)?;
+                Self::check_key_available(&conn, $(&self.$key_field),+)?;
+                $crate::relations::check_aliases_available(&conn, $db_struct::TABLE, stringify!($entity), self.id, &self.metadata)?;
                 
                 self.id = $db_struct::insert(&conn, self)?;
-                println!("✅ Created {} '{}'", stringify!($entity), self.display_key());
+                $crate::say!("✅ Created {} '{}'", stringify!($entity), self.display_key());
                 Ok(())
             }
 
-            /// Get entity by logical key
+            /// Get entity by logical key, or by one of its aliases (names kept by merge)
             pub fn get($($key_field: &$key_type),+) -> anyhow::Result<Option<Self>> {
                 let _world_root = Self::ensure_world_context()?;
                 let conn = Self::get_database_connection()?;
-                $db_struct::get_by_key(&conn, $($key_field),+)
+                if let Some(entity) = $db_struct::get_by_key(&conn, $($key_field),+)? {
+                    return Ok(Some(entity));
+                }
+                let parts: Vec<String> = vec![$($key_field.to_string()),+];
+                $db_struct::get_by_alias(&conn, &parts.join(":"))
             }
 
             /// List all entities
@@ -324,12 +486,20 @@ macro_rules! define_entity_methods {
                 $db_struct::list(&conn)
             }
 
+            /// List entities with the --where/--sort/--limit options of a list command
+            pub fn list_with(options: &$crate::listing::ListArgs) -> anyhow::Result<Vec<Self>> {
+                let _world_root = Self::ensure_world_context()?;
+                let conn = Self::get_database_connection()?;
+                $db_struct::list_filtered(&conn, options)
+            }
+
             /// Update entity with set args
             pub fn update(&mut self, set_args: Vec<(String, String)>) -> anyhow::Result<()> {
                 self.process_set_args(set_args)?;
                 let conn = Self::get_database_connection()?;
+                $crate::relations::check_aliases_available(&conn, $db_struct::TABLE, stringify!($entity), self.id, &self.metadata)?;
                 $db_struct::update(&conn, self)?;
-                println!("✅ Updated {} '{}'", stringify!($entity), self.display_key());
+                $crate::say!("✅ Updated {} '{}'", stringify!($entity), self.display_key());
                 Ok(())
             }
 
@@ -340,10 +510,78 @@ macro_rules! define_entity_methods {
                 }
                 let conn = Self::get_database_connection()?;
                 $db_struct::delete(&conn, self.id)?;
-                println!("✅ Deleted {} '{}'", stringify!($entity), self.display_key());
+                $crate::say!("✅ Deleted {} '{}'", stringify!($entity), self.display_key());
+                Ok(())
+            }
+
+            /// Rename entity (change its logical key), refusing keys already in use
+            pub fn rename(&mut self, $($key_field: $key_type),+) -> anyhow::Result<()> {
+                let conn = Self::get_database_connection()?;
+                Self::check_key_available(&conn, $(&$key_field),+)?;
+                $db_struct::update_key(&conn, self.id, $(&$key_field),+)?;
+
+                let old_key = self.display_key();
+                $(self.$key_field = $key_field;)+
+                $crate::say!("✅ Renamed {} '{}' → '{}'", stringify!($entity), old_key, self.display_key());
                 Ok(())
             }
 
+            /// Merge a duplicate into this entity: metadata is combined using `policy`, relation
+            /// rows are re-pointed here, the duplicate is deleted and its key kept as an alias
+            pub fn merge(&mut self, other: Self, policy: $crate::relations::MergePolicy) -> anyhow::Result<()> {
+                if self.id == other.id {
+                    anyhow::bail!("Cannot merge {} '{}' into itself", stringify!($entity), self.display_key());
+                }
+                $crate::relations::merge_metadata(&mut self.metadata, &other.metadata, &other.display_key(), policy)?;
+
+                let conn = Self::get_database_connection()?;
+                let tx = $crate::database::savepoint(&conn)?;
+                let (moved, duplicates) = $crate::relations::repoint_relations(&tx, $db_struct::TABLE, other.id, self.id)?;
+                $db_struct::delete(&tx, other.id)?;
+                $db_struct::update(&tx, self)?;
+                tx.commit()?;
+
+                if moved > 0 || duplicates > 0 {
+                    $crate::say!("🔗 Moved {} relation(s), dropped {} duplicate(s)", moved, duplicates);
+                }
+                $crate::say!("✅ Merged {} '{}' into '{}'", stringify!($entity), other.display_key(), self.display_key());
+                Ok(())
+            }
+
+            /// Fail when another entity already uses the given logical key
+            fn check_key_available(conn: &rusqlite::Connection, $($key_field: &$key_type),+) -> anyhow::Result<()> {
+                let parts: Vec<String> = vec![$($key_field.to_string()),+];
+                if $db_struct::get_id_by_key(conn, $($key_field),+)?.is_some() {
+                    return Err($crate::output::CliError::error(
+                        $crate::output::ErrorCode::AlreadyExists,
+                        format!("{} '{}' already exists", stringify!($entity), parts.join(":")),
+                    ));
+                }
+                if let Some(owner) = $db_struct::get_by_alias(conn, &parts.join(":"))? {
+                    return Err($crate::output::CliError::error(
+                        $crate::output::ErrorCode::AlreadyExists,
+                        format!("'{}' is already an alias of {} '{}'", parts.join(":"), stringify!($entity), owner.display_key()),
+                    ));
+                }
+                Ok(())
+            }
+
+            /// Entity kind used for [schema.<kind>] lookups
+            fn schema_kind() -> String {
+                stringify!($entity).to_lowercase()
+            }
+
+            /// Print the effective schema: built-in columns plus [schema.<kind>] fields
+            pub fn show_schema() -> anyhow::Result<()> {
+                let _world_root = Self::ensure_world_context()?;
+                let builtin = vec![
+                    $((stringify!($key_field), concat!(stringify!($key_type), " (key)")),)+
+                    $((stringify!($field), stringify!($field_type)),)*
+                ];
+                let statuses = $crate::status::allowed_statuses(&Self::schema_kind(), Self::STATUS_VALUES);
+                $crate::schema::show_entity_schema(&Self::schema_kind(), &builtin, &statuses)
+            }
+
             /// Display key for user feedback  
             pub fn display_key(&self) -> String {
                 // For single key, just return it. For composite keys, join with ':'
@@ -353,14 +591,16 @@ macro_rules! define_entity_methods {
             }
 
             // Utility methods
-            fn get_database_connection() -> anyhow::Result<rusqlite::Connection> {
-                let db_path = crate::world::WorldConfig::get_database_path()?;
-                rusqlite::Connection::open(&db_path).map_err(|e| anyhow::anyhow!("Database connection failed: {}", e))
+            fn get_database_connection() -> anyhow::Result<std::rc::Rc<rusqlite::Connection>> {
+                $crate::database::world_connection()
             }
 
             fn ensure_world_context() -> anyhow::Result<std::path::PathBuf> {
                 crate::world::WorldConfig::get_world_root()
-                    .map_err(|_| anyhow::anyhow!("Not in a multiverse project directory. Run 'multiverse world init <name>' to create one."))
+                    .map_err(|_| $crate::output::CliError::error(
+                        $crate::output::ErrorCode::NotInWorld,
+                        "Not in a multiverse project directory. Run 'multiverse world init <name>' to create one.",
+                    ))
             }
         }
     };
@@ -3,6 +3,7 @@ use clap::Subcommand;
 #[derive(Subcommand)]
 pub enum EpisodeCommands {
     /// Create a new episode in a story
+    #[command(after_help = crate::relations::relation_set_help("episode"))]
     Create {
         /// Story name
         #[arg(short, long)]
@@ -10,6 +11,9 @@ pub enum EpisodeCommands {
         /// Set any field (--set display_name="Name" --set status="Active" --set age=25 --set faction=rebels)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
     },
     
     /// List episodes in a story
@@ -17,6 +21,8 @@ pub enum EpisodeCommands {
         /// Story name
         #[arg(short, long)]
         story: String,
+        #[command(flatten)]
+        options: crate::listing::ListArgs,
     },
     
     /// Show episode details
@@ -27,6 +33,9 @@ pub enum EpisodeCommands {
         /// Episode number
         #[arg(short, long)]
         number: i32,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
     },
     
     /// Delete an episode
@@ -40,9 +49,13 @@ pub enum EpisodeCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+        /// What to do with existing relations: refuse, detach or cascade
+        #[arg(long, value_enum, default_value_t = crate::relations::DeleteMode::Refuse)]
+        on_relations: crate::relations::DeleteMode,
     },
 
     /// Update an existing episode
+    #[command(after_help = crate::relations::relation_set_help("episode"))]
     Update {
         /// Story name
         #[arg(short, long)]
@@ -53,7 +66,26 @@ pub enum EpisodeCommands {
         /// Set any field (--set display_name="Name" --set status="Active" --set age=25 --set faction=rebels)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
+        /// Remove a metadata field (--unset age --unset stats.strength)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+    /// Renumber an episode, moving its file and keeping its relations
+    Rename {
+        /// Story name
+        #[arg(short, long)]
+        story: String,
+        /// Current episode number
+        #[arg(short, long)]
+        number: i32,
+        /// New episode number
+        new_number: i32,
     },
+    /// Show the field schema of episodes (built-in fields and [schema.episode] in config.toml)
+    Schema,
 }
 
 /// Parse a single key-value pair for --set flag
@@ -1,44 +1,54 @@
 use super::cli::EpisodeCommands;
 use super::models::{Episode, EpisodeStatus};
-use crate::relations::{process_relations, EntityType, separate_relation_fields};
+use crate::relations::{process_relations, EntityType, separate_relation_fields, relation_keys_for, show_entity_relations, DeleteMode, check_entity_delete, release_entity, entity_relations};
 use anyhow::Result;
+use crate::say;
 
 pub fn handle_episode_command(command: EpisodeCommands) -> Result<()> {
     match command {
-        EpisodeCommands::Create { story, set } => {
-            handle_create(story, set)
+        EpisodeCommands::Create { story, set, set_json } => {
+            handle_create(story, set, set_json)
         }
-        EpisodeCommands::List { story } => handle_list(story),
-        EpisodeCommands::Info { story, number } => handle_info(story, number),
-        EpisodeCommands::Delete { story, number, force } => handle_delete(story, number, force),
-        EpisodeCommands::Update { story, number, set } => handle_update(story, number, set),
+        EpisodeCommands::List { story, options } => handle_list(story, options),
+        EpisodeCommands::Schema => Episode::show_schema(),
+        EpisodeCommands::Info { story, number, at } => handle_info(story, number, at),
+        EpisodeCommands::Delete { story, number, force, on_relations } => handle_delete(story, number, force, on_relations),
+        EpisodeCommands::Rename { story, number, new_number } => handle_rename(story, number, new_number),
+        EpisodeCommands::Update { story, number, set, set_json, unset } => handle_update(story, number, set, set_json, unset),
     }
 }
 
-fn handle_update(story_name: String, episode_number: i32, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("🔄 Updating episode {} in story '{}'", episode_number, story_name);
+fn handle_update(story_name: String, episode_number: i32, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>, unset: Vec<String>) -> Result<()> {
+    say!("🔄 Updating episode {} in story '{}'", episode_number, story_name);
 
     let mut episode = Episode::get(&story_name, &episode_number)?
-        .ok_or_else(|| anyhow::anyhow!("Episode {} not found in story '{}'", episode_number, story_name))?;
+        .ok_or_else(|| episode_not_found(&story_name, episode_number))?;
 
     let episode_id = format!("{}:{}", story_name, episode_number);
     let regular_fields = process_relations(EntityType::Episode(episode_id), set_args)?;
+    episode.unset_fields(&unset)?;
+    episode.process_json_args(set_json)?;
     episode.update(regular_fields)?;
 
-    println!("✅ Episode {} updated!", episode.number);
-    handle_info(story_name, episode_number)?;
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Episode(format!("{}:{}", episode.story, episode.number)), None)?;
+        return crate::output::emit_entity("episode", Some("updated"), &episode, Some(relations));
+    }
+
+    say!("✅ Episode {} updated!", episode.number);
+    handle_info(story_name, episode_number, None)?;
 
     Ok(())
 }
 
-fn handle_create(story_name: String, set: Vec<(String, String)>) -> Result<()> {
+fn handle_create(story_name: String, set: Vec<(String, String)>, set_json: Vec<(String, String)>) -> Result<()> {
     use crate::world::WorldConfig;
     use anyhow::Context;
     
-    println!("📄 Creating episode in story '{}'...", story_name);
+    say!("📄 Creating episode in story '{}'...", story_name);
     
     // Separate relation fields from regular fields  
-    let relation_keys = ["character"];
+    let relation_keys = relation_keys_for("episode");
     let (relation_fields, regular_fields) = separate_relation_fields(set.clone(), &relation_keys);
     
     let mut episode = Episode::new_with_next_number(story_name.clone())?;
@@ -47,6 +57,7 @@ fn handle_create(story_name: String, set: Vec<(String, String)>) -> Result<()> {
     if !regular_fields.is_empty() {
         episode.process_set_args(regular_fields.clone())?;
     }
+    episode.process_json_args(set_json)?;
     
     episode.create_with_file()?;
     
@@ -56,44 +67,67 @@ fn handle_create(story_name: String, set: Vec<(String, String)>) -> Result<()> {
         process_relations(EntityType::Episode(episode_id), relation_fields)?;
     }
     
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Episode(format!("{}:{}", episode.story, episode.number)), None)?;
+        return crate::output::emit_entity("episode", Some("created"), &episode, Some(relations));
+    }
+    
     let world_root = WorldConfig::get_world_root()
         .context("Not in a multiverse project directory")?;
     let story = crate::story::Story::get(&story_name.to_string())?
-        .ok_or_else(|| anyhow::anyhow!("Story '{}' not found", story_name))?;
+        .ok_or_else(|| crate::output::not_found("Story", &story_name))?;
     let story_path = story.get_story_path(&world_root);
-    let episode_filename = format!("{:03}.md", episode.number);
-    let episode_path = story_path.join(&episode_filename);
+    let episode_path = episode.get_episode_path(&story_path, &story.numbering_format()?)?;
     
-    println!("✅ Episode {} created!", episode.number);
-    println!("   Story: {}", story_name);
-    println!("   File: {}", episode_path.display());
+    say!("✅ Episode {} created!", episode.number);
+    say!("   Story: {}", story_name);
+    say!("   File: {}", episode_path.display());
     
     // Extract title from regular fields if provided
     if let Some((_, title)) = regular_fields.iter().find(|(key, _)| key == "title") {
-        println!("   Title: {}", title);
+        say!("   Title: {}", title);
     }
     
     Ok(())
 }
 
-fn handle_list(story_name: String) -> Result<()> {
-    let episodes = Episode::list_for_story(&story_name)?;
+fn handle_list(story_name: String, mut options: crate::listing::ListArgs) -> Result<()> {
+    // Episodes of the story, in reading order unless --sort says otherwise
+    options.filters.push(crate::listing::Filter { key: "story".to_string(), value: story_name.clone(), negate: false });
+    if options.sort.is_none() {
+        options.sort = Some("number".to_string());
+    }
+    let episodes = Episode::list_with(&options)?;
+    
+    if crate::output::is_structured() {
+        return crate::output::emit_list(&episodes, &options.fields);
+    }
+    
+    if !options.fields.is_empty() {
+        return crate::listing::print_fields(&episodes, &options.fields);
+    }
     
     if episodes.is_empty() {
-        println!("📄 No episodes found in story '{}'", story_name);
-        println!("   Use 'multiverse episode create --story {} --set title=<title>' to create one", story_name);
+        say!("📄 No episodes found in story '{}'", story_name);
+        say!("   Use 'multiverse episode create --story {} --set title=<title>' to create one", story_name);
         return Ok(());
     }
     
-    println!("📄 Episodes in story '{}':", story_name);
+    say!("📄 Episodes in story '{}':", story_name);
+    
+    let numbering = match crate::story::Story::get(&story_name)? {
+        Some(story) => story.numbering_format()?,
+        None => crate::story::DEFAULT_NUMBERING.to_string(),
+    };
     
     for episode in episodes {
-        let status_emoji = match episode.status {
+        let status_emoji = crate::status::status_emoji("episode", episode.status.as_str(), match episode.status {
             EpisodeStatus::Draft => "📝",
             EpisodeStatus::InProgress => "⏳",
             EpisodeStatus::Review => "👀",
             EpisodeStatus::Published => "✅",
-        };
+            EpisodeStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
         
         let title_str = if !episode.title.is_empty() {
             episode.title.as_str()
@@ -107,9 +141,9 @@ fn handle_list(story_name: String) -> Result<()> {
             String::new()
         };
         
-        println!("   {} {:03}. {}{}", 
+        say!("   {} {}. {}{}", 
             status_emoji, 
-            episode.number, 
+            crate::story::format_episode_number(&numbering, episode.number)?, 
             title_str,
             word_count_str
         );
@@ -118,47 +152,93 @@ fn handle_list(story_name: String) -> Result<()> {
     Ok(())
 }
 
-fn handle_info(story_name: String, episode_number: i32) -> Result<()> {
+fn handle_info(story_name: String, episode_number: i32, at: Option<String>) -> Result<()> {
     let episode = Episode::get(&story_name, &episode_number)?
-        .ok_or_else(|| anyhow::anyhow!("Episode {} not found in story '{}'", episode_number, story_name))?;
+        .ok_or_else(|| episode_not_found(&story_name, episode_number))?;
+    
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Episode(format!("{}:{}", episode.story, episode.number)), at.as_deref())?;
+        return crate::output::emit_entity("episode", None, &episode, Some(relations));
+    }
     
-    println!("📄 Episode {}: {}", episode.number, story_name);
+    say!("📄 Episode {}: {}", episode.number, story_name);
     
     if !episode.title.is_empty() {
-        println!("   Title: {}", episode.title);
+        say!("   Title: {}", episode.title);
     }
     
-    println!("   Status: {:?}", episode.status);
-    println!("   Word Count: {}", episode.word_count);
-    println!("   Created: {}", episode.created_at.format("%Y-%m-%d %H:%M"));
+    say!("   Status: {:?}", episode.status);
+    say!("   Word Count: {}", episode.word_count);
+    say!("   Created: {}", episode.created_at.format("%Y-%m-%d %H:%M"));
     
     // Show metadata
     if !episode.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &episode.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
+    show_entity_relations(&EntityType::Episode(format!("{}:{}", episode.story, episode.number)), at.as_deref())?;
+    
     Ok(())
 }
 
-fn handle_delete(story_name: String, episode_number: i32, force: bool) -> Result<()> {
+fn handle_delete(story_name: String, episode_number: i32, force: bool, on_relations: DeleteMode) -> Result<()> {
     let episode = Episode::get(&story_name, &episode_number)?
-        .ok_or_else(|| anyhow::anyhow!("Episode {} not found in story '{}'", episode_number, story_name))?;
+        .ok_or_else(|| episode_not_found(&story_name, episode_number))?;
     
+    let entity = EntityType::Episode(format!("{}:{}", story_name, episode_number));
+    let relations = check_entity_delete(&entity, on_relations)?;
+
     if !force {
-        println!("⚠️  Are you sure you want to delete episode {} from story '{}'?", episode_number, story_name);
-        println!("   This will permanently delete the episode file and database entry");
-        println!("   Use --force to skip this confirmation");
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("delete episode {} from story '{}'", episode_number, story_name)));
+        }
+        say!("⚠️  Are you sure you want to delete episode {} from story '{}'?", episode_number, story_name);
+        say!("   This will permanently delete the episode file and database entry");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will be removed as well", relations.len());
+        }
+        say!("   Use --force to skip this confirmation");
         return Ok(());
     }
     
-    println!("🗑️  Deleting episode {} from story '{}'...", episode_number, story_name);
+    say!("🗑️  Deleting episode {} from story '{}'...", episode_number, story_name);
     
+    release_entity(&entity, on_relations)?;
     episode.delete_with_file(force)?;
     
-    println!("✅ Episode {} deleted!", episode_number);
+    say!("✅ Episode {} deleted!", episode_number);
+
+    if crate::output::is_structured() {
+        return crate::output::emit_entity("episode", Some("deleted"), &episode, Some(relations));
+    }
     
     Ok(())
 }
+
+fn handle_rename(story_name: String, episode_number: i32, new_number: i32) -> Result<()> {
+    let mut episode = Episode::get(&story_name, &episode_number)?
+        .ok_or_else(|| episode_not_found(&story_name, episode_number))?;
+
+    say!("✏️  Renumbering episode {} in story '{}' to {}...", episode_number, story_name, new_number);
+
+    episode.renumber_with_file(new_number)?;
+
+    say!("✅ Episode {} is now episode {}!", episode_number, new_number);
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Episode(format!("{}:{}", episode.story, episode.number)), None)?;
+        return crate::output::emit_entity("episode", Some("renamed"), &episode, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn episode_not_found(story_name: &str, episode_number: i32) -> anyhow::Error {
+    crate::output::CliError::error(
+        crate::output::ErrorCode::NotFound,
+        format!("Episode {} not found in story '{}'", episode_number, story_name),
+    )
+}
@@ -3,6 +3,7 @@
 
 use crate::define_complete_entity;
 use serde::{Deserialize, Serialize};
+use crate::say;
 
 // Generate complete Episode entity with composite key
 define_complete_entity!(
@@ -34,25 +35,57 @@ define_complete_entity!(
 
 // Custom implementations for Episode
 impl Episode {
-    /// Get the display title (fallback to episode number)
-    pub fn display_title(&self) -> String {
-        if !self.title.is_empty() {
-            format!("{:03}. {}", self.number, self.title)
+    /// Get the display title (fallback to episode number), numbered with the story type's format
+    pub fn display_title(&self, numbering: &str) -> anyhow::Result<String> {
+        let number = crate::story::format_episode_number(numbering, self.number)?;
+        Ok(if !self.title.is_empty() {
+            format!("{}. {}", number, self.title)
         } else {
-            format!("{:03}. Episode {}", self.number, self.number)
+            format!("{}. Episode {}", number, self.number)
+        })
+    }
+
+    /// Episode file name for a number, e.g. `001.md` with the default `{:03}` numbering
+    pub fn file_name(number: i32, numbering: &str) -> anyhow::Result<String> {
+        Ok(format!("{}.md", crate::story::format_episode_number(numbering, number)?))
+    }
+
+    /// Path of this episode's file within the story directory. When the story type's
+    /// numbering changed since the file was written, the existing file is found under the
+    /// default `{:03}` name or as the only `.md` file whose name holds just this number.
+    pub fn get_episode_path(&self, story_path: &std::path::Path, numbering: &str) -> anyhow::Result<std::path::PathBuf> {
+        let path = story_path.join(Self::file_name(self.number, numbering)?);
+        if path.exists() {
+            return Ok(path);
         }
+
+        let legacy = story_path.join(Self::file_name(self.number, crate::story::DEFAULT_NUMBERING)?);
+        if legacy.exists() {
+            return Ok(legacy);
+        }
+
+        let mut existing = Self::files_numbered(story_path, self.number);
+        Ok(if existing.len() == 1 { existing.remove(0) } else { path })
     }
 
-    /// Get episode file path within story directory
-    pub fn get_episode_path(&self, story_path: &std::path::Path) -> std::path::PathBuf {
-        let filename = format!("{:03}.md", self.number);
-        story_path.join(filename)
+    /// `.md` files of a story directory whose name has a single run of digits equal to `number`
+    fn files_numbered(story_path: &std::path::Path, number: i32) -> Vec<std::path::PathBuf> {
+        let Ok(entries) = std::fs::read_dir(story_path) else { return Vec::new() };
+        entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().and_then(|extension| extension.to_str()) == Some("md"))
+            .filter(|path| {
+                let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else { return false };
+                let mut runs = stem.split(|c: char| !c.is_ascii_digit()).filter(|run| !run.is_empty());
+                matches!((runs.next(), runs.next()), (Some(run), None) if run.parse::<i32>() == Ok(number))
+            })
+            .collect()
     }
 
     /// Create a new episode with the next sequential number for the story
     pub fn new_with_next_number(story_name: String) -> anyhow::Result<Self> {
         let next_number = Self::get_next_episode_number(&story_name)?;
-        let episode = Episode {
+        let mut episode = Episode {
             id: 0, // Will be set by database
             story: story_name,
             number: next_number,
@@ -62,6 +95,7 @@ impl Episode {
             created_at: chrono::Utc::now(),
             status: EpisodeStatus::Draft,
         };
+        episode.apply_initial_status()?;
         Ok(episode)
     }
 
@@ -82,7 +116,7 @@ impl Episode {
         
         // Verify the story exists
         let story = crate::story::Story::get(&self.story)?
-            .ok_or_else(|| anyhow::anyhow!("Story '{}' not found", self.story))?;
+            .ok_or_else(|| crate::output::not_found("Story", &self.story))?;
 
         // Create the episode in database first
         self.create()?;
@@ -91,15 +125,16 @@ impl Episode {
         let world_root = WorldConfig::get_world_root()
             .context("Not in a multiverse project directory")?;
         let story_path = story.get_story_path(&world_root);
-        let episode_path = self.get_episode_path(&story_path);
+        let episode_path = story_path.join(Self::file_name(self.number, &story.numbering_format()?)?);
         
-        // Create episode content
+        // Create episode content; the file is written once the command commits
         let content = self.generate_episode_content(&story)?;
-        std::fs::write(&episode_path, content)
-            .with_context(|| format!("Failed to write episode file: {}", episode_path.display()))?;
-        
-        println!("📄 Created episode file: {}", episode_path.display());
-        Ok(())
+        crate::database::after_commit(move || {
+            std::fs::write(&episode_path, content)
+                .with_context(|| format!("Failed to write episode file: {}", episode_path.display()))?;
+            say!("📄 Created episode file: {}", episode_path.display());
+            Ok(())
+        })
     }
 
     /// Generate initial episode content
@@ -134,24 +169,56 @@ impl Episode {
         
         // Get the story and episode path
         let story = crate::story::Story::get(&self.story)?
-            .ok_or_else(|| anyhow::anyhow!("Story '{}' not found", self.story))?;
+            .ok_or_else(|| crate::output::not_found("Story", &self.story))?;
         
         let world_root = WorldConfig::get_world_root()
             .context("Not in a multiverse project directory")?;
         let story_path = story.get_story_path(&world_root);
-        let episode_path = self.get_episode_path(&story_path);
+        let episode_path = self.get_episode_path(&story_path, &story.numbering_format()?)?;
         
         // Delete from database first
         self.delete(force)?;
         
-        // Delete episode file if it exists
-        if episode_path.exists() {
-            std::fs::remove_file(&episode_path)
-                .with_context(|| format!("Failed to delete episode file: {}", episode_path.display()))?;
-            println!("🗑️ Deleted episode file: {}", episode_path.display());
+        // Delete episode file if it exists, once the command commits
+        crate::database::after_commit(move || {
+            if episode_path.exists() {
+                std::fs::remove_file(&episode_path)
+                    .with_context(|| format!("Failed to delete episode file: {}", episode_path.display()))?;
+                say!("🗑️ Deleted episode file: {}", episode_path.display());
+            }
+            Ok(())
+        })
+    }
+
+    /// Renumber episode within its story, moving its file along
+    pub fn renumber_with_file(&mut self, new_number: i32) -> anyhow::Result<()> {
+        use crate::world::WorldConfig;
+        use anyhow::Context;
+
+        let story = crate::story::Story::get(&self.story)?
+            .ok_or_else(|| crate::output::not_found("Story", &self.story))?;
+
+        let world_root = WorldConfig::get_world_root()
+            .context("Not in a multiverse project directory")?;
+        let story_path = story.get_story_path(&world_root);
+        let numbering = story.numbering_format()?;
+        let old_path = self.get_episode_path(&story_path, &numbering)?;
+        let new_path = story_path.join(Self::file_name(new_number, &numbering)?);
+        if new_path.exists() {
+            anyhow::bail!("Episode file already exists: {}", new_path.display());
         }
-        
-        Ok(())
+
+        let story_name = self.story.clone();
+        self.rename(story_name, new_number)?;
+
+        crate::database::after_commit(move || {
+            if old_path.exists() {
+                std::fs::rename(&old_path, &new_path)
+                    .with_context(|| format!("Failed to move episode file to {}", new_path.display()))?;
+                say!("📄 Moved episode file: {}", new_path.display());
+            }
+            Ok(())
+        })
     }
 
     /// Get episodes for a specific story
@@ -284,9 +351,14 @@ impl Episode {
         let episode_number: i32 = parts[1].parse()
             .map_err(|_| anyhow::anyhow!("Invalid episode number: '{}'", parts[1]))?;
         
+        // The story part may be an alias
+        let story_id = crate::story::Story::resolve_id(story_name)?;
+
         let conn = Self::get_database_connection()?;
-        let mut stmt = conn.prepare("SELECT id FROM episodes WHERE story = ? AND number = ?")?;
-        let id: i32 = stmt.query_row([story_name, &episode_number.to_string()], |row| {
+        let mut stmt = conn.prepare(
+            "SELECT e.id FROM episodes e JOIN stories s ON s.name = e.story WHERE s.id = ? AND e.number = ?"
+        )?;
+        let id: i32 = stmt.query_row([&story_id, &episode_number.to_string()], |row| {
             row.get(0)
         }).map_err(|_| anyhow::anyhow!(
             "Episode not found: story '{}', episode {}", story_name, episode_number
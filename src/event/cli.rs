@@ -3,16 +3,23 @@ use clap::Subcommand;
 #[derive(Subcommand)]
 pub enum EventCommands {
     /// Create a new event with flexible metadata
+    #[command(after_help = crate::relations::relation_set_help("event"))]
     Create {
         /// Event name (unique identifier)
         name: String,
         /// Set metadata field (can be used multiple times: --set title="Event Name" --set date="3A/2 Lum 124 DF")
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
     },
     
     /// List events in current world
-    List,
+    List {
+        #[command(flatten)]
+        options: crate::listing::ListArgs,
+    },
     
     /// List events in chronological order (using timeline dates)
     Timeline,
@@ -21,6 +28,9 @@ pub enum EventCommands {
     Info {
         /// Event name
         name: String,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
     },
     
     /// Delete an event
@@ -30,16 +40,48 @@ pub enum EventCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+        /// What to do with existing relations: refuse, detach or cascade
+        #[arg(long, value_enum, default_value_t = crate::relations::DeleteMode::Refuse)]
+        on_relations: crate::relations::DeleteMode,
     },
 
     /// Update an existing event
+    #[command(after_help = crate::relations::relation_set_help("event"))]
     Update {
         /// Event name
         name: String,
         /// Set metadata field (can be used multiple times: --set title="New Name" --set date="new date")
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
+        /// Remove a metadata field (--unset age --unset stats.strength)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+    /// Rename an event, keeping its relations
+    Rename {
+        /// Current event name
+        name: String,
+        /// New event name
+        new_name: String,
+    },
+    /// Merge a duplicate event into another one, moving its relations
+    Merge {
+        /// Event to keep
+        keep: String,
+        /// Duplicate event to merge in and delete (its name becomes an alias)
+        drop: String,
+        /// Metadata set differently on both: keep the survivor's value, take the duplicate's, or fail
+        #[arg(long, value_enum, default_value_t = crate::relations::MergePolicy::Keep)]
+        on_conflict: crate::relations::MergePolicy,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
     },
+    /// Show the field schema of events (built-in fields and [schema.event] in config.toml)
+    Schema,
 }
 
 /// Parse a single key-value pair for --set flag
@@ -162,7 +162,7 @@ pub fn delete_event(conn: &Connection, name: &str) -> Result<()> {
     ).context("Failed to delete event")?;
     
     if rows_affected == 0 {
-        return Err(anyhow::anyhow!("Event '{}' not found", name));
+        return Err(crate::output::not_found("Event", &name));
     }
     
     Ok(())
@@ -1,26 +1,30 @@
 use super::cli::EventCommands;
 use super::models::Event;
-use crate::relations::{process_relations, EntityType, separate_relation_fields};
+use crate::relations::{process_relations, EntityType, separate_relation_fields, relation_keys_for, show_entity_relations, DeleteMode, check_entity_delete, release_entity, entity_relations, MergePolicy, metadata_conflicts};
 use anyhow::Result;
+use crate::say;
 
 pub fn handle_event_command(command: EventCommands) -> Result<()> {
     match command {
-        EventCommands::Create { name, set } => {
-            handle_create(name, set)
+        EventCommands::Create { name, set, set_json } => {
+            handle_create(name, set, set_json)
         }
-        EventCommands::List => handle_list(),
+        EventCommands::List { options } => handle_list(options),
+        EventCommands::Schema => Event::show_schema(),
         EventCommands::Timeline => handle_timeline(),
-        EventCommands::Info { name } => handle_info(name),
-        EventCommands::Delete { name, force } => handle_delete(name, force),
-        EventCommands::Update { name, set } => handle_update(name, set),
+        EventCommands::Info { name, at } => handle_info(name, at),
+        EventCommands::Delete { name, force, on_relations } => handle_delete(name, force, on_relations),
+        EventCommands::Rename { name, new_name } => handle_rename(name, new_name),
+        EventCommands::Merge { keep, drop, on_conflict, force } => handle_merge(keep, drop, on_conflict, force),
+        EventCommands::Update { name, set, set_json, unset } => handle_update(name, set, set_json, unset),
     }
 }
 
-fn handle_update(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("🔄 Updating event '{name}'");
+fn handle_update(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>, unset: Vec<String>) -> Result<()> {
+    say!("🔄 Updating event '{name}'");
 
     let mut event = Event::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Event '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Event", &name))?;
 
     // Process relations and get back non-relation fields
     let regular_fields = process_relations(EntityType::Event(name.clone()), set_args)?;
@@ -36,6 +40,8 @@ fn handle_update(name: String, set_args: Vec<(String, String)>) -> Result<()> {
     // Check if date is being updated
     let date_update = regular_fields.iter().find(|(k, _)| k == "date").cloned();
     
+    event.unset_fields(&unset)?;
+    event.process_json_args(set_json)?;
     event.update(regular_fields)?;
     
     // If date was updated, recalculate sort_key
@@ -45,22 +51,27 @@ fn handle_update(name: String, set_args: Vec<(String, String)>) -> Result<()> {
         event.update(vec![])?; // This saves the current state including date_text and sort_key
     }
 
-    println!("✅ Event '{}' updated!", name);
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Event(event.name.clone()), None)?;
+        return crate::output::emit_entity("event", Some("updated"), &event, Some(relations));
+    }
+
+    say!("✅ Event '{}' updated!", name);
     show_created_event(&event)?;
 
     Ok(())
 }
 
-fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
+fn handle_create(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>) -> Result<()> {
     let title = set_args.iter()
         .find(|(k, _)| k == "title" || k == "display_name")
         .map(|(_, v)| v.as_str())
         .unwrap_or(&name);
     
-    println!("📅 Creating event '{name}' ({})", title);
+    say!("📅 Creating event '{name}' ({})", title);
 
     // Separate relation fields from regular fields  
-    let relation_keys = ["character", "location", "faction"];
+    let relation_keys = relation_keys_for("event");
     let (relation_fields, regular_fields) = separate_relation_fields(set_args, &relation_keys);
     
     // Normalize field names: title -> display_name
@@ -72,7 +83,7 @@ fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
     }
 
     // Use Event factory method with built-in validation
-    let mut event = Event::create_new(name.clone(), regular_fields)?;
+    let mut event = Event::create_new(name.clone(), regular_fields, set_json)?;
     
     // If date was provided, parse it and update sort_key BEFORE creating
     if let Some(date_value) = event.metadata.get("date") {
@@ -88,6 +99,11 @@ fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
         process_relations(EntityType::Event(name.clone()), relation_fields)?;
     }
     
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Event(event.name.clone()), None)?;
+        return crate::output::emit_entity("event", Some("created"), &event, Some(relations));
+    }
+    
     // Display success information
     show_created_event(&event)?;
     
@@ -95,135 +111,222 @@ fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
 }
 
 fn show_created_event(event: &Event) -> Result<()> {
-    println!("✅ Event '{}' created!", event.name);
-    println!("   Title: {}", event.display_name);
-    println!("   Status: {:?}", event.status);
+    say!("✅ Event '{}' created!", event.name);
+    say!("   Title: {}", event.display_name);
+    say!("   Status: {:?}", event.status);
     
     if !event.date_text.is_empty() {
-        println!("   Date: {}", event.date_text);
-        println!("   Sort key: {}", event.sort_key);
+        say!("   Date: {}", event.date_text);
+        say!("   Sort key: {}", event.sort_key);
     }
     
     // Show metadata
     if !event.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &event.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
     Ok(())
 }
 
-fn handle_list() -> Result<()> {
-    let events = Event::list()?;
+fn handle_list(options: crate::listing::ListArgs) -> Result<()> {
+    let events = Event::list_with(&options)?;
+    
+    if crate::output::is_structured() {
+        return crate::output::emit_list(&events, &options.fields);
+    }
+    
+    if !options.fields.is_empty() {
+        return crate::listing::print_fields(&events, &options.fields);
+    }
     
     if events.is_empty() {
-        println!("📅 No events found in this world");
-        println!("   Use 'multiverse event create <name> --set title=\"<title>\"' to create one");
+        say!("📅 No events found in this world");
+        say!("   Use 'multiverse event create <name> --set title=\"<title>\"' to create one");
         return Ok(());
     }
     
-    println!("📅 Events in current world:");
+    say!("📅 Events in current world:");
     
     for event in events {
-        let status_emoji = match event.status {
+        let status_emoji = crate::status::status_emoji("event", event.status.as_str(), match event.status {
             crate::event::models::EventStatus::Active => "🟢",
             crate::event::models::EventStatus::Inactive => "⚫",
             crate::event::models::EventStatus::Completed => "✅", 
             crate::event::models::EventStatus::Cancelled => "❌",
             crate::event::models::EventStatus::Pending => "⏳",
-        };
+            crate::event::models::EventStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
         
-        println!("   {} {} - \"{}\"", 
+        say!("   {} {} - \"{}\"", 
             status_emoji, 
             event.name, 
             event.display_name
         );
         
         if !event.date_text.is_empty() {
-            println!("      Date: {}", event.date_text);
+            say!("      Date: {}", event.date_text);
         }
         
         // Show key metadata fields
         if let Some(event_type) = event.metadata.get("type") {
-            println!("      Type: {}", event_type.as_str().unwrap_or("Unknown"));
+            say!("      Type: {}", event_type.as_str().unwrap_or("Unknown"));
         }
         if let Some(description) = event.metadata.get("description") {
-            println!("      {}", description.as_str().unwrap_or(""));
+            say!("      {}", description.as_str().unwrap_or(""));
         }
     }
     
     Ok(())
 }
 
-fn handle_info(name: String) -> Result<()> {
+fn handle_info(name: String, at: Option<String>) -> Result<()> {
     let event = Event::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Event '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Event", &name))?;
+    
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Event(event.name.clone()), at.as_deref())?;
+        return crate::output::emit_entity("event", None, &event, Some(relations));
+    }
     
-    println!("📅 Event: {} - \"{}\"", event.name, event.display_name);
-    println!("   Status: {:?}", event.status);
-    println!("   Created: {}", event.created_at.format("%Y-%m-%d %H:%M"));
+    say!("📅 Event: {} - \"{}\"", event.name, event.display_name);
+    say!("   Status: {:?}", event.status);
+    say!("   Created: {}", event.created_at.format("%Y-%m-%d %H:%M"));
     
     if !event.date_text.is_empty() {
-        println!("   Date: {}", event.date_text);
-        println!("   Sort key: {}", event.sort_key);
+        say!("   Date: {}", event.date_text);
+        say!("   Sort key: {}", event.sort_key);
     }
     
     if let Some(desc) = event.metadata.get("description") {
-        println!("   Description: {}", desc.as_str().unwrap_or(""));
+        say!("   Description: {}", desc.as_str().unwrap_or(""));
     }
     
     // Show metadata
     if !event.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &event.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
+    show_entity_relations(&EntityType::Event(event.name.clone()), at.as_deref())?;
+    
     Ok(())
 }
 
-fn handle_delete(name: String, force: bool) -> Result<()> {
+fn handle_delete(name: String, force: bool, on_relations: DeleteMode) -> Result<()> {
     let event = Event::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Event '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Event", &name))?;
     
+    let entity = EntityType::Event(name.clone());
+    let relations = check_entity_delete(&entity, on_relations)?;
+
     if !force {
-        println!("⚠️  Are you sure you want to delete event '{name}'?");
-        println!("   This will permanently delete the event from database");
-        println!("   Use --force to skip this confirmation");
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("delete event '{}'", name)));
+        }
+        say!("⚠️  Are you sure you want to delete event '{name}'?");
+        say!("   This will permanently delete the event from database");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will be removed as well", relations.len());
+        }
+        say!("   Use --force to skip this confirmation");
         return Ok(());
     }
     
-    println!("🗑️  Deleting event '{name}'...");
+    say!("🗑️  Deleting event '{name}'...");
     
+    release_entity(&entity, on_relations)?;
     event.delete(force)?;
     
-    println!("✅ Event '{name}' deleted!");
+    say!("✅ Event '{name}' deleted!");
+
+    if crate::output::is_structured() {
+        return crate::output::emit_entity("event", Some("deleted"), &event, Some(relations));
+    }
     
     Ok(())
 }
 
+fn handle_rename(name: String, new_name: String) -> Result<()> {
+    let mut event = Event::get(&name)?
+        .ok_or_else(|| crate::output::not_found("Event", &name))?;
+
+    say!("✏️  Renaming event '{name}' to '{new_name}'...");
+
+    event.rename(new_name.clone())?;
+
+    say!("✅ Event '{name}' is now '{new_name}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Event(event.name.clone()), None)?;
+        return crate::output::emit_entity("event", Some("renamed"), &event, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_merge(keep: String, drop: String, on_conflict: MergePolicy, force: bool) -> Result<()> {
+    let mut survivor = Event::get(&keep)?
+        .ok_or_else(|| crate::output::not_found("Event", &keep))?;
+    let duplicate = Event::get(&drop)?
+        .ok_or_else(|| crate::output::not_found("Event", &drop))?;
+
+    if !force {
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("merge event '{}' into '{}'", drop, keep)));
+        }
+        let relations = entity_relations(&EntityType::Event(drop.clone()), None)?;
+        let conflicts = metadata_conflicts(&survivor.metadata, &duplicate.metadata);
+        say!("⚠️  Are you sure you want to merge event '{drop}' into '{keep}'?");
+        say!("   '{drop}' will be deleted and kept as an alias of '{keep}'");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will move to '{keep}'", relations.len());
+        }
+        if !conflicts.is_empty() {
+            say!("   Conflicting metadata ({:?}): {}", on_conflict, conflicts.join(", "));
+        }
+        say!("   Use --force to skip this confirmation");
+        return Ok(());
+    }
+
+    say!("🔀 Merging event '{drop}' into '{keep}'...");
+
+    survivor.merge(duplicate, on_conflict)?;
+
+    say!("✅ Event '{drop}' merged into '{keep}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Event(survivor.name.clone()), None)?;
+        return crate::output::emit_entity("event", Some("merged"), &survivor, Some(relations));
+    }
+
+    Ok(())
+}
+
 fn handle_timeline() -> Result<()> {
     let events = Event::list_chronological()?;
     
     if events.is_empty() {
-        println!("📅 No events found in this world");
-        println!("   Use 'multiverse event create <name> --set title=\"<title>\"' to create one");
+        say!("📅 No events found in this world");
+        say!("   Use 'multiverse event create <name> --set title=\"<title>\"' to create one");
         return Ok(());
     }
     
-    println!("⏰ Events Timeline (chronological order):");
+    say!("⏰ Events Timeline (chronological order):");
     
     for event in events {
-        let status_emoji = match event.status {
+        let status_emoji = crate::status::status_emoji("event", event.status.as_str(), match event.status {
             crate::event::models::EventStatus::Active => "🟢",
             crate::event::models::EventStatus::Inactive => "⚫",
             crate::event::models::EventStatus::Completed => "✅", 
             crate::event::models::EventStatus::Cancelled => "❌",
             crate::event::models::EventStatus::Pending => "⏳",
-        };
+            crate::event::models::EventStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
         
         let date_display = if !event.date_text.is_empty() {
             &event.date_text
@@ -231,16 +334,16 @@ fn handle_timeline() -> Result<()> {
             "Unknown date"
         };
         
-        println!("   {} {} - \"{}\"", 
+        say!("   {} {} - \"{}\"", 
             status_emoji, 
             date_display,
             event.display_name
         );
         
-        println!("      [Sort: {}] {}", event.sort_key, event.name);
+        say!("      [Sort: {}] {}", event.sort_key, event.name);
         
         if let Some(event_type) = event.metadata.get("type") {
-            println!("      Type: {}", event_type.as_str().unwrap_or("Unknown"));
+            say!("      Type: {}", event_type.as_str().unwrap_or("Unknown"));
         }
     }
     
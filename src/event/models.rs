@@ -41,13 +41,10 @@ impl Event {
         }
     }
 
-    /// Resolve event name to database ID
+    /// Resolve event name or alias to database ID
     pub fn resolve_id(name: &str) -> anyhow::Result<String> {
         let conn = Self::get_database_connection()?;
-        let mut stmt = conn.prepare("SELECT id FROM events WHERE name = ?")?;
-        let id: i32 = stmt.query_row([name], |row| {
-            row.get(0)
-        }).map_err(|_| anyhow::anyhow!("Event not found: '{}'", name))?;
+        let id = crate::relations::resolve_entity_id(&conn, EventDb::TABLE, "Event", name)?;
         Ok(id.to_string())
     }
 
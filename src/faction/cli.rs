@@ -3,21 +3,31 @@ use clap::Subcommand;
 #[derive(Subcommand)]
 pub enum FactionCommands {
     /// Create a new faction with flexible metadata
+    #[command(after_help = crate::relations::relation_set_help("faction"))]
     Create {
         /// Faction name (unique identifier)
         name: String,
         /// Set metadata field (can be used multiple times: --set title="Faction Name" --set type=government)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
     },
     
     /// List factions in current world
-    List,
+    List {
+        #[command(flatten)]
+        options: crate::listing::ListArgs,
+    },
     
     /// Show faction details
     Info {
         /// Faction name
         name: String,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
     },
     
     /// Delete a faction
@@ -27,16 +37,48 @@ pub enum FactionCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+        /// What to do with existing relations: refuse, detach or cascade
+        #[arg(long, value_enum, default_value_t = crate::relations::DeleteMode::Refuse)]
+        on_relations: crate::relations::DeleteMode,
     },
 
     /// Update an existing faction
+    #[command(after_help = crate::relations::relation_set_help("faction"))]
     Update {
         /// Faction name
         name: String,
         /// Set metadata field (can be used multiple times: --set title="New Name" --set type=military)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
+        /// Remove a metadata field (--unset age --unset stats.strength)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+    /// Rename a faction, keeping its relations
+    Rename {
+        /// Current faction name
+        name: String,
+        /// New faction name
+        new_name: String,
+    },
+    /// Merge a duplicate faction into another one, moving its relations
+    Merge {
+        /// Faction to keep
+        keep: String,
+        /// Duplicate faction to merge in and delete (its name becomes an alias)
+        drop: String,
+        /// Metadata set differently on both: keep the survivor's value, take the duplicate's, or fail
+        #[arg(long, value_enum, default_value_t = crate::relations::MergePolicy::Keep)]
+        on_conflict: crate::relations::MergePolicy,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
     },
+    /// Show the field schema of factions (built-in fields and [schema.faction] in config.toml)
+    Schema,
 }
 
 /// Parse a single key-value pair for --set flag
@@ -1,25 +1,29 @@
 use super::cli::FactionCommands;
 use super::models::Faction;
-use crate::relations::{process_relations, EntityType, separate_relation_fields};
+use crate::relations::{process_relations, EntityType, separate_relation_fields, relation_keys_for, show_entity_relations, DeleteMode, check_entity_delete, release_entity, entity_relations, MergePolicy, metadata_conflicts};
 use anyhow::Result;
+use crate::say;
 
 pub fn handle_faction_command(command: FactionCommands) -> Result<()> {
     match command {
-        FactionCommands::Create { name, set } => {
-            handle_create(name, set)
+        FactionCommands::Create { name, set, set_json } => {
+            handle_create(name, set, set_json)
         }
-        FactionCommands::List => handle_list(),
-        FactionCommands::Info { name } => handle_info(name),
-        FactionCommands::Delete { name, force } => handle_delete(name, force),
-        FactionCommands::Update { name, set } => handle_update(name, set),
+        FactionCommands::List { options } => handle_list(options),
+        FactionCommands::Schema => Faction::show_schema(),
+        FactionCommands::Info { name, at } => handle_info(name, at),
+        FactionCommands::Delete { name, force, on_relations } => handle_delete(name, force, on_relations),
+        FactionCommands::Rename { name, new_name } => handle_rename(name, new_name),
+        FactionCommands::Merge { keep, drop, on_conflict, force } => handle_merge(keep, drop, on_conflict, force),
+        FactionCommands::Update { name, set, set_json, unset } => handle_update(name, set, set_json, unset),
     }
 }
 
-fn handle_update(name: String, mut set_args: Vec<(String, String)>) -> Result<()> {
-    println!("🔄 Updating faction '{name}'");
+fn handle_update(name: String, mut set_args: Vec<(String, String)>, set_json: Vec<(String, String)>, unset: Vec<String>) -> Result<()> {
+    say!("🔄 Updating faction '{name}'");
 
     let mut faction = Faction::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Faction '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Faction", &name))?;
 
     // Normalize field names: title -> display_name
     for (key, _) in &mut set_args {
@@ -29,21 +33,28 @@ fn handle_update(name: String, mut set_args: Vec<(String, String)>) -> Result<()
     }
 
     let regular_fields = process_relations(EntityType::Faction(name.clone()), set_args)?;
+    faction.unset_fields(&unset)?;
+    faction.process_json_args(set_json)?;
     faction.update(regular_fields)?;
 
-    println!("✅ Faction '{}' updated!", name);
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Faction(faction.name.clone()), None)?;
+        return crate::output::emit_entity("faction", Some("updated"), &faction, Some(relations));
+    }
+
+    say!("✅ Faction '{}' updated!", name);
     show_created_faction(&faction)?;
 
     Ok(())
 }
 
-fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()> {
+fn handle_create(name: String, mut set_args: Vec<(String, String)>, set_json: Vec<(String, String)>) -> Result<()> {
     let title = set_args.iter()
         .find(|(k, _)| k == "title" || k == "display_name")
         .map(|(_, v)| v.as_str())
         .unwrap_or(&name);
     
-    println!("⚔️ Creating faction '{name}' ({})", title);
+    say!("⚔️ Creating faction '{name}' ({})", title);
 
     // Normalize field names: title -> display_name
     for (key, _) in &mut set_args {
@@ -53,11 +64,11 @@ fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()
     }
 
     // Separate relation fields from regular fields  
-    let relation_keys = ["character", "location", "event"];
+    let relation_keys = relation_keys_for("faction");
     let (relation_fields, regular_fields) = separate_relation_fields(set_args, &relation_keys);
     
     // Create faction with regular fields FIRST
-    let mut faction = Faction::create_new(name.clone(), regular_fields)?;
+    let mut faction = Faction::create_new(name.clone(), regular_fields, set_json)?;
     faction.create()?;
     
     // THEN process relations after faction exists in database
@@ -65,6 +76,11 @@ fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()
         process_relations(EntityType::Faction(name.clone()), relation_fields)?;
     }
     
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Faction(faction.name.clone()), None)?;
+        return crate::output::emit_entity("faction", Some("created"), &faction, Some(relations));
+    }
+    
     // Display success information
     show_created_faction(&faction)?;
     
@@ -72,42 +88,51 @@ fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()
 }
 
 fn show_created_faction(faction: &Faction) -> Result<()> {
-    println!("✅ Faction '{}' created!", faction.name);
-    println!("   Title: {}", faction.display_name);
-    println!("   Status: {:?}", faction.status);
+    say!("✅ Faction '{}' created!", faction.name);
+    say!("   Title: {}", faction.display_name);
+    say!("   Status: {:?}", faction.status);
     
     // Show metadata
     if !faction.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &faction.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
     Ok(())
 }
 
-fn handle_list() -> Result<()> {
-    let factions = Faction::list()?;
+fn handle_list(options: crate::listing::ListArgs) -> Result<()> {
+    let factions = Faction::list_with(&options)?;
+    
+    if crate::output::is_structured() {
+        return crate::output::emit_list(&factions, &options.fields);
+    }
+    
+    if !options.fields.is_empty() {
+        return crate::listing::print_fields(&factions, &options.fields);
+    }
     
     if factions.is_empty() {
-        println!("⚔️ No factions found in this world");
-        println!("   Use 'multiverse faction create <name> --set title=\"<title>\"' to create one");
+        say!("⚔️ No factions found in this world");
+        say!("   Use 'multiverse faction create <name> --set title=\"<title>\"' to create one");
         return Ok(());
     }
     
-    println!("⚔️ Factions in current world:");
+    say!("⚔️ Factions in current world:");
     
     for faction in factions {
-        let status_emoji = match faction.status {
+        let status_emoji = crate::status::status_emoji("faction", faction.status.as_str(), match faction.status {
             crate::faction::models::FactionStatus::Active => "🟢",
             crate::faction::models::FactionStatus::Inactive => "⚫",
             crate::faction::models::FactionStatus::Disbanded => "💥", 
             crate::faction::models::FactionStatus::Allied => "🤝",
             crate::faction::models::FactionStatus::Hostile => "⚔️",
-        };
+            crate::faction::models::FactionStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
         
-        println!("   {} {} - \"{}\"", 
+        say!("   {} {} - \"{}\"", 
             status_emoji, 
             faction.name, 
             faction.display_name
@@ -115,56 +140,133 @@ fn handle_list() -> Result<()> {
         
         // Show key metadata fields
         if let Some(faction_type) = faction.metadata.get("type") {
-            println!("      Type: {}", faction_type.as_str().unwrap_or("Unknown"));
+            say!("      Type: {}", faction_type.as_str().unwrap_or("Unknown"));
         }
         if let Some(description) = faction.metadata.get("description") {
-            println!("      {}", description.as_str().unwrap_or(""));
+            say!("      {}", description.as_str().unwrap_or(""));
         }
     }
     
     Ok(())
 }
 
-fn handle_info(name: String) -> Result<()> {
+fn handle_info(name: String, at: Option<String>) -> Result<()> {
     let faction = Faction::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Faction '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Faction", &name))?;
     
-    println!("⚔️ Faction: {} - \"{}\"", faction.name, faction.display_name);
-    println!("   Status: {:?}", faction.status);
-    println!("   Created: {}", faction.created_at.format("%Y-%m-%d %H:%M"));
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Faction(faction.name.clone()), at.as_deref())?;
+        return crate::output::emit_entity("faction", None, &faction, Some(relations));
+    }
+    
+    say!("⚔️ Faction: {} - \"{}\"", faction.name, faction.display_name);
+    say!("   Status: {:?}", faction.status);
+    say!("   Created: {}", faction.created_at.format("%Y-%m-%d %H:%M"));
     
     if let Some(desc) = faction.metadata.get("description") {
-        println!("   Description: {}", desc.as_str().unwrap_or(""));
+        say!("   Description: {}", desc.as_str().unwrap_or(""));
     }
     
     // Show metadata
     if !faction.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &faction.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
+    show_entity_relations(&EntityType::Faction(faction.name.clone()), at.as_deref())?;
+    
     Ok(())
 }
 
-fn handle_delete(name: String, force: bool) -> Result<()> {
+fn handle_delete(name: String, force: bool, on_relations: DeleteMode) -> Result<()> {
     let faction = Faction::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Faction '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Faction", &name))?;
     
+    let entity = EntityType::Faction(name.clone());
+    let relations = check_entity_delete(&entity, on_relations)?;
+
     if !force {
-        println!("⚠️  Are you sure you want to delete faction '{name}'?");
-        println!("   This will permanently delete the faction from database");
-        println!("   Use --force to skip this confirmation");
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("delete faction '{}'", name)));
+        }
+        say!("⚠️  Are you sure you want to delete faction '{name}'?");
+        say!("   This will permanently delete the faction from database");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will be removed as well", relations.len());
+        }
+        say!("   Use --force to skip this confirmation");
         return Ok(());
     }
     
-    println!("🗑️  Deleting faction '{name}'...");
+    say!("🗑️  Deleting faction '{name}'...");
     
+    release_entity(&entity, on_relations)?;
     faction.delete(force)?;
     
-    println!("✅ Faction '{name}' deleted!");
+    say!("✅ Faction '{name}' deleted!");
+
+    if crate::output::is_structured() {
+        return crate::output::emit_entity("faction", Some("deleted"), &faction, Some(relations));
+    }
     
     Ok(())
 }
 
+fn handle_rename(name: String, new_name: String) -> Result<()> {
+    let mut faction = Faction::get(&name)?
+        .ok_or_else(|| crate::output::not_found("Faction", &name))?;
+
+    say!("✏️  Renaming faction '{name}' to '{new_name}'...");
+
+    faction.rename(new_name.clone())?;
+
+    say!("✅ Faction '{name}' is now '{new_name}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Faction(faction.name.clone()), None)?;
+        return crate::output::emit_entity("faction", Some("renamed"), &faction, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_merge(keep: String, drop: String, on_conflict: MergePolicy, force: bool) -> Result<()> {
+    let mut survivor = Faction::get(&keep)?
+        .ok_or_else(|| crate::output::not_found("Faction", &keep))?;
+    let duplicate = Faction::get(&drop)?
+        .ok_or_else(|| crate::output::not_found("Faction", &drop))?;
+
+    if !force {
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("merge faction '{}' into '{}'", drop, keep)));
+        }
+        let relations = entity_relations(&EntityType::Faction(drop.clone()), None)?;
+        let conflicts = metadata_conflicts(&survivor.metadata, &duplicate.metadata);
+        say!("⚠️  Are you sure you want to merge faction '{drop}' into '{keep}'?");
+        say!("   '{drop}' will be deleted and kept as an alias of '{keep}'");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will move to '{keep}'", relations.len());
+        }
+        if !conflicts.is_empty() {
+            say!("   Conflicting metadata ({:?}): {}", on_conflict, conflicts.join(", "));
+        }
+        say!("   Use --force to skip this confirmation");
+        return Ok(());
+    }
+
+    say!("🔀 Merging faction '{drop}' into '{keep}'...");
+
+    survivor.merge(duplicate, on_conflict)?;
+
+    say!("✅ Faction '{drop}' merged into '{keep}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Faction(survivor.name.clone()), None)?;
+        return crate::output::emit_entity("faction", Some("merged"), &survivor, Some(relations));
+    }
+
+    Ok(())
+}
+
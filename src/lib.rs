@@ -11,6 +11,17 @@ pub mod timeline;
 pub mod relations;
 pub mod race;
 pub mod templates;
+pub mod schema;
+pub mod status;
+pub mod metadata;
+pub mod listing;
+pub mod output;
+pub mod repl;
+pub mod line_editor;
+pub mod queries;
+pub mod views;
+pub mod migrations;
+pub mod config_sections;
 
 // New modular entity macro system
 pub mod entity_macros;
@@ -26,6 +37,8 @@ pub use system::{handle_system_command, SystemCommands, System};
 pub use faction::{handle_faction_command, FactionCommands, Faction};
 pub use event::{handle_event_command, EventCommands, Event};
 pub use race::{handle_race_command, RaceCommands, Race};
+pub use relations::{handle_relation_command, RelationCommands};
+pub use queries::{handle_query_command, QueryCommands};
 pub use timeline::{TimelineDate, TimelineConfig, load_timeline_config, timeline_config_exists};
 pub use database::{get_connection, init_database};
 
@@ -0,0 +1,150 @@
+//! Line input of the interactive SQL shell
+//!
+//! ```text
+//! ← →  Home End (Ctrl-A, Ctrl-E)   move the cursor
+//! Backspace Delete                  edit at the cursor
+//! ↑ ↓                               walk the history, back to the line being typed
+//! Ctrl-C                            drop the line      Ctrl-D on an empty line: end input
+//! ```
+//!
+//! `LineBuffer` holds the editing state and knows nothing of the terminal; `LineEditor`
+//! reads keys from the terminal and redraws the line after each one.
+
+use console::{Key, Term};
+use std::io;
+
+/// What a key did to the line
+#[derive(Debug, PartialEq, Eq)]
+pub enum Edit {
+    /// The line changed or the cursor moved: redraw it
+    Changed,
+    /// Nothing to do (cursor already at the edge, unbound key)
+    Ignored,
+    /// Enter: the line is complete
+    Submit(String),
+    /// Ctrl-C: the line is dropped
+    Cancel,
+    /// Ctrl-D on an empty line: end of input
+    Eof,
+}
+
+/// A line being edited, with its view of the history
+pub struct LineBuffer<'h> {
+    chars: Vec<char>,
+    cursor: usize,
+    history: &'h [String],
+    /// History entry shown, `history.len()` for the line being typed
+    recalled: usize,
+    /// The line being typed, kept while the history is shown
+    draft: Vec<char>,
+}
+
+impl<'h> LineBuffer<'h> {
+    pub fn new(history: &'h [String]) -> Self {
+        LineBuffer { chars: Vec::new(), cursor: 0, history, recalled: history.len(), draft: Vec::new() }
+    }
+
+    pub fn text(&self) -> String {
+        self.chars.iter().collect()
+    }
+
+    /// Characters before the cursor
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn handle(&mut self, key: Key) -> Edit {
+        match key {
+            Key::Enter => return Edit::Submit(self.text()),
+            Key::CtrlC => return Edit::Cancel,
+            Key::Char('\u{4}') if self.chars.is_empty() => return Edit::Eof,
+            Key::Char(c) if !c.is_control() => {
+                self.chars.insert(self.cursor, c);
+                self.cursor += 1;
+            }
+            Key::Backspace if self.cursor > 0 => {
+                self.cursor -= 1;
+                self.chars.remove(self.cursor);
+            }
+            Key::Del if self.cursor < self.chars.len() => {
+                self.chars.remove(self.cursor);
+            }
+            Key::ArrowLeft if self.cursor > 0 => self.cursor -= 1,
+            Key::ArrowRight if self.cursor < self.chars.len() => self.cursor += 1,
+            Key::Home if self.cursor > 0 => self.cursor = 0,
+            Key::End if self.cursor < self.chars.len() => self.cursor = self.chars.len(),
+            Key::ArrowUp if self.recalled > 0 => {
+                if self.recalled == self.history.len() {
+                    self.draft = std::mem::take(&mut self.chars);
+                }
+                self.recalled -= 1;
+                self.show_recalled();
+            }
+            Key::ArrowDown if self.recalled < self.history.len() => {
+                self.recalled += 1;
+                self.show_recalled();
+            }
+            _ => return Edit::Ignored,
+        }
+        Edit::Changed
+    }
+
+    /// Put the recalled entry (or the draft) on the line; multi-line statements are joined
+    fn show_recalled(&mut self) {
+        self.chars = match self.history.get(self.recalled) {
+            Some(entry) => entry.trim_end().replace('\n', " ").chars().collect(),
+            None => self.draft.clone(),
+        };
+        self.cursor = self.chars.len();
+    }
+}
+
+/// Reads lines from the terminal, drawing on stderr like the rest of the shell's prompts
+pub struct LineEditor {
+    term: Term,
+}
+
+impl Default for LineEditor {
+    fn default() -> Self {
+        LineEditor { term: Term::stderr() }
+    }
+}
+
+impl LineEditor {
+    /// Whether the prompt goes to a terminal; otherwise input is read as plain lines
+    pub fn is_available() -> bool {
+        Term::stderr().is_term()
+    }
+
+    /// Read one line after `prompt`: None at end of input
+    pub fn read_line(&self, prompt: &str, history: &[String]) -> io::Result<Option<String>> {
+        let mut line = LineBuffer::new(history);
+        self.term.write_str(prompt)?;
+        loop {
+            match line.handle(self.term.read_key_raw()?) {
+                Edit::Changed => self.redraw(prompt, &line)?,
+                Edit::Ignored => {}
+                Edit::Submit(text) => {
+                    self.term.write_line("")?;
+                    return Ok(Some(text));
+                }
+                Edit::Cancel => {
+                    self.term.write_line("^C")?;
+                    line = LineBuffer::new(history);
+                    self.term.write_str(prompt)?;
+                }
+                Edit::Eof => return Ok(None),
+            }
+        }
+    }
+
+    fn redraw(&self, prompt: &str, line: &LineBuffer) -> io::Result<()> {
+        let text = line.text();
+        let after_cursor: String = text.chars().skip(line.cursor()).collect();
+        self.term.clear_line()?;
+        self.term.write_str(prompt)?;
+        self.term.write_str(&text)?;
+        self.term.move_cursor_left(console::measure_text_width(&after_cursor))?;
+        self.term.flush()
+    }
+}
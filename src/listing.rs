@@ -0,0 +1,248 @@
+//! Filtering, sorting and field selection shared by every `list` command
+//! character list --where status=Active --where metadata.profession=ranger --sort display_name --fields name,age --limit 20
+//! character list --related faction=nights_watch --related-role member
+//! character list --not-related faction
+//!
+//! Keys are columns (name, display_name, status, ...) or metadata paths (`metadata.stats.strength`,
+//! or just `profession` when no column has that name). Filters and sorting run in SQLite,
+//! metadata through `json_extract`. Relation filters use the --set relation keys of the entity
+//! type and the tables of the relation registry.
+
+use anyhow::Result;
+use clap::Args;
+use rusqlite::Connection;
+use crate::relations::{find_relation_for_key, relation_keys_for, EntityType, RelationTable};
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct ListArgs {
+    /// Only show entries matching key=value or key!=value (repeatable). A list field matches when it contains the value
+    #[arg(long = "where", value_name = "KEY=VALUE", value_parser = parse_filter)]
+    pub filters: Vec<Filter>,
+    /// Sort by a column or metadata key; prefix with '-' for descending (--sort=-age)
+    #[arg(long, allow_hyphen_values = true)]
+    pub sort: Option<String>,
+    /// Print only these fields as a table (--fields name,age)
+    #[arg(long, value_delimiter = ',')]
+    pub fields: Vec<String>,
+    /// Show at most this many entries
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Only show entries with a relation through a --set relation key: faction=nights_watch,
+    /// faction=nights_watch*member, or just faction for any (repeatable)
+    #[arg(long, value_name = "KEY[=NAME]")]
+    pub related: Vec<String>,
+    /// Role the --related relations must have
+    #[arg(long, value_name = "ROLE", requires = "related")]
+    pub related_role: Option<String>,
+    /// Only show entries without any relation through this key (repeatable)
+    #[arg(long, value_name = "KEY")]
+    pub not_related: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Filter {
+    pub key: String,
+    pub value: String,
+    pub negate: bool,
+}
+
+fn parse_filter(s: &str) -> Result<Filter, String> {
+    let (key, value, negate) = if let Some((key, value)) = s.split_once("!=") {
+        (key, value, true)
+    } else if let Some((key, value)) = s.split_once('=') {
+        (key, value, false)
+    } else {
+        return Err(format!("invalid filter: expected KEY=VALUE or KEY!=VALUE in `{s}`"));
+    };
+    Ok(Filter { key: key.trim().to_string(), value: value.to_string(), negate })
+}
+
+/// SQL expression for a list key: a column of the table or a json_extract of metadata
+fn key_expression(columns: &[&str], key: &str) -> Result<Expression> {
+    if columns.contains(&key) {
+        return Ok(Expression::Column(format!("\"{}\"", key)));
+    }
+
+    let path = key.strip_prefix("metadata.").unwrap_or(key);
+    let mut json_path = String::from("$");
+    for segment in path.split('.') {
+        if segment.is_empty() || !segment.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+            anyhow::bail!("Invalid field '{}': use a column name or metadata.<key>", key);
+        }
+        json_path.push_str(&format!(".\"{}\"", segment));
+    }
+    Ok(Expression::Metadata(json_path))
+}
+
+enum Expression {
+    Column(String),
+    /// JSON path into the metadata column, e.g. $."stats"."strength"
+    Metadata(String),
+}
+
+impl Expression {
+    fn value(&self) -> String {
+        match self {
+            Expression::Column(column) => column.clone(),
+            Expression::Metadata(path) => format!("json_extract(metadata, '{}')", path),
+        }
+    }
+
+    /// Equality test against parameter ?n; metadata lists match any of their items
+    fn matches(&self, param: usize) -> String {
+        match self {
+            Expression::Column(column) => format!("CAST({} AS TEXT) = ?{}", column, param),
+            Expression::Metadata(path) => format!(
+                "(CAST(json_extract(metadata, '{p}') AS TEXT) = ?{n} OR (json_type(metadata, '{p}') = 'array' \
+                 AND EXISTS (SELECT 1 FROM json_each(metadata, '{p}') WHERE CAST(value AS TEXT) = ?{n})))",
+                p = path, n = param
+            ),
+        }
+    }
+}
+
+impl ListArgs {
+    /// SELECT statement and parameters listing entities of `kind` from `table`. `columns` are
+    /// the table's own columns; rows come in `default_order` unless --sort is given.
+    pub fn select_sql(
+        &self,
+        conn: &Connection,
+        kind: &str,
+        table: &str,
+        select: &str,
+        columns: &[&str],
+        default_order: &str,
+    ) -> Result<(String, Vec<String>)> {
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+        for filter in &self.filters {
+            params.push(filter.value.clone());
+            let test = key_expression(columns, &filter.key)?.matches(params.len());
+            conditions.push(if filter.negate { format!("NOT COALESCE({}, 0)", test) } else { test });
+        }
+        for related in &self.related {
+            conditions.push(self.related_condition(conn, kind, related, &mut params)?);
+        }
+        for key in &self.not_related {
+            let (relation, reverse) = relation_for_key(kind, key)?;
+            if crate::database::table_exists(conn, relation.table)? {
+                let mut related = relation.related_ids_sql(reverse, None, None);
+                if relation.is_self_relation() {
+                    related = format!("{} UNION {}", related, relation.related_ids_sql(!reverse, None, None));
+                }
+                conditions.push(format!("CAST(id AS TEXT) NOT IN ({})", related));
+            }
+        }
+
+        let mut sql = format!("SELECT {} FROM {}", select, table);
+        if !conditions.is_empty() {
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+
+        match &self.sort {
+            Some(sort) => {
+                let (key, direction) = match sort.strip_prefix('-') {
+                    Some(key) => (key, "DESC"),
+                    None => (sort.as_str(), "ASC"),
+                };
+                // Entries without the key go last either way
+                let expression = key_expression(columns, key)?.value();
+                sql.push_str(&format!(" ORDER BY {e} IS NULL, {e} {d}, id", e = expression, d = direction));
+            }
+            None => sql.push_str(&format!(" ORDER BY {}", default_order)),
+        }
+
+        if let Some(limit) = self.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+        Ok((sql, params))
+    }
+
+    /// Condition for one --related spec: KEY, KEY=NAME or KEY=NAME*ROLE
+    fn related_condition(&self, conn: &Connection, kind: &str, spec: &str, params: &mut Vec<String>) -> Result<String> {
+        let (key, target) = match spec.split_once('=') {
+            Some((key, target)) => (key.trim(), Some(target.trim())),
+            None => (spec.trim(), None),
+        };
+        let (target, role) = match target.and_then(|target| target.split_once('*')) {
+            Some((name, role)) => (Some(name), Some(role)),
+            None => (target, self.related_role.as_deref()),
+        };
+
+        let (relation, reverse) = relation_for_key(kind, key)?;
+        if let Some(role) = role {
+            relation.check_role(role)?;
+        }
+        if !crate::database::table_exists(conn, relation.table)? {
+            return Ok("0".to_string());
+        }
+
+        let target_param = match target {
+            Some(name) => {
+                params.push(EntityType::from_kind(key, name.to_string())?.resolve_id()?);
+                Some(params.len())
+            }
+            None => None,
+        };
+        let role_param = role.map(|role| {
+            params.push(role.to_string());
+            params.len()
+        });
+        let mut related = relation.related_ids_sql(reverse, target_param, role_param);
+
+        // A self-relation pair is stored once, so the entity may be on the to side,
+        // where the role reads as its inverse (child_of_father for father)
+        if relation.is_self_relation() {
+            let inverse_param = role.map(|role| {
+                params.push((relation.inverse_role)(role).unwrap_or_else(|| role.to_string()));
+                params.len()
+            });
+            related = format!("{} UNION {}", related, relation.related_ids_sql(!reverse, target_param, inverse_param));
+        }
+        Ok(format!("CAST(id AS TEXT) IN ({})", related))
+    }
+}
+
+/// Relation table behind a --related/--not-related key
+fn relation_for_key(kind: &str, key: &str) -> Result<(RelationTable, bool)> {
+    find_relation_for_key(kind, key).ok_or_else(|| anyhow::anyhow!(
+        "'{}' is not a relation key of {}. Relation keys: {}",
+        key, kind, relation_keys_for(kind).join(", ")
+    ))
+}
+
+/// Print the --fields of each entity as an aligned table. Fields are looked up on the
+/// entity first, then in its metadata (dotted paths reach nested keys).
+pub fn print_fields<T: Serialize>(entities: &[T], fields: &[String]) -> Result<()> {
+    let mut rows = vec![fields.to_vec()];
+    for entity in entities {
+        let value = serde_json::to_value(entity)?;
+        rows.push(fields.iter().map(|field| display_value(field_value(&value, field))).collect());
+    }
+
+    crate::output::print_table(rows);
+    Ok(())
+}
+
+/// Value of a --fields entry in a serialized entity
+pub fn field_value<'a>(entity: &'a JsonValue, field: &str) -> Option<&'a JsonValue> {
+    if let Some(value) = entity.get(field) {
+        return Some(value);
+    }
+    let path = field.strip_prefix("metadata.").unwrap_or(field);
+    path.split('.').try_fold(entity.get("metadata")?, |value, segment| value.get(segment))
+}
+
+fn display_value(value: Option<&JsonValue>) -> String {
+    match value {
+        None | Some(JsonValue::Null) => "-".to_string(),
+        Some(JsonValue::String(text)) => text.clone(),
+        Some(JsonValue::Array(items)) => items.iter()
+            .map(|item| display_value(Some(item)))
+            .collect::<Vec<_>>()
+            .join(", "),
+        Some(other) => other.to_string(),
+    }
+}
@@ -3,21 +3,31 @@ use clap::Subcommand;
 #[derive(Subcommand)]
 pub enum LocationCommands {
     /// Create a new location with unified --set for all fields
+    #[command(after_help = crate::relations::relation_set_help("location"))]
     Create {
         /// Location name (unique identifier)
         name: String,
         /// Set any field (--set display_name="Name" --set status="Active" --set type=city --set description="...")
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
     },
     
     /// List locations in current world
-    List,
+    List {
+        #[command(flatten)]
+        options: crate::listing::ListArgs,
+    },
     
     /// Show location details
     Info {
         /// Location name
         name: String,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
     },
     
     /// Delete a location
@@ -27,16 +37,48 @@ pub enum LocationCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+        /// What to do with existing relations: refuse, detach or cascade
+        #[arg(long, value_enum, default_value_t = crate::relations::DeleteMode::Refuse)]
+        on_relations: crate::relations::DeleteMode,
     },
 
     /// Update an existing location
+    #[command(after_help = crate::relations::relation_set_help("location"))]
     Update {
         /// Location name
         name: String,
         /// Set any field (--set display_name="Name" --set status="Active" --set type=city --set description="...")
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
+        /// Remove a metadata field (--unset age --unset stats.strength)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+    /// Rename a location, keeping its relations
+    Rename {
+        /// Current location name
+        name: String,
+        /// New location name
+        new_name: String,
+    },
+    /// Merge a duplicate location into another one, moving its relations
+    Merge {
+        /// Location to keep
+        keep: String,
+        /// Duplicate location to merge in and delete (its name becomes an alias)
+        drop: String,
+        /// Metadata set differently on both: keep the survivor's value, take the duplicate's, or fail
+        #[arg(long, value_enum, default_value_t = crate::relations::MergePolicy::Keep)]
+        on_conflict: crate::relations::MergePolicy,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
     },
+    /// Show the field schema of locations (built-in fields and [schema.location] in config.toml)
+    Schema,
 }
 
 /// Parse a single key-value pair for --set flag
@@ -1,45 +1,56 @@
 use super::cli::LocationCommands;
 use super::models::{Location, LocationStatus};
-use crate::relations::{process_relations, EntityType, separate_relation_fields};
+use crate::relations::{process_relations, EntityType, separate_relation_fields, relation_keys_for, show_entity_relations, DeleteMode, check_entity_delete, release_entity, entity_relations, MergePolicy, metadata_conflicts};
 use anyhow::Result;
+use crate::say;
 
 pub fn handle_location_command(command: LocationCommands) -> Result<()> {
     match command {
-        LocationCommands::Create { name, set } => {
-            handle_create(name, set)
+        LocationCommands::Create { name, set, set_json } => {
+            handle_create(name, set, set_json)
         }
-        LocationCommands::List => handle_list(),
-        LocationCommands::Info { name } => handle_info(name),
-        LocationCommands::Delete { name, force } => handle_delete(name, force),
-        LocationCommands::Update { name, set } => handle_update(name, set),
+        LocationCommands::List { options } => handle_list(options),
+        LocationCommands::Schema => Location::show_schema(),
+        LocationCommands::Info { name, at } => handle_info(name, at),
+        LocationCommands::Delete { name, force, on_relations } => handle_delete(name, force, on_relations),
+        LocationCommands::Rename { name, new_name } => handle_rename(name, new_name),
+        LocationCommands::Merge { keep, drop, on_conflict, force } => handle_merge(keep, drop, on_conflict, force),
+        LocationCommands::Update { name, set, set_json, unset } => handle_update(name, set, set_json, unset),
     }
 }
 
-fn handle_update(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("🔄 Updating location '{name}'");
+fn handle_update(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>, unset: Vec<String>) -> Result<()> {
+    say!("🔄 Updating location '{name}'");
 
     let mut location = Location::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Location '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Location", &name))?;
 
     // Process relations and get back non-relation fields
     let regular_fields = process_relations(EntityType::Location(name.clone()), set_args)?;
     
+    location.unset_fields(&unset)?;
+    location.process_json_args(set_json)?;
     // Update regular fields
     location.update(regular_fields)?;
 
-    println!("✅ Location '{}' updated!", name);
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Location(location.name.clone()), None)?;
+        return crate::output::emit_entity("location", Some("updated"), &location, Some(relations));
+    }
+
+    say!("✅ Location '{}' updated!", name);
     show_created_location(&location)?;
 
     Ok(())
 }
 
-fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()> {
+fn handle_create(name: String, mut set_args: Vec<(String, String)>, set_json: Vec<(String, String)>) -> Result<()> {
     let title = set_args.iter()
         .find(|(k, _)| k == "title" || k == "display_name")
         .map(|(_, v)| v.as_str())
         .unwrap_or(&name);
     
-    println!("📍 Creating location '{name}' ({})", title);
+    say!("📍 Creating location '{name}' ({})", title);
 
     // Normalize field names: title -> display_name
     for (key, _) in &mut set_args {
@@ -49,11 +60,11 @@ fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()
     }
 
     // Separate relation fields from regular fields  
-    let relation_keys = ["faction", "location", "system", "character"];
+    let relation_keys = relation_keys_for("location");
     let (relation_fields, regular_fields) = separate_relation_fields(set_args, &relation_keys);
     
     // Create location with regular fields FIRST
-    let mut location = Location::create_new(name.clone(), regular_fields)?;
+    let mut location = Location::create_new(name.clone(), regular_fields, set_json)?;
     location.create()?;
     
     // THEN process relations after location exists in database
@@ -61,6 +72,11 @@ fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()
         process_relations(EntityType::Location(name.clone()), relation_fields)?;
     }
     
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Location(location.name.clone()), None)?;
+        return crate::output::emit_entity("location", Some("created"), &location, Some(relations));
+    }
+    
     // Display success information
     show_created_location(&location)?;
     
@@ -68,46 +84,55 @@ fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()
 }
 
 fn show_created_location(location: &Location) -> Result<()> {
-    println!("✅ Location '{}' created!", location.name);
-    println!("   Title: {}", location.display_name);
-    println!("   Status: {:?}", location.status);
+    say!("✅ Location '{}' created!", location.name);
+    say!("   Title: {}", location.display_name);
+    say!("   Status: {:?}", location.status);
     
     // Show metadata
     if !location.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &location.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
     Ok(())
 }
 
-fn handle_list() -> Result<()> {
-    let locations = Location::list()?;
+fn handle_list(options: crate::listing::ListArgs) -> Result<()> {
+    let locations = Location::list_with(&options)?;
+    
+    if crate::output::is_structured() {
+        return crate::output::emit_list(&locations, &options.fields);
+    }
+    
+    if !options.fields.is_empty() {
+        return crate::listing::print_fields(&locations, &options.fields);
+    }
     
     if locations.is_empty() {
-        println!("🏛️  No locations found in this world");
-        println!("   Use 'multiverse location create <name> --set display_name=\"<name>\" --set type=<type>' to create one");
+        say!("🏛️  No locations found in this world");
+        say!("   Use 'multiverse location create <name> --set display_name=\"<name>\" --set type=<type>' to create one");
         return Ok(());
     }
     
-    println!("🏛️  Locations in current world:");
+    say!("🏛️  Locations in current world:");
     
     for location in locations {
-        let status_emoji = match location.status {
+        let status_emoji = crate::status::status_emoji("location", location.status.as_str(), match location.status {
             LocationStatus::Active => "🟢",
             LocationStatus::Inactive => "⚫",
             LocationStatus::Destroyed => "💥", 
             LocationStatus::Hidden => "👻",
             LocationStatus::Unknown => "❓",
-        };
+            LocationStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
         
         let type_str = location.metadata.get("type")
             .and_then(|v| v.as_str())
             .unwrap_or("unknown");
             
-        println!("   {} {} - \"{}\" ({})", 
+        say!("   {} {} - \"{}\" ({})", 
             status_emoji, 
             location.name, 
             location.display_name,
@@ -116,68 +141,142 @@ fn handle_list() -> Result<()> {
         
         // Show key metadata fields
         if let Some(population) = location.metadata.get("population") {
-            println!("      Population: {}", population.as_str().unwrap_or("Unknown"));
+            say!("      Population: {}", population.as_str().unwrap_or("Unknown"));
         }
         if let Some(climate) = location.metadata.get("climate") {
-            println!("      Climate: {}", climate.as_str().unwrap_or("Unknown"));
+            say!("      Climate: {}", climate.as_str().unwrap_or("Unknown"));
         }
         
         if let Some(desc) = location.metadata.get("description") {
-            println!("      {}", desc.as_str().unwrap_or(""));
+            say!("      {}", desc.as_str().unwrap_or(""));
         }
     }
     
     Ok(())
 }
 
-fn handle_info(name: String) -> Result<()> {
+fn handle_info(name: String, at: Option<String>) -> Result<()> {
     let location = Location::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Location '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Location", &name))?;
     
-    println!("🏛️  Location: {} - \"{}\"", location.name, location.display_name);
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Location(location.name.clone()), at.as_deref())?;
+        return crate::output::emit_entity("location", None, &location, Some(relations));
+    }
+    
+    say!("🏛️  Location: {} - \"{}\"", location.name, location.display_name);
     
     if let Some(type_val) = location.metadata.get("type") {
-        println!("   Type: {}", type_val.as_str().unwrap_or("Unknown"));
+        say!("   Type: {}", type_val.as_str().unwrap_or("Unknown"));
     }
     
-    println!("   Status: {:?}", location.status);
-    println!("   Created: {}", location.created_at.format("%Y-%m-%d %H:%M"));
+    say!("   Status: {:?}", location.status);
+    say!("   Created: {}", location.created_at.format("%Y-%m-%d %H:%M"));
     
     if let Some(desc) = location.metadata.get("description") {
-        println!("   Description: {}", desc.as_str().unwrap_or(""));
+        say!("   Description: {}", desc.as_str().unwrap_or(""));
     }
     
     // Show metadata
     if !location.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &location.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
-    // TODO: Show episodes where location appears
-    println!("   Episodes: (to be implemented)");
+    show_entity_relations(&EntityType::Location(location.name.clone()), at.as_deref())?;
     
     Ok(())
 }
 
-fn handle_delete(name: String, force: bool) -> Result<()> {
+fn handle_delete(name: String, force: bool, on_relations: DeleteMode) -> Result<()> {
     let location = Location::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Location '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Location", &name))?;
     
+    let entity = EntityType::Location(name.clone());
+    let relations = check_entity_delete(&entity, on_relations)?;
+
     if !force {
-        println!("⚠️  Are you sure you want to delete location '{name}'?");
-        println!("   This will permanently delete the location from database");
-        println!("   Use --force to skip this confirmation");
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("delete location '{}'", name)));
+        }
+        say!("⚠️  Are you sure you want to delete location '{name}'?");
+        say!("   This will permanently delete the location from database");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will be removed as well", relations.len());
+        }
+        say!("   Use --force to skip this confirmation");
         return Ok(());
     }
     
-    println!("🗑️  Deleting location '{name}'...");
+    say!("🗑️  Deleting location '{name}'...");
     
+    release_entity(&entity, on_relations)?;
     location.delete(force)?;
     
-    println!("✅ Location '{name}' deleted!");
+    say!("✅ Location '{name}' deleted!");
+
+    if crate::output::is_structured() {
+        return crate::output::emit_entity("location", Some("deleted"), &location, Some(relations));
+    }
     
     Ok(())
 }
 
+fn handle_rename(name: String, new_name: String) -> Result<()> {
+    let mut location = Location::get(&name)?
+        .ok_or_else(|| crate::output::not_found("Location", &name))?;
+
+    say!("✏️  Renaming location '{name}' to '{new_name}'...");
+
+    location.rename(new_name.clone())?;
+
+    say!("✅ Location '{name}' is now '{new_name}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Location(location.name.clone()), None)?;
+        return crate::output::emit_entity("location", Some("renamed"), &location, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_merge(keep: String, drop: String, on_conflict: MergePolicy, force: bool) -> Result<()> {
+    let mut survivor = Location::get(&keep)?
+        .ok_or_else(|| crate::output::not_found("Location", &keep))?;
+    let duplicate = Location::get(&drop)?
+        .ok_or_else(|| crate::output::not_found("Location", &drop))?;
+
+    if !force {
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("merge location '{}' into '{}'", drop, keep)));
+        }
+        let relations = entity_relations(&EntityType::Location(drop.clone()), None)?;
+        let conflicts = metadata_conflicts(&survivor.metadata, &duplicate.metadata);
+        say!("⚠️  Are you sure you want to merge location '{drop}' into '{keep}'?");
+        say!("   '{drop}' will be deleted and kept as an alias of '{keep}'");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will move to '{keep}'", relations.len());
+        }
+        if !conflicts.is_empty() {
+            say!("   Conflicting metadata ({:?}): {}", on_conflict, conflicts.join(", "));
+        }
+        say!("   Use --force to skip this confirmation");
+        return Ok(());
+    }
+
+    say!("🔀 Merging location '{drop}' into '{keep}'...");
+
+    survivor.merge(duplicate, on_conflict)?;
+
+    say!("✅ Location '{drop}' merged into '{keep}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Location(survivor.name.clone()), None)?;
+        return crate::output::emit_entity("location", Some("merged"), &survivor, Some(relations));
+    }
+
+    Ok(())
+}
+
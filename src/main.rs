@@ -2,15 +2,18 @@ mod cli;
 
 use clap::Parser;
 use anyhow::Result;
-use cli::{Cli, Commands};
+use cli::{Cli, Commands, WorldCommand};
 use multiverse::*;
 use multiverse::timeline::handle_timeline_command;
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    output::set_format(cli.format);
     
-    match cli.command {
-        Commands::World { command } => handle_world_command(command),
+    // One connection and transaction per command: it all applies, or nothing does
+    let result = database::with_command_context(|| match cli.command {
+        Commands::World { command: WorldCommand::Doctor { fix } } => relations::handle_doctor(fix),
+        Commands::World { command: WorldCommand::World(command) } => handle_world_command(command),
         Commands::Character { command } => handle_character_command(command),
         Commands::Timeline { command } => handle_timeline_command(command),
         Commands::Info => handle_info(),
@@ -21,8 +24,19 @@ fn main() -> Result<()> {
         Commands::Faction { command } => handle_faction_command(command),
         Commands::System { command } => handle_system_command(command),
         Commands::Race { command } => handle_race_command(command),
-        Commands::Query { sql } => handle_query_command(sql),
+        Commands::Relation { command } => handle_relation_command(command),
+        Commands::Query { sql, repl: _, command } => handle_query_command(sql, command),
+        Commands::Migrate { status } => migrations::handle_migrate(status),
+    });
+
+    // Scripts get the error as a structured object on stdout
+    if let Err(error) = &result {
+        if output::is_structured() {
+            output::emit_error(error)?;
+            std::process::exit(1);
+        }
     }
+    result
 }
 
 fn handle_info() -> Result<()> {
@@ -46,7 +60,4 @@ fn handle_info() -> Result<()> {
     Ok(())
 }
 
-fn handle_query_command(sql: String) -> Result<()> {
-    database::execute_query(&sql)
-}
 
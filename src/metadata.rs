@@ -0,0 +1,134 @@
+//! Structured edits of entity metadata: lists, nested keys, raw JSON and removal
+//!
+//! ```text
+//! --set themes+=power                       append to a list (created when missing)
+//! --set themes-=betrayal                    remove from a list
+//! --set stats.strength=12                   nested key, intermediate objects are created
+//! --set-json appearance='{"eyes": "grey"}'  any JSON value
+//! --unset age --unset stats.strength        remove keys
+//! ```
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOp {
+    /// `key.path=value`
+    Assign,
+    /// `key+=value`
+    Append,
+    /// `key-=value`
+    Remove,
+}
+
+/// Split a --set key into a metadata path and operation. `themes+` appends, `themes-`
+/// removes and `stats.strength` assigns a nested key; plain keys return None.
+pub fn structured_key(key: &str) -> Option<(&str, SetOp)> {
+    if let Some(path) = key.strip_suffix('+') {
+        return Some((path, SetOp::Append));
+    }
+    if let Some(path) = key.strip_suffix('-') {
+        return Some((path, SetOp::Remove));
+    }
+    key.contains('.').then_some((key, SetOp::Assign))
+}
+
+/// Top-level metadata key a --set/--set-json/--unset key writes to
+pub fn top_level_key(key: &str) -> &str {
+    let path = structured_key(key).map_or(key, |(path, _)| path);
+    path.split('.').next().unwrap_or(path)
+}
+
+fn segments(path: &str) -> Result<Vec<&str>> {
+    let segments: Vec<&str> = path.split('.').collect();
+    if segments.iter().any(|segment| segment.trim().is_empty()) {
+        anyhow::bail!("Invalid metadata key '{}'", path);
+    }
+    Ok(segments)
+}
+
+/// The value at a dotted path, creating missing objects on the way (new leaves are null)
+fn slot_mut<'a>(metadata: &'a mut HashMap<String, JsonValue>, path: &str) -> Result<&'a mut JsonValue> {
+    let segments = segments(path)?;
+    let mut slot = metadata.entry(segments[0].to_string()).or_insert(JsonValue::Null);
+    for (depth, segment) in segments.iter().enumerate().skip(1) {
+        if slot.is_null() {
+            *slot = JsonValue::Object(Map::new());
+        }
+        let JsonValue::Object(object) = slot else {
+            anyhow::bail!("Cannot set '{}': '{}' is not an object", path, segments[..depth].join("."));
+        };
+        slot = object.entry(segment.to_string()).or_insert(JsonValue::Null);
+    }
+    Ok(slot)
+}
+
+/// Items of a list field. Text stored by a plain `--set themes=a,b` counts as a
+/// comma-separated list, so older values can be edited with += and -=.
+fn list_items(path: &str, value: &JsonValue) -> Result<Vec<JsonValue>> {
+    match value {
+        JsonValue::Null => Ok(Vec::new()),
+        JsonValue::Array(items) => Ok(items.clone()),
+        JsonValue::String(text) => Ok(text.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(JsonValue::from)
+            .collect()),
+        _ => anyhow::bail!("Field '{}' is not a list", path),
+    }
+}
+
+/// Apply an assignment or list edit at a dotted metadata path
+pub fn apply(metadata: &mut HashMap<String, JsonValue>, path: &str, op: SetOp, value: JsonValue) -> Result<()> {
+    let slot = slot_mut(metadata, path)?;
+    if op == SetOp::Assign {
+        *slot = value;
+        return Ok(());
+    }
+
+    if op == SetOp::Remove && slot.is_null() {
+        anyhow::bail!("Field '{}' is not set", path);
+    }
+    let mut items = list_items(path, slot)?;
+    // A schema list value ("a,b") adds or removes every element
+    let values = match value {
+        JsonValue::Array(values) => values,
+        value => vec![value],
+    };
+    for value in values {
+        match op {
+            SetOp::Append if !items.contains(&value) => items.push(value),
+            SetOp::Remove => items.retain(|item| *item != value),
+            _ => {}
+        }
+    }
+    *slot = JsonValue::Array(items);
+    Ok(())
+}
+
+/// Remove the key at a dotted path; false when it was not set
+pub fn unset(metadata: &mut HashMap<String, JsonValue>, path: &str) -> Result<bool> {
+    let segments = segments(path)?;
+    let (leaf, parents) = segments.split_last().expect("split always yields a segment");
+    if parents.is_empty() {
+        return Ok(metadata.remove(*leaf).is_some());
+    }
+
+    let mut value = match metadata.get_mut(parents[0]) {
+        Some(value) => value,
+        None => return Ok(false),
+    };
+    for segment in &parents[1..] {
+        match value.get_mut(*segment) {
+            Some(next) => value = next,
+            None => return Ok(false),
+        }
+    }
+    Ok(value.as_object_mut().is_some_and(|object| object.remove(*leaf).is_some()))
+}
+
+/// Parse a --set-json value
+pub fn parse_json(key: &str, text: &str) -> Result<JsonValue> {
+    serde_json::from_str(text).with_context(|| format!("Invalid JSON for '{}'", key))
+}
@@ -0,0 +1,367 @@
+//! Versioned schema migrations, applied in order whenever the world database is opened
+//!
+//! Each step runs in its own transaction and is recorded in `schema_migrations`, so a
+//! `world.db` written by any older version is brought up to date on first use. Steps must
+//! be safe on a database that already has their changes (tables and columns created by the
+//! lazy `init_table` calls of earlier versions): use `IF NOT EXISTS` and `ensure_column`.
+//! New steps go at the end of `MIGRATIONS` with the next version number; never edit or
+//! reorder a released one. Each step spells out the SQL it ran rather than reading the
+//! live `CREATE_SQL` or relation registry, so later schema changes need a new step, and
+//! nothing here depends on config.toml.
+
+use anyhow::{Context, Result};
+use rusqlite::{Connection, OptionalExtension, TransactionBehavior};
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::database::{ensure_column, table_exists};
+
+pub struct Migration {
+    pub version: i64,
+    pub description: &'static str,
+    apply: fn(&Connection) -> Result<()>,
+}
+
+pub const MIGRATIONS: &[Migration] = &[
+    Migration { version: 1, description: "Create entity tables", apply: create_entity_tables },
+    Migration { version: 2, description: "Create relation tables", apply: create_relation_tables },
+    Migration { version: 3, description: "Add metadata column to relation tables", apply: add_relation_metadata },
+    Migration { version: 4, description: "Add from/until period columns to relation tables", apply: add_relation_periods },
+    Migration { version: 5, description: "Store races and systems created_at as RFC 3339 text", apply: normalize_created_at },
+    Migration { version: 6, description: "Index relation targets and event sort keys", apply: create_indexes },
+    Migration { version: 7, description: "Reserved: config relation tables are upgraded on open", apply: upgrade_config_relations },
+];
+
+/// State of one migration for `multiverse migrate --status`
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: &'static str,
+    pub applied_at: Option<String>,
+}
+
+fn ensure_migrations_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS schema_migrations (
+            version INTEGER PRIMARY KEY,
+            applied_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn applied_versions(conn: &Connection) -> Result<HashMap<i64, String>> {
+    if !table_exists(conn, "schema_migrations")? {
+        return Ok(HashMap::new());
+    }
+    let mut stmt = conn.prepare("SELECT version, COALESCE(applied_at, '') FROM schema_migrations")?;
+    let versions = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<rusqlite::Result<HashMap<i64, String>>>()?;
+    Ok(versions)
+}
+
+/// Apply every pending migration and return those that ran
+pub fn migrate(conn: &Connection) -> Result<Vec<&'static Migration>> {
+    let applied = applied_versions(conn)?;
+    if MIGRATIONS.iter().all(|migration| applied.contains_key(&migration.version)) {
+        return Ok(Vec::new());
+    }
+    ensure_migrations_table(conn)?;
+    // Views would stop a step from dropping or renaming the tables they read; they are
+    // recreated by `get_connection` once the schema is current
+    crate::views::drop_query_views(conn)?;
+
+    // Rebuilding a table drops the old one, which must not touch the rows pointing at it.
+    // The pragma is ignored inside a transaction, so it is switched around all of them.
+    let foreign_keys: bool = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0))?;
+    conn.execute("PRAGMA foreign_keys = OFF", [])?;
+    let ran = apply_pending(conn);
+    conn.execute(&format!("PRAGMA foreign_keys = {}", if foreign_keys { "ON" } else { "OFF" }), [])?;
+    ran
+}
+
+fn apply_pending(conn: &Connection) -> Result<Vec<&'static Migration>> {
+    let mut ran = Vec::new();
+    for migration in MIGRATIONS {
+        // IMMEDIATE takes the write lock first, so two commands opening an old
+        // database at the same time do not both run a step
+        let tx = rusqlite::Transaction::new_unchecked(conn, TransactionBehavior::Immediate)?;
+        let done = tx.query_row(
+            "SELECT 1 FROM schema_migrations WHERE version = ?1",
+            [migration.version],
+            |_| Ok(()),
+        ).optional()?.is_some();
+        if done {
+            continue;
+        }
+
+        (migration.apply)(&tx)
+            .with_context(|| format!("Migration {} ({}) failed", migration.version, migration.description))?;
+        tx.execute(
+            "INSERT INTO schema_migrations (version, applied_at) VALUES (?1, ?2)",
+            rusqlite::params![migration.version, chrono::Utc::now().to_rfc3339()],
+        )?;
+        tx.commit()?;
+        ran.push(migration);
+    }
+    Ok(ran)
+}
+
+/// Every known migration with the time it was applied, None when pending
+pub fn migration_status(conn: &Connection) -> Result<Vec<MigrationStatus>> {
+    let applied = applied_versions(conn)?;
+    Ok(MIGRATIONS.iter()
+        .map(|migration| MigrationStatus {
+            version: migration.version,
+            description: migration.description,
+            applied_at: applied.get(&migration.version).cloned(),
+        })
+        .collect())
+}
+
+/// `multiverse migrate`: apply pending migrations, or with `--status` only report them
+pub fn handle_migrate(status_only: bool) -> Result<()> {
+    let db_path = crate::world::WorldConfig::get_database_path()?;
+    let conn = crate::database::open_database(&db_path)?;
+
+    if !status_only {
+        let ran = migrate(&conn)?;
+        crate::views::refresh_query_views(&conn)?;
+        if !crate::output::is_structured() {
+            if ran.is_empty() {
+                println!("✅ Database schema is up to date");
+            }
+            for migration in &ran {
+                println!("✅ Applied migration {}: {}", migration.version, migration.description);
+            }
+            return Ok(());
+        }
+    }
+
+    let status = migration_status(&conn)?;
+    if crate::output::is_structured() {
+        return crate::output::emit(&status);
+    }
+
+    let applied = status.iter().filter(|migration| migration.applied_at.is_some()).count();
+    println!("🗄️  Database schema: {} of {} migrations applied", applied, status.len());
+    for migration in &status {
+        match &migration.applied_at {
+            Some(applied_at) => println!("   ✅ {:>3}  {} ({})", migration.version, migration.description, applied_at),
+            None => println!("   ⏳ {:>3}  {} (pending)", migration.version, migration.description),
+        }
+    }
+    if applied < status.len() {
+        println!("💡 Pending migrations run the next time the world is used, or now with: multiverse migrate");
+    }
+    Ok(())
+}
+
+/// Entity tables as created by version 1
+const V1_ENTITY_TABLES: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS characters (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        display_name TEXT NOT NULL,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_at TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'Active'
+    )",
+    "CREATE TABLE IF NOT EXISTS locations (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        display_name TEXT NOT NULL,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_at TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'Active'
+    )",
+    "CREATE TABLE IF NOT EXISTS factions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        display_name TEXT NOT NULL,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_at TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'Active'
+    )",
+    RACES_TABLE,
+    SYSTEMS_TABLE,
+    "CREATE TABLE IF NOT EXISTS events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        display_name TEXT NOT NULL,
+        date_text TEXT NOT NULL DEFAULT '',
+        sort_key INTEGER NOT NULL DEFAULT 0,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_at TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'Active'
+    )",
+    "CREATE TABLE IF NOT EXISTS stories (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL UNIQUE,
+        display_name TEXT NOT NULL,
+        story_type TEXT NOT NULL DEFAULT 'Fantasy',
+        word_count INTEGER NOT NULL DEFAULT 0,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_at TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'Draft'
+    )",
+    "CREATE TABLE IF NOT EXISTS episodes (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        story TEXT NOT NULL,
+        number INTEGER NOT NULL,
+        title TEXT NOT NULL DEFAULT '',
+        word_count INTEGER NOT NULL DEFAULT 0,
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_at TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'Draft',
+        UNIQUE(story, number)
+    )",
+];
+
+/// Built-in relation tables of version 2: table, role column, from table, to table.
+/// Config-defined ones follow config.toml and are created and upgraded when the database
+/// is opened, see `relations::init_config_relation_tables`.
+const V2_RELATION_TABLES: &[(&str, &str, &str, &str)] = &[
+    ("character_episode_relations", "role", "characters", "episodes"),
+    ("character_character_relations", "relationship_type", "characters", "characters"),
+    ("character_location_relations", "relationship_type", "characters", "locations"),
+    ("character_faction_relations", "role", "characters", "factions"),
+    ("character_race_relations", "heritage", "characters", "races"),
+    ("character_system_relations", "usage_type", "characters", "systems"),
+    ("race_system_relations", "affinity", "races", "systems"),
+    ("location_faction_relations", "control_type", "locations", "factions"),
+    ("location_location_relations", "relationship_type", "locations", "locations"),
+    ("location_system_relations", "infrastructure_type", "locations", "systems"),
+    ("event_character_relations", "participation_type", "events", "characters"),
+    ("event_location_relations", "location_role", "events", "locations"),
+    ("event_faction_relations", "faction_role", "events", "factions"),
+];
+
+/// races and systems with a TEXT created_at: created so by version 1 on new databases, and
+/// the target of the version 5 rebuild on older ones
+const RACES_TABLE: &str = "CREATE TABLE IF NOT EXISTS races (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT UNIQUE NOT NULL,
+        display_name TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'Active',
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_at TEXT NOT NULL
+    )";
+
+const SYSTEMS_TABLE: &str = "CREATE TABLE IF NOT EXISTS systems (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT UNIQUE NOT NULL,
+        display_name TEXT NOT NULL,
+        system_type TEXT NOT NULL,
+        status TEXT NOT NULL DEFAULT 'Active',
+        metadata TEXT NOT NULL DEFAULT '{}',
+        created_at TEXT NOT NULL
+    )";
+
+fn create_entity_tables(conn: &Connection) -> Result<()> {
+    for sql in V1_ENTITY_TABLES {
+        conn.execute(sql, [])?;
+    }
+    Ok(())
+}
+
+fn create_relation_tables(conn: &Connection) -> Result<()> {
+    for (table, role_column, from_table, to_table) in V2_RELATION_TABLES {
+        conn.execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                from_id TEXT NOT NULL,
+                to_id TEXT NOT NULL,
+                {role_column} TEXT,
+                metadata TEXT DEFAULT '{{}}',
+                from_date TEXT,
+                from_sort_key INTEGER,
+                until_date TEXT,
+                until_sort_key INTEGER,
+                created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (from_id, to_id),
+                FOREIGN KEY (from_id) REFERENCES {from_table} (id),
+                FOREIGN KEY (to_id) REFERENCES {to_table} (id)
+            )"
+        ), [])?;
+    }
+    Ok(())
+}
+
+fn add_relation_metadata(conn: &Connection) -> Result<()> {
+    for (table, ..) in V2_RELATION_TABLES {
+        ensure_column(conn, table, "metadata", "TEXT DEFAULT '{}'")?;
+    }
+    Ok(())
+}
+
+fn add_relation_periods(conn: &Connection) -> Result<()> {
+    for (table, ..) in V2_RELATION_TABLES {
+        ensure_column(conn, table, "from_date", "TEXT")?;
+        ensure_column(conn, table, "from_sort_key", "INTEGER")?;
+        ensure_column(conn, table, "until_date", "TEXT")?;
+        ensure_column(conn, table, "until_sort_key", "INTEGER")?;
+    }
+    Ok(())
+}
+
+/// races and systems were created with `created_at DATETIME DEFAULT CURRENT_TIMESTAMP`, so rows
+/// inserted without a value hold "YYYY-MM-DD HH:MM:SS" and fail to load. Rebuild both tables with
+/// the TEXT column of the other entities and convert those values to RFC 3339.
+fn normalize_created_at(conn: &Connection) -> Result<()> {
+    rebuild_created_at(conn, "races", RACES_TABLE)?;
+    rebuild_created_at(conn, "systems", SYSTEMS_TABLE)
+}
+
+fn rebuild_created_at(conn: &Connection, table: &str, create_sql: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let columns = stmt.query_map([], |row| Ok((row.get::<_, String>(1)?, row.get::<_, String>(2)?)))?
+        .collect::<rusqlite::Result<Vec<(String, String)>>>()?;
+    let is_datetime = columns.iter()
+        .any(|(name, column_type)| name == "created_at" && column_type.eq_ignore_ascii_case("DATETIME"));
+    if !is_datetime {
+        return Ok(());
+    }
+
+    let rebuilt = format!("{}_rebuilt", table);
+    let create_rebuilt = create_sql.replacen(
+        &format!("CREATE TABLE IF NOT EXISTS {}", table),
+        &format!("CREATE TABLE {}", rebuilt),
+        1,
+    );
+    conn.execute(&create_rebuilt, [])?;
+
+    let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+    let values: Vec<String> = names.iter()
+        .map(|name| if *name == "created_at" {
+            "CASE WHEN created_at GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9] [0-9][0-9]:[0-9][0-9]:[0-9][0-9]' \
+             THEN replace(created_at, ' ', 'T') || '+00:00' ELSE created_at END".to_string()
+        } else {
+            name.to_string()
+        })
+        .collect();
+    conn.execute_batch(&format!(
+        "INSERT INTO {rebuilt} ({columns}) SELECT {values} FROM {table};
+         DROP TABLE {table};
+         ALTER TABLE {rebuilt} RENAME TO {table};",
+        rebuilt = rebuilt,
+        columns = names.join(", "),
+        values = values.join(", "),
+        table = table,
+    ))?;
+    Ok(())
+}
+
+fn create_indexes(conn: &Connection) -> Result<()> {
+    for (table, ..) in V2_RELATION_TABLES {
+        conn.execute(&format!("CREATE INDEX IF NOT EXISTS idx_{table}_to_id ON {table} (to_id)"), [])?;
+    }
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_events_sort_key ON events (sort_key)", [])?;
+    Ok(())
+}
+
+/// Version 7 upgraded the config-defined relation tables, which made the step depend on
+/// config.toml; they are now upgraded on every open instead. The number stays taken so
+/// databases that recorded it agree with this list.
+fn upgrade_config_relations(_conn: &Connection) -> Result<()> {
+    Ok(())
+}
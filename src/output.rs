@@ -0,0 +1,455 @@
+//! Machine-readable output selected with the global `--format` flag
+//! (json, ndjson, yaml, table, csv, tsv, markdown, plain)
+//!
+//! Without the flag commands print their usual prose and `query` prints JSON. With it, `list`, `info`, `create`,
+//! `update` and `delete` write one structured result to stdout (the serialized entity,
+//! with its relations), errors become `{"error": {"code": ..., "message": ...}}`, and the
+//! prose printed through `say!` moves to stderr.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value as JsonValue};
+use std::sync::Mutex;
+use crate::relations::EntityRelation;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    /// One compact JSON object per line
+    Ndjson,
+    /// Block-style YAML document
+    Yaml,
+    /// Aligned columns with a header row
+    Table,
+    /// Comma-separated values with a header row (RFC 4180 quoting)
+    Csv,
+    /// Tab-separated values with a header row; tabs, newlines and backslashes are escaped
+    Tsv,
+    /// Markdown table, ready to paste into notes
+    Markdown,
+    /// Tab-separated values without header or decoration
+    Plain,
+}
+
+static FORMAT: Mutex<Option<OutputFormat>> = Mutex::new(None);
+
+/// Record the --format flag at startup; the query shell's `.format` changes it later
+pub fn set_format(format: Option<OutputFormat>) {
+    *FORMAT.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = format;
+}
+
+pub fn format() -> Option<OutputFormat> {
+    *FORMAT.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Whether commands should emit a structured result instead of prose
+pub fn is_structured() -> bool {
+    format().is_some()
+}
+
+/// println! for human-readable prose: it moves to stderr when --format is set,
+/// so stdout carries only the structured result
+#[macro_export]
+macro_rules! say {
+    ($($arg:tt)*) => {
+        if $crate::output::is_structured() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// Stable error codes of the structured error object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    AlreadyExists,
+    ConfirmationRequired,
+    /// Refused because of dependent data (relations, episodes) or conflicting values
+    Conflict,
+    NotInWorld,
+    Database,
+    Error,
+}
+
+/// An error carrying its code; other errors report `error` (or `database` for SQLite failures)
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct CliError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn error(code: ErrorCode, message: impl Into<String>) -> anyhow::Error {
+        CliError { code, message: message.into() }.into()
+    }
+}
+
+/// "<Entity> '<name>' not found"
+pub fn not_found(entity: &str, name: &str) -> anyhow::Error {
+    CliError::error(ErrorCode::NotFound, format!("{} '{}' not found", entity, name))
+}
+
+/// Error for destructive commands run without --force in structured mode, where
+/// there is nobody to read the confirmation prompt
+pub fn confirmation_required(action: &str) -> anyhow::Error {
+    CliError::error(ErrorCode::ConfirmationRequired, format!("Use --force to {}", action))
+}
+
+fn error_code(error: &anyhow::Error) -> ErrorCode {
+    for cause in error.chain() {
+        if let Some(error) = cause.downcast_ref::<CliError>() {
+            return error.code;
+        }
+        if cause.is::<rusqlite::Error>() {
+            return ErrorCode::Database;
+        }
+    }
+    ErrorCode::Error
+}
+
+/// Write the structured error object for a failed command to stdout
+pub fn emit_error(error: &anyhow::Error) -> Result<()> {
+    emit(&serde_json::json!({
+        "error": {
+            "code": error_code(error),
+            "message": format!("{:#}", error),
+        }
+    }))
+}
+
+/// Serialized entity with its kind, an optional action ("created", "updated", "deleted")
+/// and, when given, its relations
+pub fn emit_entity<T: Serialize>(kind: &str, action: Option<&str>, entity: &T, relations: Option<Vec<EntityRelation>>) -> Result<()> {
+    let mut object = Map::new();
+    object.insert("kind".to_string(), JsonValue::from(kind));
+    if let Some(action) = action {
+        object.insert("action".to_string(), JsonValue::from(action));
+    }
+    if let JsonValue::Object(fields) = serde_json::to_value(entity)? {
+        object.extend(fields);
+    }
+    if let Some(relations) = relations {
+        object.insert("relations".to_string(), relations.iter().map(relation_json).collect());
+    }
+    emit(&JsonValue::Object(object))
+}
+
+/// Serialized entities of a list command, limited to `fields` when given
+pub fn emit_list<T: Serialize>(entities: &[T], fields: &[String]) -> Result<()> {
+    let mut rows = Vec::new();
+    for entity in entities {
+        let value = serde_json::to_value(entity)?;
+        if fields.is_empty() {
+            rows.push(value);
+        } else {
+            let selected: Map<String, JsonValue> = fields.iter()
+                .map(|field| (field.clone(), crate::listing::field_value(&value, field).cloned().unwrap_or(JsonValue::Null)))
+                .collect();
+            rows.push(JsonValue::Object(selected));
+        }
+    }
+    render(&JsonValue::Array(rows), (!fields.is_empty()).then_some(fields))
+}
+
+/// Result set of `multiverse query`. JSON and YAML keep the `{query, columns, rows, count}`
+/// envelope; the other formats print the rows with their columns in query order.
+pub fn emit_query(sql: &str, columns: &[String], rows: Vec<Vec<JsonValue>>) -> Result<()> {
+    let format = format().unwrap_or(OutputFormat::Json);
+    if !matches!(format, OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Ndjson) {
+        print_tabular(format, Some(columns.to_vec()), rows);
+        return Ok(());
+    }
+
+    // Joins repeat column names (name, metadata, ...); later ones become name:2, name:3
+    let mut keys: Vec<String> = Vec::with_capacity(columns.len());
+    for column in columns {
+        let mut key = column.clone();
+        let mut n = 1;
+        while keys.contains(&key) {
+            n += 1;
+            key = format!("{}:{}", column, n);
+        }
+        keys.push(key);
+    }
+    let objects: Vec<JsonValue> = rows.into_iter()
+        .map(|row| JsonValue::Object(keys.iter().cloned().zip(row).collect()))
+        .collect();
+
+    if format == OutputFormat::Ndjson {
+        return render(&JsonValue::Array(objects), None);
+    }
+    render(&serde_json::json!({
+        "query": sql,
+        "columns": columns,
+        "count": objects.len(),
+        "rows": objects,
+    }), None)
+}
+
+fn relation_json(relation: &EntityRelation) -> JsonValue {
+    let fields: Map<String, JsonValue> = relation.record.fields.iter()
+        .map(|(column, value)| (column.to_string(), JsonValue::from(value.as_str())))
+        .collect();
+    let period = &relation.record.period;
+
+    serde_json::json!({
+        "relation": relation.table.name(),
+        "direction": if relation.outgoing { "outgoing" } else { "incoming" },
+        "kind": relation.other_kind,
+        "name": relation.other_label,
+        "fields": fields,
+        "metadata": relation.record.metadata,
+        "from": period.from,
+        "until": period.until,
+    })
+}
+
+/// Render a value in the selected format (JSON when no format is set)
+pub fn emit<T: Serialize>(value: &T) -> Result<()> {
+    render(&serde_json::to_value(value)?, None)
+}
+
+/// `columns` fixes the column order of tabular output; by default it is the
+/// union of the object keys
+fn render(value: &JsonValue, columns: Option<&[String]>) -> Result<()> {
+    match format().unwrap_or(OutputFormat::Json) {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(value)?),
+        OutputFormat::Ndjson => match value {
+            JsonValue::Array(items) => {
+                for item in items {
+                    println!("{}", item);
+                }
+            }
+            other => println!("{}", other),
+        },
+        OutputFormat::Yaml => print!("{}", yaml(value)),
+        format => {
+            let (header, rows) = tabulate(value, columns);
+            print_tabular(format, header, rows);
+        }
+    }
+    Ok(())
+}
+
+/// Block-style YAML for a JSON value. Strings stay plain unless they would read back
+/// as another type or break the syntax; those are double-quoted with JSON escapes.
+fn yaml(value: &JsonValue) -> String {
+    let mut out = String::new();
+    write_yaml(&mut out, value, 0);
+    out
+}
+
+/// Write `value` as whole lines indented by `indent`
+fn write_yaml(out: &mut String, value: &JsonValue, indent: usize) {
+    let pad = " ".repeat(indent);
+    match value {
+        JsonValue::Array(items) if !items.is_empty() => {
+            for item in items {
+                out.push_str(&pad);
+                out.push('-');
+                write_yaml_child(out, item, indent + 2, true);
+            }
+        }
+        JsonValue::Object(object) if !object.is_empty() => {
+            for (key, item) in object {
+                out.push_str(&pad);
+                out.push_str(&yaml_string(key));
+                out.push(':');
+                write_yaml_child(out, item, indent + 2, false);
+            }
+        }
+        scalar => {
+            out.push_str(&pad);
+            out.push_str(&yaml_scalar(scalar));
+            out.push('\n');
+        }
+    }
+}
+
+/// Value after "-" or "key:". Nested collections continue on indented lines, except
+/// inside a list, where their first entry shares the dash line ("- name: jon").
+fn write_yaml_child(out: &mut String, value: &JsonValue, indent: usize, in_list: bool) {
+    let nested = match value {
+        JsonValue::Array(items) => !items.is_empty(),
+        JsonValue::Object(object) => !object.is_empty(),
+        _ => false,
+    };
+    if !nested {
+        out.push(' ');
+        out.push_str(&yaml_scalar(value));
+        out.push('\n');
+    } else if in_list {
+        let mut lines = String::new();
+        write_yaml(&mut lines, value, indent);
+        out.push(' ');
+        out.push_str(&lines[indent..]);
+    } else {
+        out.push('\n');
+        write_yaml(out, value, indent);
+    }
+}
+
+fn yaml_scalar(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => "null".to_string(),
+        JsonValue::String(text) => yaml_string(text),
+        JsonValue::Array(_) => "[]".to_string(),
+        JsonValue::Object(_) => "{}".to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn yaml_string(text: &str) -> String {
+    let reserved = ["null", "~", "true", "false", "yes", "no", "on", "off", "y", "n"]
+        .contains(&text.to_ascii_lowercase().as_str());
+    let looks_numeric = text.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '.' | '+'))
+        || text.parse::<f64>().is_ok();
+    let special_start = text.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@` ".contains(c));
+    let breaks_syntax = text.contains(": ") || text.contains(" #") || text.ends_with([':', ' '])
+        || text.contains(|c: char| c.is_control());
+
+    if text.is_empty() || reserved || looks_numeric || special_start || breaks_syntax {
+        JsonValue::from(text).to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Arrays of objects become one row per item under a header of columns; a single
+/// object becomes one key/value row per field, without header
+fn tabulate(value: &JsonValue, columns: Option<&[String]>) -> (Option<Vec<String>>, Vec<Vec<JsonValue>>) {
+    match value {
+        JsonValue::Array(items) => {
+            let mut columns: Vec<String> = columns.map(<[String]>::to_vec).unwrap_or_default();
+            if columns.is_empty() {
+                for item in items {
+                    for key in item.as_object().into_iter().flat_map(|object| object.keys()) {
+                        if !columns.contains(key) {
+                            columns.push(key.clone());
+                        }
+                    }
+                }
+            }
+            if columns.is_empty() {
+                return (None, items.iter().map(|item| vec![item.clone()]).collect());
+            }
+            let rows = items.iter()
+                .map(|item| columns.iter()
+                    .map(|column| item.get(column.as_str()).cloned().unwrap_or(JsonValue::Null))
+                    .collect())
+                .collect();
+            (Some(columns), rows)
+        }
+        JsonValue::Object(object) => (None, object.iter()
+            .map(|(key, value)| vec![JsonValue::from(key.as_str()), value.clone()])
+            .collect()),
+        other => (None, vec![vec![other.clone()]]),
+    }
+}
+
+/// Print rows in one of the tabular formats (table, csv, tsv, markdown, plain)
+pub fn print_tabular(format: OutputFormat, header: Option<Vec<String>>, rows: Vec<Vec<JsonValue>>) {
+    let rows: Vec<Vec<String>> = rows.iter().map(|row| row.iter().map(cell).collect()).collect();
+    match format {
+        OutputFormat::Table => print_table(header.into_iter().chain(rows).collect()),
+        OutputFormat::Csv => {
+            for row in header.iter().chain(&rows) {
+                println!("{}", row.iter().map(|cell| csv_field(cell)).collect::<Vec<_>>().join(","));
+            }
+        }
+        OutputFormat::Tsv => {
+            for row in header.iter().chain(&rows) {
+                println!("{}", row.iter().map(|cell| tsv_field(cell)).collect::<Vec<_>>().join("\t"));
+            }
+        }
+        OutputFormat::Markdown => {
+            let header = header.unwrap_or_else(|| match rows.first().map_or(1, Vec::len) {
+                2 => vec!["field".to_string(), "value".to_string()],
+                _ => vec!["value".to_string()],
+            });
+            println!("| {} |", header.iter().map(|cell| markdown_field(cell)).collect::<Vec<_>>().join(" | "));
+            println!("|{}|", vec!["---"; header.len()].join("|"));
+            for row in &rows {
+                println!("| {} |", row.iter().map(|cell| markdown_field(cell)).collect::<Vec<_>>().join(" | "));
+            }
+        }
+        _ => {
+            for row in rows {
+                println!("{}", row.join("\t"));
+            }
+        }
+    }
+}
+
+/// Widest a column may get in a table before its cells are cut with '…'. The last
+/// column is never cut, since nothing follows it.
+const MAX_COLUMN_WIDTH: usize = 40;
+
+/// Print rows as left-aligned columns separated by two spaces. Line breaks inside
+/// cells are flattened so every row stays on one line.
+pub fn print_table(rows: Vec<Vec<String>>) {
+    let columns = rows.first().map_or(0, Vec::len);
+    let rows: Vec<Vec<String>> = rows.into_iter()
+        .map(|row| row.into_iter().enumerate()
+            .map(|(column, cell)| {
+                let cell = cell.replace(['\n', '\r', '\t'], " ");
+                if column + 1 < columns { truncate(&cell, MAX_COLUMN_WIDTH) } else { cell }
+            })
+            .collect())
+        .collect();
+
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect();
+    for row in rows {
+        let cells: Vec<String> = row.iter().zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(width - 1).collect();
+    cut.push('…');
+    cut
+}
+
+/// Quote fields containing separators, quotes or line breaks; quotes are doubled
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) || text.starts_with(' ') || text.ends_with(' ') {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+fn tsv_field(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn markdown_field(text: &str) -> String {
+    text.replace('|', "\\|")
+        .replace("\r\n", "<br>")
+        .replace('\n', "<br>")
+}
+
+/// Strings print bare, nulls empty, nested values as compact JSON
+fn cell(value: &JsonValue) -> String {
+    match value {
+        JsonValue::Null => String::new(),
+        JsonValue::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
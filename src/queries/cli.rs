@@ -0,0 +1,23 @@
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum QueryCommands {
+    /// Run a saved query from .multiverse/queries/<name>.sql
+    Run {
+        /// Query name (file name without .sql)
+        name: String,
+        /// Value for a :name parameter of the query (repeatable: --param faction=stark)
+        #[arg(long = "param", value_parser = parse_key_val)]
+        params: Vec<(String, String)>,
+    },
+    /// List saved queries with their description and parameters
+    List,
+}
+
+/// Parse a single key-value pair for --param flag
+fn parse_key_val(s: &str) -> Result<(String, String), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let pos = s
+        .find('=')
+        .ok_or_else(|| format!("invalid KEY=value: no `=` found in `{s}`"))?;
+    Ok((s[..pos].to_string(), s[pos + 1..].to_string()))
+}
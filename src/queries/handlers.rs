@@ -0,0 +1,45 @@
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+use crate::queries::{QueryCommands, SavedQuery};
+use crate::world::WorldConfig;
+
+/// `query <sql>`, `query --repl` (no SQL) or one of the saved-query subcommands
+pub fn handle_query_command(sql: Option<String>, command: Option<QueryCommands>) -> Result<()> {
+    match (command, sql) {
+        (Some(QueryCommands::Run { name, params }), _) => handle_run(name, params),
+        (Some(QueryCommands::List), _) => handle_list(),
+        (None, Some(sql)) => crate::database::execute_query(&sql),
+        (None, None) => crate::repl::run_repl(),
+    }
+}
+
+fn handle_run(name: String, params: Vec<(String, String)>) -> Result<()> {
+    let query = SavedQuery::load(&name)?
+        .ok_or_else(|| crate::output::not_found("Query", &name))?;
+
+    let conn = crate::database::get_readonly_connection(&WorldConfig::get_database_path()?)?;
+    crate::database::run_query(&conn, &query.sql, &params)
+}
+
+fn handle_list() -> Result<()> {
+    let queries = SavedQuery::list()?;
+    let conn = crate::database::get_readonly_connection(&WorldConfig::get_database_path()?)?;
+
+    let rows = queries.iter()
+        .map(|query| {
+            // A query that does not prepare (e.g. a missing table) still shows up
+            let params = crate::database::query_parameters(&conn, &query.sql).unwrap_or_default();
+            vec![
+                JsonValue::from(query.name.as_str()),
+                JsonValue::from(params.join(" ")),
+                JsonValue::from(query.description.as_str()),
+            ]
+        })
+        .collect();
+
+    if crate::output::format().is_none() {
+        crate::output::set_format(Some(crate::output::OutputFormat::Table));
+    }
+    let columns: Vec<String> = ["name", "params", "description"].iter().map(|column| column.to_string()).collect();
+    crate::output::emit_query("query list", &columns, rows)
+}
@@ -0,0 +1,176 @@
+//! Saved queries: `.multiverse/queries/<name>.sql`
+//!
+//! The leading `--` comment of a file is its description. Queries take named parameters
+//! (`:faction`) bound from `query run <name> --param faction=stark`; a parameter left out is
+//! NULL, so optional ones read `(:faction IS NULL OR f.name = :faction)`.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use crate::world::WorldConfig;
+
+pub const QUERIES_DIR: &str = ".multiverse/queries";
+
+/// Consistency checks seeded into every world (file name, SQL)
+pub const BUILTIN_QUERIES: &[(&str, &str)] = &[
+    ("orphaned_characters", "\
+-- Active characters without a faction (with :faction, those who are not members of it)
+SELECT c.name, c.display_name
+FROM characters c
+WHERE c.status = 'Active'
+  AND NOT EXISTS (
+    SELECT 1 FROM character_faction_relations r
+    JOIN factions f ON f.id = r.to_id
+    WHERE r.from_id = c.id AND (:faction IS NULL OR f.name = :faction)
+  )
+ORDER BY c.name;
+"),
+    ("uncontrolled_locations", "\
+-- Active locations no faction controls
+SELECT l.name, l.display_name
+FROM locations l
+LEFT JOIN location_faction_relations r ON r.from_id = l.id
+WHERE r.from_id IS NULL AND l.status = 'Active'
+ORDER BY l.name;
+"),
+    ("empty_events", "\
+-- Events without participating characters
+SELECT e.name, e.display_name, e.date_text
+FROM events e
+LEFT JOIN event_character_relations r ON r.from_id = e.id
+WHERE r.from_id IS NULL
+ORDER BY e.sort_key, e.name;
+"),
+    ("memberless_factions", "\
+-- Active or allied factions without members
+SELECT f.name, f.display_name, f.status
+FROM factions f
+LEFT JOIN character_faction_relations r ON r.to_id = f.id
+WHERE r.to_id IS NULL AND f.status IN ('Active', 'Allied')
+ORDER BY f.name;
+"),
+    ("character_relations", "\
+-- Every relation of :character, in both directions
+SELECT relation, from_type, from_name, to_type, to_name, role
+FROM v_relations
+WHERE (from_type = 'character' AND from_name = :character)
+   OR (to_type = 'character' AND to_name = :character)
+ORDER BY relation, from_name, to_name;
+"),
+    ("location_timeline", "\
+-- Events at :location in timeline order
+SELECT e.name AS event, e.date_text, l.name AS location, r.location_role
+FROM events e
+JOIN event_location_relations r ON r.from_id = e.id
+JOIN locations l ON l.id = r.to_id
+WHERE l.name = :location
+ORDER BY e.sort_key, e.name;
+"),
+    ("faction_conflicts", "\
+-- Hostile factions paired with each active faction
+SELECT hostile.name AS hostile_faction, active.name AS active_faction
+FROM factions hostile
+JOIN factions active ON active.status = 'Active' AND active.id != hostile.id
+WHERE hostile.status = 'Hostile'
+ORDER BY hostile.name, active.name;
+"),
+    ("episodes_without_characters", "\
+-- Episodes with no characters assigned
+SELECT e.story, e.number, e.title
+FROM episodes e
+LEFT JOIN character_episode_relations r ON r.to_id = e.id
+WHERE r.to_id IS NULL
+ORDER BY e.story, e.number;
+"),
+    ("word_count_progression", "\
+-- Cumulative word count per story, episode by episode (:story limits it to one story)
+SELECT e.story, e.number, e.title, e.word_count,
+       SUM(e.word_count) OVER (PARTITION BY e.story ORDER BY e.number) AS cumulative_words
+FROM episodes e
+WHERE :story IS NULL OR e.story = :story
+ORDER BY e.story, e.number;
+"),
+    ("deceased_in_pending_events", "\
+-- Deceased characters taking part in pending events
+SELECT c.name AS character, e.name AS event, e.date_text
+FROM characters c
+JOIN event_character_relations r ON r.to_id = c.id
+JOIN events e ON e.id = r.from_id
+WHERE c.status = 'Deceased' AND e.status = 'Pending'
+ORDER BY c.name, e.sort_key;
+"),
+];
+
+#[derive(Debug, Clone)]
+pub struct SavedQuery {
+    pub name: String,
+    pub description: String,
+    pub sql: String,
+}
+
+impl SavedQuery {
+    fn parse(name: &str, sql: String) -> Self {
+        let description = sql.lines()
+            .map(str::trim)
+            .take_while(|line| line.starts_with("--"))
+            .map(|line| line.trim_start_matches('-').trim())
+            .collect::<Vec<_>>()
+            .join(" ");
+        SavedQuery { name: name.to_string(), description, sql }
+    }
+
+    /// Load `.multiverse/queries/<name>.sql`
+    pub fn load(name: &str) -> Result<Option<Self>> {
+        if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '-') {
+            anyhow::bail!("Invalid query name '{}': use letters, digits, '_' and '-'", name);
+        }
+        let path = queries_dir()?.join(format!("{}.sql", name));
+        if !path.exists() {
+            return Ok(None);
+        }
+        let sql = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(Some(Self::parse(name, sql)))
+    }
+
+    /// Every saved query, sorted by name
+    pub fn list() -> Result<Vec<Self>> {
+        let mut queries = Vec::new();
+        for entry in std::fs::read_dir(queries_dir()?)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("sql") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            let sql = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            queries.push(Self::parse(name, sql));
+        }
+        queries.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(queries)
+    }
+}
+
+/// The world's query library, seeded with the built-in queries when it does not exist yet
+pub fn queries_dir() -> Result<PathBuf> {
+    let world_root = WorldConfig::get_world_root()?;
+    let dir = world_root.join(QUERIES_DIR);
+    if !dir.exists() {
+        seed_queries(&world_root)?;
+    }
+    Ok(dir)
+}
+
+/// Write the built-in queries into `<world>/.multiverse/queries`, keeping files that exist
+pub fn seed_queries(world_root: &Path) -> Result<()> {
+    let dir = world_root.join(QUERIES_DIR);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("Failed to create {}", dir.display()))?;
+    for (name, sql) in BUILTIN_QUERIES {
+        let path = dir.join(format!("{}.sql", name));
+        if !path.exists() {
+            std::fs::write(&path, sql)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
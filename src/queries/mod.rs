@@ -0,0 +1,7 @@
+pub mod cli;
+pub mod handlers;
+pub mod library;
+
+pub use cli::QueryCommands;
+pub use handlers::handle_query_command;
+pub use library::{seed_queries, SavedQuery, BUILTIN_QUERIES};
@@ -3,21 +3,31 @@ use clap::Subcommand;
 #[derive(Subcommand)]
 pub enum RaceCommands {
     /// Create a new race with unified --set for all fields
+    #[command(after_help = crate::relations::relation_set_help("race"))]
     Create {
         /// Race name (unique identifier)
         name: String,
         /// Set any field (--set display_name="Name" --set description="Description" --set status="Active" --set lifespan=1000)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
     },
     
     /// List all races in the current world
-    List,
+    List {
+        #[command(flatten)]
+        options: crate::listing::ListArgs,
+    },
     
     /// Show details for a specific race
     Info {
         /// The unique name of the race
         name: String,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
     },
     
     /// Delete a race
@@ -27,16 +37,48 @@ pub enum RaceCommands {
         /// Skip the confirmation prompt
         #[arg(long)]
         force: bool,
+        /// What to do with existing relations: refuse, detach or cascade
+        #[arg(long, value_enum, default_value_t = crate::relations::DeleteMode::Refuse)]
+        on_relations: crate::relations::DeleteMode,
     },
 
     /// Update an existing race
+    #[command(after_help = crate::relations::relation_set_help("race"))]
     Update {
         /// Race name
         name: String,
         /// Set any field (--set display_name="Name" --set description="Description" --set status="Active" --set lifespan=1000)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
+        /// Remove a metadata field (--unset age --unset stats.strength)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+    /// Rename a race, keeping its relations
+    Rename {
+        /// Current race name
+        name: String,
+        /// New race name
+        new_name: String,
+    },
+    /// Merge a duplicate race into another one, moving its relations
+    Merge {
+        /// Race to keep
+        keep: String,
+        /// Duplicate race to merge in and delete (its name becomes an alias)
+        drop: String,
+        /// Metadata set differently on both: keep the survivor's value, take the duplicate's, or fail
+        #[arg(long, value_enum, default_value_t = crate::relations::MergePolicy::Keep)]
+        on_conflict: crate::relations::MergePolicy,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
     },
+    /// Show the field schema of races (built-in fields and [schema.race] in config.toml)
+    Schema,
 }
 
 /// Parse a single key-value pair for the --set flag
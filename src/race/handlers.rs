@@ -1,31 +1,35 @@
 use super::cli::RaceCommands;
 use super::models::{Race, RaceStatus};
-use crate::relations::{process_relations, EntityType, separate_relation_fields};
+use crate::relations::{process_relations, EntityType, separate_relation_fields, relation_keys_for, show_entity_relations, DeleteMode, check_entity_delete, release_entity, entity_relations, MergePolicy, metadata_conflicts};
 use anyhow::{Result, Context};
+use crate::say;
 
 pub fn handle_race_command(command: RaceCommands) -> Result<()> {
     match command {
-        RaceCommands::Create { name, set } => {
-            handle_create(name, set)
+        RaceCommands::Create { name, set, set_json } => {
+            handle_create(name, set, set_json)
         }
-        RaceCommands::List => handle_list(),
-        RaceCommands::Info { name } => handle_info(name),
-        RaceCommands::Delete { name, force } => handle_delete(name, force),
-        RaceCommands::Update { name, set } => {
-            handle_update(name, set)
+        RaceCommands::List { options } => handle_list(options),
+        RaceCommands::Schema => Race::show_schema(),
+        RaceCommands::Info { name, at } => handle_info(name, at),
+        RaceCommands::Delete { name, force, on_relations } => handle_delete(name, force, on_relations),
+        RaceCommands::Rename { name, new_name } => handle_rename(name, new_name),
+        RaceCommands::Merge { keep, drop, on_conflict, force } => handle_merge(keep, drop, on_conflict, force),
+        RaceCommands::Update { name, set, set_json, unset } => {
+            handle_update(name, set, set_json, unset)
         }
     }
 }
 
-fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("✨ Creating race: {}", name);
+fn handle_create(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>) -> Result<()> {
+    say!("✨ Creating race: {}", name);
 
     // Separate relation fields from regular fields  
-    let relation_keys = ["system", "character"];
+    let relation_keys = relation_keys_for("race");
     let (relation_fields, regular_fields) = separate_relation_fields(set_args, &relation_keys);
     
     // Create race with regular fields FIRST
-    let mut race = Race::create_new(name.clone(), regular_fields)?;
+    let mut race = Race::create_new(name.clone(), regular_fields, set_json)?;
     race.create().context("Failed to create race in database")?;
     
     // THEN process relations after race exists in database
@@ -33,45 +37,59 @@ fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
         process_relations(EntityType::Race(name.clone()), relation_fields)?;
     }
 
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Race(race.name.clone()), None)?;
+        return crate::output::emit_entity("race", Some("created"), &race, Some(relations));
+    }
+    
     show_created_race(&race)?;
     Ok(())
 }
 
-fn handle_list() -> Result<()> {
-    let races = Race::list().context("Failed to list races")?;
+fn handle_list(options: crate::listing::ListArgs) -> Result<()> {
+    let races = Race::list_with(&options).context("Failed to list races")?;
+    
+    if crate::output::is_structured() {
+        return crate::output::emit_list(&races, &options.fields);
+    }
+    
+    if !options.fields.is_empty() {
+        return crate::listing::print_fields(&races, &options.fields);
+    }
     
     if races.is_empty() {
-        println!("✨ No races found in this world");
-        println!("   Use 'multiverse race create <name> --set display_name=\"<name>\"' to create one");
+        say!("✨ No races found in this world");
+        say!("   Use 'multiverse race create <name> --set display_name=\"<name>\"' to create one");
         return Ok(());
     }
 
-    println!("✨ Races in current world:");
+    say!("✨ Races in current world:");
 
     for race in races {
-        let status_emoji = match race.status {
+        let status_emoji = crate::status::status_emoji("race", race.status.as_str(), match race.status {
             RaceStatus::Active => "🟢",
             RaceStatus::Inactive => "🟡",
             RaceStatus::Extinct => "🔴",
             RaceStatus::Legendary => "⭐",
             RaceStatus::Mythical => "✨",
-        };
+            RaceStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
 
-        println!(
+        say!(
             "   {} {} - \"{}\"",
             status_emoji, race.name, race.display_name
         );
 
         // Show key metadata fields
         if let Some(origin) = race.metadata.get("origin") {
-            println!("      Origin: {}", origin.as_str().unwrap_or("Unknown"));
+            say!("      Origin: {}", origin.as_str().unwrap_or("Unknown"));
         }
         if let Some(lifespan) = race.metadata.get("lifespan") {
-            println!("      Lifespan: {}", lifespan.as_str().unwrap_or("Unknown"));
+            say!("      Lifespan: {}", lifespan.as_str().unwrap_or("Unknown"));
         }
 
         if let Some(desc) = race.metadata.get("description") {
-            println!("      {}", desc.as_str().unwrap_or(""));
+            say!("      {}", desc.as_str().unwrap_or(""));
         }
     }
 
@@ -79,90 +97,171 @@ fn handle_list() -> Result<()> {
 }
 
 
-fn handle_info(name: String) -> Result<()> {
+fn handle_info(name: String, at: Option<String>) -> Result<()> {
     let race = Race::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Race '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Race", &name))?;
+    
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Race(race.name.clone()), at.as_deref())?;
+        return crate::output::emit_entity("race", None, &race, Some(relations));
+    }
 
-    println!("✨ Race: {} - \"{}\"", race.name, race.display_name);
-    println!("   Status: {:?}", race.status);
-    println!("   Created: {}", race.created_at.format("%Y-%m-%d %H:%M"));
+    say!("✨ Race: {} - \"{}\"", race.name, race.display_name);
+    say!("   Status: {:?}", race.status);
+    say!("   Created: {}", race.created_at.format("%Y-%m-%d %H:%M"));
 
     if let Some(desc) = race.metadata.get("description") {
-        println!("   Description: {}", desc.as_str().unwrap_or(""));
+        say!("   Description: {}", desc.as_str().unwrap_or(""));
     }
 
     // Show metadata
     if !race.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &race.metadata {
             if key != "description" {
-                println!("     {}: {}", key, value);
+                say!("     {}: {}", key, value);
             }
         }
     }
 
-    // TODO: Show characters of this race
-    println!("   Usage: (to be implemented)");
+    show_entity_relations(&EntityType::Race(race.name.clone()), at.as_deref())?;
 
     Ok(())
 }
 
-fn handle_delete(name: String, force: bool) -> Result<()> {
+fn handle_delete(name: String, force: bool, on_relations: DeleteMode) -> Result<()> {
     let _race = Race::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Race '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Race", &name))?;
+
+    let entity = EntityType::Race(name.clone());
+    let relations = check_entity_delete(&entity, on_relations)?;
 
     if !force {
-        println!("⚠️  Are you sure you want to delete race '{name}'?");
-        println!(
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("delete race '{}'", name)));
+        }
+        say!("⚠️  Are you sure you want to delete race '{name}'?");
+        say!(
             "   This will permanently delete the race and remove it from all character references"
         );
-        println!("   Use --force to skip this confirmation");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will be removed as well", relations.len());
+        }
+        say!("   Use --force to skip this confirmation");
         return Ok(());
     }
 
-    println!("🗑️  Deleting race '{name}'...");
+    say!("🗑️  Deleting race '{name}'...");
 
     let race = Race::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Race '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Race", &name))?;
+    release_entity(&entity, on_relations)?;
     race.delete(force)?;
 
-    println!("✅ Race '{name}' deleted!");
+    say!("✅ Race '{name}' deleted!");
+
+    if crate::output::is_structured() {
+        return crate::output::emit_entity("race", Some("deleted"), &race, Some(relations));
+    }
 
     Ok(())
 }
 
-fn handle_update(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("🔄 Updating race '{}'", name);
+fn handle_rename(name: String, new_name: String) -> Result<()> {
+    let mut race = Race::get(&name)?
+        .ok_or_else(|| crate::output::not_found("Race", &name))?;
+
+    say!("✏️  Renaming race '{name}' to '{new_name}'...");
+
+    race.rename(new_name.clone())?;
+
+    say!("✅ Race '{name}' is now '{new_name}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Race(race.name.clone()), None)?;
+        return crate::output::emit_entity("race", Some("renamed"), &race, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_merge(keep: String, drop: String, on_conflict: MergePolicy, force: bool) -> Result<()> {
+    let mut survivor = Race::get(&keep)?
+        .ok_or_else(|| crate::output::not_found("Race", &keep))?;
+    let duplicate = Race::get(&drop)?
+        .ok_or_else(|| crate::output::not_found("Race", &drop))?;
+
+    if !force {
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("merge race '{}' into '{}'", drop, keep)));
+        }
+        let relations = entity_relations(&EntityType::Race(drop.clone()), None)?;
+        let conflicts = metadata_conflicts(&survivor.metadata, &duplicate.metadata);
+        say!("⚠️  Are you sure you want to merge race '{drop}' into '{keep}'?");
+        say!("   '{drop}' will be deleted and kept as an alias of '{keep}'");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will move to '{keep}'", relations.len());
+        }
+        if !conflicts.is_empty() {
+            say!("   Conflicting metadata ({:?}): {}", on_conflict, conflicts.join(", "));
+        }
+        say!("   Use --force to skip this confirmation");
+        return Ok(());
+    }
+
+    say!("🔀 Merging race '{drop}' into '{keep}'...");
+
+    survivor.merge(duplicate, on_conflict)?;
+
+    say!("✅ Race '{drop}' merged into '{keep}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Race(survivor.name.clone()), None)?;
+        return crate::output::emit_entity("race", Some("merged"), &survivor, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_update(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>, unset: Vec<String>) -> Result<()> {
+    say!("🔄 Updating race '{}'", name);
 
     let mut race = Race::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Race '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Race", &name))?;
 
     // Process relations and get back non-relation fields
     let regular_fields = process_relations(EntityType::Race(name.clone()), set_args)?;
     
+    race.unset_fields(&unset)?;
+    race.process_json_args(set_json)?;
     // Update regular fields
     race.update(regular_fields)?;
 
-    println!("✅ Race '{}' updated!", name);
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Race(race.name.clone()), None)?;
+        return crate::output::emit_entity("race", Some("updated"), &race, Some(relations));
+    }
+
+    say!("✅ Race '{}' updated!", name);
     show_created_race(&race)?;
 
     Ok(())
 }
 
 fn show_created_race(race: &Race) -> Result<()> {
-    println!("   Display name: {}", race.display_name);
-    println!("   Status: {:?}", race.status);
+    say!("   Display name: {}", race.display_name);
+    say!("   Status: {:?}", race.status);
     
     if let Some(description) = race.metadata.get("description") {
-        println!("   Description: {}", description.as_str().unwrap_or(""));
+        say!("   Description: {}", description.as_str().unwrap_or(""));
     }
     
     // Show metadata
     if !race.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &race.metadata {
             if key != "description" {
-                println!("     {}: {}", key, value);
+                say!("     {}: {}", key, value);
             }
         }
     }
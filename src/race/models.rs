@@ -19,7 +19,7 @@ define_complete_entity!(
         display_name TEXT NOT NULL,
         status TEXT NOT NULL DEFAULT 'Active',
         metadata TEXT NOT NULL DEFAULT '{}',
-        created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        created_at TEXT NOT NULL
     )"
 );
 
@@ -34,13 +34,10 @@ impl Race {
         }
     }
     
-    /// Resolve race name to database ID
+    /// Resolve race name or alias to database ID
     pub fn resolve_id(name: &str) -> anyhow::Result<String> {
         let conn = Self::get_database_connection()?;
-        let mut stmt = conn.prepare("SELECT id FROM races WHERE name = ?")?;
-        let id: i32 = stmt.query_row([name], |row| {
-            row.get(0)
-        }).map_err(|_| anyhow::anyhow!("Race not found: '{}'", name))?;
+        let id = crate::relations::resolve_entity_id(&conn, RaceDb::TABLE, "Race", name)?;
         Ok(id.to_string())
     }
 }
\ No newline at end of file
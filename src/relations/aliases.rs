@@ -0,0 +1,158 @@
+//! Entity aliases and name resolution with "did you mean" suggestions
+//! character update aragorn --set alias=strider,elessar
+//! character update frodo --set character=strider*friend   # resolves to aragorn
+
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use crate::output::{CliError, ErrorCode};
+
+/// Metadata key holding the alternative names of an entity
+pub const ALIASES_KEY: &str = "aliases";
+
+/// Suggestions shown when a name cannot be resolved
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Aliases recorded in entity metadata
+pub fn aliases_of(metadata: &HashMap<String, JsonValue>) -> Vec<String> {
+    match metadata.get(ALIASES_KEY) {
+        Some(JsonValue::Array(values)) => values.iter()
+            .filter_map(|value| value.as_str().map(str::to_string))
+            .collect(),
+        Some(JsonValue::String(alias)) => parse_aliases(alias),
+        _ => Vec::new(),
+    }
+}
+
+/// Split a comma-separated `--set alias=` value, dropping blanks and repeats
+pub fn parse_aliases(value: &str) -> Vec<String> {
+    let mut aliases: Vec<String> = Vec::new();
+    for alias in value.split(',').map(str::trim).filter(|alias| !alias.is_empty()) {
+        if !aliases.iter().any(|existing| existing == alias) {
+            aliases.push(alias.to_string());
+        }
+    }
+    aliases
+}
+
+/// Store aliases in entity metadata; an empty list removes the key
+pub fn set_aliases(metadata: &mut HashMap<String, JsonValue>, aliases: Vec<String>) {
+    if aliases.is_empty() {
+        metadata.remove(ALIASES_KEY);
+    } else {
+        metadata.insert(ALIASES_KEY.to_string(), JsonValue::from(aliases));
+    }
+}
+
+/// Resolve an entity name to its id: exact name first, then aliases.
+/// On a miss the error suggests the closest names, e.g. "did you mean aragorn?"
+pub fn resolve_entity_id(conn: &Connection, table: &str, entity: &str, name: &str) -> Result<i32> {
+    // The common case is an exact name, answered by the UNIQUE index
+    let exact = conn.query_row(&format!("SELECT id FROM {} WHERE name = ?1", table), [name], |row| row.get(0))
+        .optional()?;
+    if let Some(id) = exact {
+        return Ok(id);
+    }
+
+    let mut stmt = conn.prepare(&format!("SELECT id, name, metadata FROM {}", table))?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?))
+    })?;
+
+    let mut candidates = Vec::new();
+    let mut alias_match = None;
+    for row in rows {
+        let (id, entity_name, metadata) = row?;
+        if entity_name == name {
+            return Ok(id);
+        }
+
+        let metadata: HashMap<String, JsonValue> = metadata
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        let aliases = aliases_of(&metadata);
+        if alias_match.is_none() && aliases.iter().any(|alias| alias == name) {
+            alias_match = Some(id);
+        }
+
+        candidates.push(entity_name);
+        candidates.extend(aliases);
+    }
+
+    if let Some(id) = alias_match {
+        return Ok(id);
+    }
+
+    let suggestions = closest_names(name, &candidates);
+    let message = if suggestions.is_empty() {
+        format!("{} not found: '{}'", entity, name)
+    } else {
+        format!("{} not found: '{}' (did you mean {}?)", entity, name, suggestions.join(", "))
+    };
+    Err(CliError::error(ErrorCode::NotFound, message))
+}
+
+/// Refuse aliases that are already the name or an alias of another entity in `table`,
+/// so every name resolves to exactly one entity
+pub fn check_aliases_available(conn: &Connection, table: &str, entity: &str, id: i32, metadata: &HashMap<String, JsonValue>) -> Result<()> {
+    let aliases = aliases_of(metadata);
+    // Episodes are named story:number and never resolved by alias
+    if aliases.is_empty() || table == "episodes" {
+        return Ok(());
+    }
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT name FROM {t} WHERE id != ?1 AND (name = ?2 OR EXISTS (
+             SELECT 1 FROM json_each(CASE WHEN json_valid({t}.metadata) THEN {t}.metadata ELSE '{{}}' END, '$.{key}')
+             WHERE value = ?2))",
+        t = table,
+        key = ALIASES_KEY,
+    ))?;
+    for alias in &aliases {
+        let owner: Option<String> = stmt.query_row(rusqlite::params![id, alias], |row| row.get(0)).optional()?;
+        if let Some(owner) = owner {
+            let message = if &owner == alias {
+                format!("Alias '{}' is already the name of {} '{}'", alias, entity, owner)
+            } else {
+                format!("Alias '{}' is already used by {} '{}'", alias, entity, owner)
+            };
+            return Err(CliError::error(ErrorCode::AlreadyExists, message));
+        }
+    }
+    Ok(())
+}
+
+/// Candidates within a small edit distance of `name`, closest first
+pub fn closest_names(name: &str, candidates: &[String]) -> Vec<String> {
+    let threshold = (name.chars().count() / 3).max(2);
+
+    let mut scored: Vec<(usize, &String)> = candidates.iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .collect();
+    scored.sort();
+    scored.dedup_by(|a, b| a.1 == b.1);
+
+    scored.into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, candidate)| candidate.clone())
+        .collect()
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
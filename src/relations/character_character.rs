@@ -0,0 +1,118 @@
+//! Character-Character relation implementation
+//! Syntax: character update ned_stark --set character=jon_snow*father
+//!
+//! The role describes the subject: ned_stark is the father of jon_snow.
+//! Each pair is stored once; the other side is derived with `inverse_relationship`.
+
+use crate::define_relation;
+use crate::character::models::Character;
+use crate::relations::handlers::{split_relation_specs, parse_relation_spec, apply_relation_updates};
+use crate::say;
+
+define_relation!(
+    CharacterCharacterRelation,
+    Character -> Character,
+    table: "character_character_relations",
+    from_table: "characters",
+    to_table: "characters",
+    keys: "character" -> "character",
+    default_role: "acquaintance",
+    fields: {
+        relationship_type: String,
+    },
+    inverse: inverse_relationship,
+    process: process_character_character_relations
+);
+
+/// Relationship types that read the same from both sides
+pub const SYMMETRIC_RELATIONSHIPS: &[&str] = &[
+    "sibling", "spouse", "twin", "cousin", "friend", "rival", "enemy", "ally", "lover",
+];
+
+/// Asymmetric relationship types with their inverse, as shown from the other side.
+/// Several types share an inverse (father and mother are both seen as child); the stored
+/// row keeps the specific one. Going back from a shared inverse, the first pair wins,
+/// so generic pairs come first.
+pub const INVERSE_RELATIONSHIPS: &[(&str, &str)] = &[
+    ("parent", "child"),
+    ("father", "child"),
+    ("mother", "child"),
+    ("grandparent", "grandchild"),
+    ("uncle", "nephew"),
+    ("aunt", "nephew"),
+    ("mentor", "student"),
+    ("master", "apprentice"),
+    ("liege", "vassal"),
+    ("guardian", "ward"),
+];
+
+/// Relationship type as seen from the other character
+pub fn inverse_relationship(relationship_type: &str) -> Option<String> {
+    if SYMMETRIC_RELATIONSHIPS.contains(&relationship_type) {
+        return Some(relationship_type.to_string());
+    }
+
+    INVERSE_RELATIONSHIPS.iter()
+        .find(|(forward, _)| *forward == relationship_type)
+        .map(|(_, inverse)| inverse.to_string())
+        .or_else(|| INVERSE_RELATIONSHIPS.iter()
+            .find(|(_, inverse)| *inverse == relationship_type)
+            .map(|(forward, _)| forward.to_string()))
+}
+
+/// Store the `from_id` -> `to_id` row as `to_id` -> `from_id`, keeping its metadata and period
+fn reverse_pair(from_id: &str, to_id: &str) -> anyhow::Result<()> {
+    let conn = crate::database::world_connection()?;
+    conn.execute(
+        &format!("UPDATE {} SET from_id = ?2, to_id = ?1 WHERE from_id = ?1 AND to_id = ?2", Relations::TABLE.table),
+        [from_id, to_id],
+    )?;
+    Ok(())
+}
+
+pub fn process_character_character_relations(character_name: &str, relations: &str) -> anyhow::Result<()> {
+    say!("🔗 Processing character-character relations for '{}'", character_name);
+
+    let from_id = Character::resolve_id(character_name)?;
+
+    for spec in split_relation_specs(relations) {
+        let spec = parse_relation_spec(spec, Relations::TABLE.default_role)?;
+        let other_name = spec.name;
+        let relationship_type = spec.role.to_string();
+        let updates = spec.metadata_updates()?;
+
+        let to_id = Character::resolve_id(other_name)?;
+        if from_id == to_id {
+            return Err(anyhow::anyhow!("A character cannot be related to itself: '{}'", character_name));
+        }
+
+        // A pair is stored once: when the other side already exists, update it with the inverse type
+        let reverse = CharacterCharacterRelation::list_for_entity(&to_id)?
+            .into_iter()
+            .find(|relation| relation.to_id == from_id);
+        let relation = match (reverse, inverse_relationship(&relationship_type)) {
+            // Already recorded from the other side (e.g. father when setting child): keep the more specific type
+            (Some(reverse), _) if inverse_relationship(&reverse.relationship_type).as_deref() == Some(relationship_type.as_str()) => reverse,
+            (Some(_), Some(inverse)) => CharacterCharacterRelation::new(to_id, from_id.clone(), inverse),
+            // A type without inverse cannot be stored on the other side's row: turn that row around
+            (Some(_), None) => {
+                reverse_pair(&to_id, &from_id)?;
+                CharacterCharacterRelation::new(from_id.clone(), to_id, relationship_type.clone())
+            }
+            (None, _) => CharacterCharacterRelation::new(from_id.clone(), to_id, relationship_type.clone()),
+        };
+
+        let is_new = relation.upsert()?;
+        if !updates.is_empty() {
+            apply_relation_updates(&Relations::TABLE, &relation.from_id, &relation.to_id, &updates)?;
+        }
+
+        if is_new {
+            say!("✅ Created relation: {} -> {} ({})", character_name, other_name, relationship_type);
+        } else {
+            say!("🔄 Updated relation: {} <-> {} ({})", character_name, other_name, relationship_type);
+        }
+    }
+
+    Ok(())
+}
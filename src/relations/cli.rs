@@ -0,0 +1,90 @@
+use clap::Subcommand;
+
+#[derive(Subcommand)]
+pub enum RelationCommands {
+    /// List relations, optionally only those touching one entity
+    List {
+        /// Entity type (character, location, faction, race, system, event, story, episode)
+        #[arg(requires = "name")]
+        entity_type: Option<String>,
+        /// Entity name (episodes use story:number)
+        name: Option<String>,
+        /// Only show one relation table (e.g. character_faction)
+        #[arg(long)]
+        table: Option<String>,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
+    },
+
+    /// Show the relation between two entities
+    Show {
+        /// Entity type of the first entity
+        from_type: String,
+        /// Name of the first entity
+        from: String,
+        /// Entity type of the second entity
+        to_type: String,
+        /// Name of the second entity
+        to: String,
+    },
+
+    /// Remove the relation between two entities
+    Remove {
+        /// Entity type of the first entity
+        from_type: String,
+        /// Name of the first entity
+        from: String,
+        /// Entity type of the second entity
+        to_type: String,
+        /// Name of the second entity
+        to: String,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Change the role of an existing relation
+    Retype {
+        /// Entity type of the first entity
+        from_type: String,
+        /// Name of the first entity
+        from: String,
+        /// Entity type of the second entity
+        to_type: String,
+        /// Name of the second entity
+        to: String,
+        /// New role (relationship type, heritage, affinity...)
+        role: String,
+    },
+
+    /// Set metadata on an existing relation (an empty value removes the key)
+    Update {
+        /// Entity type of the first entity
+        from_type: String,
+        /// Name of the first entity
+        from: String,
+        /// Entity type of the second entity
+        to_type: String,
+        /// Name of the second entity
+        to: String,
+        /// Metadata field (--set since=3019 --set rank=2 --set sworn=)
+        #[arg(long, value_parser = parse_key_val, required = true)]
+        set: Vec<(String, String)>,
+    },
+
+    /// Find relation rows and episodes pointing to deleted entities
+    Doctor {
+        /// Remove the orphaned rows
+        #[arg(long)]
+        fix: bool,
+    },
+}
+
+/// Parse a single key-value pair for --set flag
+fn parse_key_val(s: &str) -> Result<(String, String), Box<dyn std::error::Error + Send + Sync + 'static>> {
+    let pos = s
+        .find('=')
+        .ok_or_else(|| format!("invalid KEY=value: no `=` found in `{s}`"))?;
+    Ok((s[..pos].to_string(), s[pos + 1..].to_string()))
+}
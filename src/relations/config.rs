@@ -0,0 +1,138 @@
+//! User-defined relation types from `[relations.<name>]` sections of .multiverse/config.toml
+//!
+//! ```toml
+//! [relations.faction_alliance]
+//! from = "faction"
+//! to = "faction"
+//! role_column = "pact"            # optional, default "role"
+//! default_role = "ally"           # optional, default "related"
+//! roles = ["ally", "vassal"]      # optional, any role accepted when empty
+//! ```
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use anyhow::Result;
+use crate::relations::{custom, EntityType};
+use crate::relations::models::{RelationTable, no_inverse_role};
+
+/// Columns every relation table already has
+const RESERVED_COLUMNS: &[&str] = &[
+    "from_id", "to_id", "created_at", "metadata",
+    "from_date", "from_sort_key", "until_date", "until_sort_key",
+];
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RelationTypeConfig {
+    /// Entity type on the from side (the subject the role describes)
+    pub from: String,
+    /// Entity type on the to side
+    pub to: String,
+    #[serde(default = "default_role_column")]
+    pub role_column: String,
+    #[serde(default = "default_role")]
+    pub default_role: String,
+    /// Allowed role vocabulary
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+fn default_role_column() -> String {
+    "role".to_string()
+}
+
+fn default_role() -> String {
+    "related".to_string()
+}
+
+/// Read the `[relations.*]` sections of the current world's config.toml; they are
+/// validated against each other by `config_relation_tables`
+pub fn load_relation_types() -> BTreeMap<String, RelationTypeConfig> {
+    crate::config_sections::load_section("relations", |_, _: &RelationTypeConfig| Ok(()))
+}
+
+/// Lowercase SQL-safe identifier
+fn is_identifier(value: &str) -> bool {
+    value.starts_with(|c: char| c.is_ascii_lowercase())
+        && value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+impl RelationTypeConfig {
+    /// Validate the config and build its table description.
+    /// `known` holds the tables already registered, whose --set keys must not be shadowed.
+    pub fn to_table(&self, name: &str, known: &[RelationTable]) -> Result<RelationTable> {
+        if !is_identifier(name) {
+            anyhow::bail!("relation name must be lowercase letters, digits and underscores");
+        }
+        let from = EntityType::from_kind(&self.from, String::new())?;
+        let to = EntityType::from_kind(&self.to, String::new())?;
+
+        if !is_identifier(&self.role_column) || RESERVED_COLUMNS.contains(&self.role_column.as_str()) {
+            anyhow::bail!("invalid role_column '{}'", self.role_column);
+        }
+        if !self.roles.is_empty() && !self.roles.contains(&self.default_role) {
+            anyhow::bail!("default_role '{}' is not one of the allowed roles", self.default_role);
+        }
+
+        let table_name = format!("{}_relations", name);
+        for table in known {
+            if table.table == table_name {
+                anyhow::bail!("table '{}' already exists", table_name);
+            }
+            let same_pair = (table.from_kind == from.kind() && table.to_kind == to.kind())
+                || (table.from_kind == to.kind() && table.to_kind == from.kind());
+            if same_pair {
+                anyhow::bail!("{} ↔ {} is already linked by {}", from.kind(), to.kind(), table.name());
+            }
+        }
+
+        // Config is read once per process: the descriptions are leaked so they share
+        // the 'static layout of the tables generated by define_relation!
+        let roles: Vec<&'static str> = self.roles.iter().cloned().map(leak).collect();
+        Ok(RelationTable {
+            table: leak(table_name),
+            from_table: from.table(),
+            to_table: to.table(),
+            from_kind: from.kind(),
+            to_kind: to.kind(),
+            default_role: leak(self.default_role.clone()),
+            fields: Box::leak(vec![leak(self.role_column.clone())].into_boxed_slice()),
+            roles: Box::leak(roles.into_boxed_slice()),
+            init_table: custom::init_table,
+            upsert: custom::upsert,
+            process: None,
+            list_all: custom::list_all,
+            list_for_entity: custom::list_for_entity,
+            list_for_target: custom::list_for_target,
+            delete: custom::delete,
+            retype: custom::retype,
+            inverse_role: no_inverse_role,
+        })
+    }
+}
+
+/// Relation tables declared in config.toml, loaded once per process.
+/// Invalid sections are reported and skipped; outside a world there are none.
+pub fn config_relation_tables(builtin: &[RelationTable]) -> &'static [RelationTable] {
+    static TABLES: OnceLock<Vec<RelationTable>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let types = load_relation_types();
+
+        let mut known = builtin.to_vec();
+        let mut tables = Vec::new();
+        for (name, config) in types {
+            match config.to_table(&name, &known) {
+                Ok(table) => {
+                    known.push(table);
+                    tables.push(table);
+                }
+                Err(e) => eprintln!("Warning: ignoring [relations.{}] in config.toml: {}", name, e),
+            }
+        }
+        tables
+    })
+}
@@ -0,0 +1,129 @@
+//! Generic storage for relation types declared in config.toml
+//! Uses the same table layout as `define_relation!`: from_id, to_id, one role column, created_at
+
+use anyhow::Result;
+use rusqlite::Connection;
+use std::rc::Rc;
+use crate::relations::metadata;
+use crate::relations::period::{self, RelationPeriod};
+use crate::relations::models::{RelationRecord, RelationTable};
+
+fn open_connection() -> Result<Rc<Connection>> {
+    crate::database::world_connection()
+}
+
+/// Create the table of a config-defined relation, or add the columns and index it lacks;
+/// run when the world database is opened (see `registry::init_config_relation_tables`),
+/// never on reads or writes
+pub fn init_table(table: &RelationTable, conn: &Connection) -> Result<()> {
+    let sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} (
+            from_id TEXT NOT NULL,
+            to_id TEXT NOT NULL,
+            {} TEXT,
+            metadata TEXT DEFAULT '{{}}',
+            from_date TEXT,
+            from_sort_key INTEGER,
+            until_date TEXT,
+            until_sort_key INTEGER,
+            created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+            PRIMARY KEY (from_id, to_id),
+            FOREIGN KEY (from_id) REFERENCES {} (id),
+            FOREIGN KEY (to_id) REFERENCES {} (id)
+        )",
+        table.table,
+        table.role_column(),
+        table.from_table,
+        table.to_table
+    );
+
+    conn.execute(&sql, [])?;
+    metadata::ensure_metadata_column(conn, table.table)?;
+    period::ensure_period_columns(conn, table.table)?;
+    crate::relations::create_target_index(conn, table.table)
+}
+
+/// Select records, optionally filtered on one id column
+fn select(table: &RelationTable, filter: Option<(&str, &str)>) -> Result<Vec<RelationRecord>> {
+    let conn = open_connection()?;
+
+    let role_column = table.role_column();
+    let condition = filter.map(|(column, _)| format!(" WHERE {} = ?", column)).unwrap_or_default();
+    let sql = format!(
+        "SELECT from_id, to_id, {}, metadata, {} FROM {}{} ORDER BY from_id, to_id",
+        role_column, period::PERIOD_COLUMNS, table.table, condition
+    );
+    let mut stmt = conn.prepare(&sql)?;
+
+    let map_row = |row: &rusqlite::Row| -> rusqlite::Result<RelationRecord> {
+        Ok(RelationRecord {
+            table: table.table,
+            from_id: row.get(0)?,
+            to_id: row.get(1)?,
+            fields: vec![(role_column, row.get::<_, Option<String>>(2)?.unwrap_or_default())],
+            metadata: metadata::from_column(row.get(3)?),
+            period: RelationPeriod::from_row(row)?,
+        })
+    };
+    let rows = match filter {
+        Some((_, id)) => stmt.query_map([id], map_row)?,
+        None => stmt.query_map([], map_row)?,
+    };
+
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row?);
+    }
+    Ok(results)
+}
+
+pub fn list_all(table: &RelationTable) -> Result<Vec<RelationRecord>> {
+    select(table, None)
+}
+
+pub fn list_for_entity(table: &RelationTable, from_id: &str) -> Result<Vec<RelationRecord>> {
+    select(table, Some(("from_id", from_id)))
+}
+
+pub fn list_for_target(table: &RelationTable, to_id: &str) -> Result<Vec<RelationRecord>> {
+    select(table, Some(("to_id", to_id)))
+}
+
+/// Create or update a relation, returns true when created
+pub fn upsert(table: &RelationTable, from_id: &str, to_id: &str, role: &str) -> Result<bool> {
+    let conn = open_connection()?;
+
+    let sql = format!(
+        "UPDATE {} SET {} = ? WHERE from_id = ? AND to_id = ?",
+        table.table, table.role_column()
+    );
+    if conn.execute(&sql, [role, from_id, to_id])? > 0 {
+        return Ok(false);
+    }
+
+    let sql = format!(
+        "INSERT INTO {} (from_id, to_id, {}, created_at) VALUES (?, ?, ?, CURRENT_TIMESTAMP)",
+        table.table, table.role_column()
+    );
+    conn.execute(&sql, [from_id, to_id, role])?;
+    Ok(true)
+}
+
+pub fn delete(table: &RelationTable, from_id: &str, to_id: &str) -> Result<()> {
+    let conn = open_connection()?;
+    let sql = format!("DELETE FROM {} WHERE from_id = ? AND to_id = ?", table.table);
+    conn.execute(&sql, [from_id, to_id])?;
+    Ok(())
+}
+
+pub fn retype(table: &RelationTable, from_id: &str, to_id: &str, role: &str) -> Result<()> {
+    let conn = open_connection()?;
+    let sql = format!(
+        "UPDATE {} SET {} = ? WHERE from_id = ? AND to_id = ?",
+        table.table, table.role_column()
+    );
+    if conn.execute(&sql, [role, from_id, to_id])? == 0 {
+        anyhow::bail!("No relation found to update between {} and {}", from_id, to_id);
+    }
+    Ok(())
+}
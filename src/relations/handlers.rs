@@ -1,9 +1,12 @@
 //! Relation handlers - extract and process relation fields from --set parameters
 
 use anyhow::Result;
-use crate::relations::{process_character_episode_relations, process_character_location_relations, process_character_faction_relations, process_character_race_relations, process_character_system_relations, process_race_system_relations, process_location_faction_relations, process_location_location_relations, process_location_system_relations, process_event_character_relations, process_event_location_relations, process_event_faction_relations};
 use serde_json::{json, Value as JsonValue};
-use crate::world::WorldConfig;
+use crate::relations::{metadata, period};
+use crate::relations::{RelationCommands, RelationRecord, RelationTable, relation_tables, find_relation_for_key};
+use rusqlite::{Connection, OptionalExtension};
+use std::rc::Rc;
+use crate::say;
 
 
 
@@ -21,32 +24,117 @@ pub fn separate_relation_fields(set_args: Vec<(String, String)>, relation_keys:
     (relation_fields, regular_fields)
 }
 
-/// Parse entity spec into name and role
-fn parse_entity_role(spec: &str) -> (&str, &str) {
-    if spec.contains('*') {
-        let parts: Vec<&str> = spec.split('*').collect();
-        (parts[0], parts.get(1).unwrap_or(&"unknown"))
-    } else {
-        (spec, "unknown")
+/// One `name*role{key=value,...}` item of a relation --set value
+#[derive(Debug, Clone)]
+pub struct RelationSpec<'a> {
+    pub name: &'a str,
+    pub role: &'a str,
+    /// Raw key=value pairs between the braces, if any
+    pub metadata: Option<&'a str>,
+}
+
+impl RelationSpec<'_> {
+    /// Metadata updates given in the spec
+    pub fn metadata_updates(&self) -> Result<Vec<(String, String)>> {
+        self.metadata.map(metadata::parse_pairs).unwrap_or_else(|| Ok(Vec::new()))
+    }
+
+    /// Rebuild the spec around another entity name (used to flip reverse relations)
+    pub fn with_name(&self, name: &str) -> String {
+        match self.metadata {
+            Some(pairs) => format!("{}*{}{{{}}}", name, self.role, pairs),
+            None => format!("{}*{}", name, self.role),
+        }
+    }
+}
+
+/// Split a relation --set value on commas outside of `{...}` metadata
+pub fn split_relation_specs(relations_str: &str) -> Vec<&str> {
+    let mut specs = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (index, c) in relations_str.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                specs.push(&relations_str[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
     }
+    specs.push(&relations_str[start..]);
+    specs.into_iter().map(str::trim).filter(|spec| !spec.is_empty()).collect()
 }
 
-/// Process reverse relations generically
-fn process_reverse_relation<F>(
-    from_entity: &str,
-    combined: &str, 
-    processor: F
-) -> Result<()>
-where 
-    F: Fn(&str, &str) -> Result<()>
-{
-    for spec in combined.split(',') {
-        let spec = spec.trim();
-        if !spec.is_empty() {
-            let (entity_name, role) = parse_entity_role(spec);
-            let relation_spec = format!("{}*{}", from_entity, role);
-            processor(entity_name, &relation_spec)?;
+/// Parse entity spec into name, role and metadata
+pub fn parse_relation_spec<'a>(spec: &'a str, default_role: &'a str) -> Result<RelationSpec<'a>> {
+    let (spec, metadata) = match spec.find('{') {
+        Some(open) => {
+            let pairs = spec[open + 1..].strip_suffix('}')
+                .ok_or_else(|| anyhow::anyhow!("Unclosed metadata in relation '{}'", spec))?;
+            (&spec[..open], Some(pairs))
         }
+        None => (spec, None),
+    };
+
+    let (name, role) = match spec.split_once('*') {
+        Some((name, role)) if !role.trim().is_empty() => (name.trim(), role.trim()),
+        Some((name, _)) => (name.trim(), default_role),
+        None => (spec.trim(), default_role),
+    };
+    Ok(RelationSpec { name, role, metadata })
+}
+
+/// Apply `{key=value}` updates to an existing relation row:
+/// from/until set the timeline period, every other key goes to the JSON metadata
+pub fn apply_relation_updates(table: &RelationTable, from_id: &str, to_id: &str, updates: &[(String, String)]) -> Result<()> {
+    let conn = open_connection()?;
+
+    let (period_updates, metadata_updates): (Vec<_>, Vec<_>) = updates.iter()
+        .cloned()
+        .partition(|(key, _)| period::PERIOD_KEYS.contains(&key.as_str()));
+
+    metadata::update_metadata(&conn, table, from_id, to_id, &metadata_updates)?;
+    if !period_updates.is_empty() {
+        period::update_period(&conn, table, from_id, to_id, &period_updates)?;
+    }
+    Ok(())
+}
+
+/// Process forward relations (entity on the from side) for a relation table
+fn process_forward_relation(table: &RelationTable, from_name: &str, relations_str: &str) -> Result<()> {
+    if let Some(process) = table.process {
+        return process(from_name, relations_str);
+    }
+
+    let from_id = EntityType::from_kind(table.from_kind, from_name.to_string())?.resolve_id()?;
+    for spec in split_relation_specs(relations_str) {
+        let spec = parse_relation_spec(spec, table.default_role)?;
+        table.check_role(spec.role)?;
+        let updates = spec.metadata_updates()?;
+        let to_id = EntityType::from_kind(table.to_kind, spec.name.to_string())?.resolve_id()?;
+        let is_new = table.upsert(&from_id, &to_id, spec.role)?;
+        if !updates.is_empty() {
+            apply_relation_updates(table, &from_id, &to_id, &updates)?;
+        }
+
+        // Print appropriate success message
+        if is_new {
+            say!("✅ Created relation: {} -> {}", from_name, spec.name);
+        } else {
+            say!("🔄 Updated relation: {} <-> {}", from_name, spec.name);
+        }
+    }
+    Ok(())
+}
+
+/// Process reverse relations (entity on the to side) by flipping each spec
+fn process_reverse_relation(table: &RelationTable, to_name: &str, relations_str: &str) -> Result<()> {
+    for spec in split_relation_specs(relations_str) {
+        let spec = parse_relation_spec(spec, table.default_role)?;
+        process_forward_relation(table, spec.name, &spec.with_name(to_name))?;
     }
     Ok(())
 }
@@ -63,191 +151,487 @@ pub enum EntityType {
     System(String),
 }
 
+impl EntityType {
+    /// Build an entity reference from a CLI type keyword and a name
+    pub fn from_kind(kind: &str, name: String) -> Result<Self> {
+        match kind {
+            "character" => Ok(EntityType::Character(name)),
+            "episode" => Ok(EntityType::Episode(name)),
+            "location" => Ok(EntityType::Location(name)),
+            "faction" => Ok(EntityType::Faction(name)),
+            "race" => Ok(EntityType::Race(name)),
+            "event" => Ok(EntityType::Event(name)),
+            "story" => Ok(EntityType::Story(name)),
+            "system" => Ok(EntityType::System(name)),
+            _ => Err(anyhow::anyhow!(
+                "Unknown entity type '{}'. Expected one of: character, episode, location, faction, race, event, story, system",
+                kind
+            )),
+        }
+    }
+
+    /// CLI type keyword
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EntityType::Character(_) => "character",
+            EntityType::Episode(_) => "episode",
+            EntityType::Location(_) => "location",
+            EntityType::Faction(_) => "faction",
+            EntityType::Race(_) => "race",
+            EntityType::Event(_) => "event",
+            EntityType::Story(_) => "story",
+            EntityType::System(_) => "system",
+        }
+    }
+
+    /// Entity name (story:number for episodes)
+    pub fn name(&self) -> &str {
+        match self {
+            EntityType::Character(name)
+            | EntityType::Episode(name)
+            | EntityType::Location(name)
+            | EntityType::Faction(name)
+            | EntityType::Race(name)
+            | EntityType::Event(name)
+            | EntityType::Story(name)
+            | EntityType::System(name) => name,
+        }
+    }
+
+    /// Database table holding this entity type
+    pub fn table(&self) -> &'static str {
+        match self {
+            EntityType::Character(_) => "characters",
+            EntityType::Episode(_) => "episodes",
+            EntityType::Location(_) => "locations",
+            EntityType::Faction(_) => "factions",
+            EntityType::Race(_) => "races",
+            EntityType::Event(_) => "events",
+            EntityType::Story(_) => "stories",
+            EntityType::System(_) => "systems",
+        }
+    }
+
+    /// Resolve the entity name to its database ID
+    pub fn resolve_id(&self) -> Result<String> {
+        match self {
+            EntityType::Character(name) => crate::character::Character::resolve_id(name),
+            EntityType::Episode(name) => crate::episode::Episode::resolve_id(name),
+            EntityType::Location(name) => crate::location::Location::resolve_id(name),
+            EntityType::Faction(name) => crate::faction::Faction::resolve_id(name),
+            EntityType::Race(name) => crate::race::Race::resolve_id(name),
+            EntityType::Event(name) => crate::event::Event::resolve_id(name),
+            EntityType::Story(name) => crate::story::Story::resolve_id(name),
+            EntityType::System(name) => crate::system::System::resolve_id(name),
+        }
+    }
+}
+
+/// CLI type keyword for an entity table
+pub fn kind_for_table(table: &str) -> &str {
+    match table {
+        "characters" => "character",
+        "episodes" => "episode",
+        "locations" => "location",
+        "factions" => "faction",
+        "races" => "race",
+        "events" => "event",
+        "stories" => "story",
+        "systems" => "system",
+        other => other,
+    }
+}
+
+/// Resolve an entity ID back to its name (story:number for episodes)
+pub fn entity_label(conn: &Connection, table: &str, id: &str) -> Result<String> {
+    let sql = if table == "episodes" {
+        "SELECT story || ':' || number FROM episodes WHERE id = ?1".to_string()
+    } else {
+        format!("SELECT name FROM {} WHERE id = ?1", table)
+    };
+
+    let label: Option<String> = conn.query_row(&sql, [id], |row| row.get(0)).optional()?;
+    Ok(label.unwrap_or_else(|| format!("#{} (missing)", id)))
+}
+
 /// Process all relation fields from --set parameters
 /// Takes entity type with name and all --set parameters
-/// Processes relation ones based on key name and entity type, returns the remaining non-relation parameters
+/// Processes relation ones based on the relation registry, returns the remaining non-relation parameters
 pub fn process_relations(
     entity: EntityType,
     mut set_args: Vec<(String, String)>
 ) -> Result<Vec<(String, String)>> {
     let mut processed_relations = Vec::new();
     
-    // Group relation values by type  
-    let mut relation_groups: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+    // Group relation values by key, keeping the order they were given in
+    let mut relation_groups: Vec<(String, Vec<String>)> = Vec::new();
     
     // Extract all relation fields and group them
     set_args.retain(|(key, value)| {
-        let is_relation = match (key.as_str(), &entity) {
-            // Character relations
-            ("episode", EntityType::Character(_)) => true,
-            ("location", EntityType::Character(_)) => true,
-            ("faction", EntityType::Character(_)) => true,
-            ("race", EntityType::Character(_)) => true,
-            ("system", EntityType::Character(_)) => true,
-            
-            // Episode relations (reverse)
-            ("character", EntityType::Episode(_)) => true,
-            
-            // Location relations
-            ("faction", EntityType::Location(_)) => true,
-            ("location", EntityType::Location(_)) => true,
-            ("system", EntityType::Location(_)) => true,
-            ("character", EntityType::Location(_)) => true, // reverse
-            
-            // Faction relations (reverse)
-            ("character", EntityType::Faction(_)) => true,
-            ("location", EntityType::Faction(_)) => true,
-            ("event", EntityType::Faction(_)) => true,
-            
-            // Race relations
-            ("system", EntityType::Race(_)) => true,
-            ("character", EntityType::Race(_)) => true, // reverse
-            
-            // Event relations
-            ("character", EntityType::Event(_)) => true,
-            ("location", EntityType::Event(_)) => true,
-            ("faction", EntityType::Event(_)) => true,
-            
-            // Story relations (reverse)
-            ("episode", EntityType::Story(_)) => true,
-            
-            // System relations (reverse)
-            ("character", EntityType::System(_)) => true,
-            ("location", EntityType::System(_)) => true,
-            ("race", EntityType::System(_)) => true,
-            
-            _ => false,
-        };
+        let is_relation = find_relation_for_key(entity.kind(), key).is_some()
+            || (key == "episode" && matches!(entity, EntityType::Story(_)));
         
         if is_relation {
-            relation_groups.entry(key.clone()).or_insert_with(Vec::new).push(value.clone());
+            match relation_groups.iter_mut().find(|(group_key, _)| group_key == key) {
+                Some((_, values)) => values.push(value.clone()),
+                None => relation_groups.push((key.clone(), vec![value.clone()])),
+            }
             false // Remove from original list
         } else {
             true // Keep non-relation fields
         }
     });
     
-    // Process each relation type using perfect pattern matching
     for (relation_type, values) in relation_groups {
-        if !values.is_empty() {
-            let combined = values.join(",");
-            
-            match (relation_type.as_str(), &entity) {
-                // Character relations (forward)
-                ("episode", EntityType::Character(name)) => {
-                    process_character_episode_relations(name, &combined)?;
-                    processed_relations.push("episode");
-                },
-                ("location", EntityType::Character(name)) => {
-                    process_character_location_relations(name, &combined)?;
-                    processed_relations.push("location");
-                },
-                ("faction", EntityType::Character(name)) => {
-                    process_character_faction_relations(name, &combined)?;
-                    processed_relations.push("faction");
-                },
-                ("race", EntityType::Character(name)) => {
-                    process_character_race_relations(name, &combined)?;
-                    processed_relations.push("race");
-                },
-                ("system", EntityType::Character(name)) => {
-                    process_character_system_relations(name, &combined)?;
-                    processed_relations.push("system");
-                },
-                
-                // Episode relations (reverse)
-                ("character", EntityType::Episode(episode_id)) => {
-                    process_reverse_relation(episode_id, &combined, process_character_episode_relations)?;
-                    processed_relations.push("character");
-                },
-                
-                // Location relations (forward and reverse)
-                ("faction", EntityType::Location(name)) => {
-                    process_location_faction_relations(name, &combined)?;
-                    processed_relations.push("faction");
-                },
-                ("location", EntityType::Location(name)) => {
-                    process_location_location_relations(name, &[combined])?;
-                    processed_relations.push("location");
-                },
-                ("system", EntityType::Location(name)) => {
-                    process_location_system_relations(name, &combined)?;
-                    processed_relations.push("system");
-                },
-                ("character", EntityType::Location(location_name)) => {
-                    process_reverse_relation(location_name, &combined, process_character_location_relations)?;
-                    processed_relations.push("character");
-                },
-                
-                // Faction relations (reverse)
-                ("character", EntityType::Faction(faction_name)) => {
-                    process_reverse_relation(faction_name, &combined, process_character_faction_relations)?;
-                    processed_relations.push("character");
-                },
-                ("location", EntityType::Faction(faction_name)) => {
-                    process_reverse_relation(faction_name, &combined, process_location_faction_relations)?;
-                    processed_relations.push("location");
-                },
-                ("event", EntityType::Faction(faction_name)) => {
-                    process_reverse_relation(faction_name, &combined, process_event_faction_relations)?;
-                    processed_relations.push("event");
-                },
-                
-                // Race relations (forward and reverse)
-                ("system", EntityType::Race(name)) => {
-                    process_race_system_relations(name, &combined)?;
-                    processed_relations.push("system");
-                },
-                ("character", EntityType::Race(race_name)) => {
-                    process_reverse_relation(race_name, &combined, process_character_race_relations)?;
-                    processed_relations.push("character");
-                },
-                
-                // Event relations (forward)
-                ("character", EntityType::Event(name)) => {
-                    process_event_character_relations(name, &combined)?;
-                    processed_relations.push("character");
-                },
-                ("location", EntityType::Event(name)) => {
-                    process_event_location_relations(name, &combined)?;
-                    processed_relations.push("location");
-                },
-                ("faction", EntityType::Event(name)) => {
-                    process_event_faction_relations(name, &combined)?;
-                    processed_relations.push("faction");
-                },
-                
-                // System relations (reverse)
-                ("character", EntityType::System(system_name)) => {
-                    process_reverse_relation(system_name, &combined, process_character_system_relations)?;
-                    processed_relations.push("character");
-                },
-                ("location", EntityType::System(system_name)) => {
-                    process_reverse_relation(system_name, &combined, process_location_system_relations)?;
-                    processed_relations.push("location");
-                },
-                ("race", EntityType::System(system_name)) => {
-                    process_reverse_relation(system_name, &combined, process_race_system_relations)?;
-                    processed_relations.push("race");
-                },
-                
-                // Story relations (managed automatically)
-                ("episode", EntityType::Story(_story_name)) => {
-                    eprintln!("Warning: Story-episode relations are managed automatically. Episodes belong to stories by design.");
-                    processed_relations.push("episode");
-                },
-                
-                _ => {
-                    eprintln!("Warning: Unsupported relation '{}' for entity {:?}", relation_type, entity);
-                }
+        let combined = values.join(",");
+
+        // Story relations (managed automatically)
+        if let EntityType::Story(_) = entity {
+            eprintln!("Warning: Story-episode relations are managed automatically. Episodes belong to stories by design.");
+            processed_relations.push(relation_type);
+            continue;
+        }
+
+        match find_relation_for_key(entity.kind(), &relation_type) {
+            Some((table, false)) => process_forward_relation(&table, entity.name(), &combined)?,
+            Some((table, true)) => process_reverse_relation(&table, entity.name(), &combined)?,
+            None => {
+                eprintln!("Warning: Unsupported relation '{}' for entity {:?}", relation_type, entity);
+                continue;
             }
         }
+        processed_relations.push(relation_type);
     }
     
     if !processed_relations.is_empty() {
-        println!("🔗 Processed relations: {}", processed_relations.join(", "));
+        say!("🔗 Processed relations: {}", processed_relations.join(", "));
     }
     
     // Return remaining non-relation fields
     Ok(set_args)
 }
+
+pub fn handle_relation_command(command: RelationCommands) -> Result<()> {
+    match command {
+        RelationCommands::List { entity_type, name, table, at } => handle_list(entity_type, name, table, at),
+        RelationCommands::Show { from_type, from, to_type, to } => {
+            handle_show(from_type, from, to_type, to)
+        }
+        RelationCommands::Remove { from_type, from, to_type, to, force } => {
+            handle_remove(from_type, from, to_type, to, force)
+        }
+        RelationCommands::Retype { from_type, from, to_type, to, role } => {
+            handle_retype(from_type, from, to_type, to, role)
+        }
+        RelationCommands::Update { from_type, from, to_type, to, set } => {
+            handle_update(from_type, from, to_type, to, set)
+        }
+        RelationCommands::Doctor { fix } => crate::relations::handle_doctor(fix),
+    }
+}
+
+fn open_connection() -> Result<Rc<Connection>> {
+    crate::database::world_connection()
+}
+
+/// Relation tables that exist in the database, optionally filtered by name
+fn existing_tables(conn: &Connection, filter: Option<&str>) -> Result<Vec<RelationTable>> {
+    if let Some(name) = filter {
+        if !relation_tables().iter().any(|t| t.name() == name || t.table == name) {
+            let known: Vec<&str> = relation_tables().iter().map(|t| t.name()).collect();
+            anyhow::bail!("Unknown relation table '{}'. Known tables: {}", name, known.join(", "));
+        }
+    }
+
+    let mut tables = Vec::new();
+    for table in relation_tables() {
+        if filter.is_some_and(|name| table.name() != name && table.table != name) {
+            continue;
+        }
+        if crate::database::table_exists(conn, table.table)? {
+            tables.push(*table);
+        }
+    }
+    Ok(tables)
+}
+
+/// Find the relation table linking two entities and the (from_id, to_id) pair in table order.
+/// The flag is true when the pair is stored in the opposite direction of the arguments.
+fn locate_relation(conn: &Connection, first: &EntityType, second: &EntityType) -> Result<(RelationTable, String, String, bool)> {
+    let first_id = first.resolve_id()?;
+    let second_id = second.resolve_id()?;
+
+    for table in existing_tables(conn, None)? {
+        if table.from_table == first.table() && table.to_table == second.table() {
+            // Tables linking a type to itself store each pair once, in either direction
+            if table.from_table == table.to_table
+                && find_record(&table, &first_id, &second_id)?.is_none()
+                && find_record(&table, &second_id, &first_id)?.is_some()
+            {
+                return Ok((table, second_id, first_id, true));
+            }
+            return Ok((table, first_id, second_id, false));
+        }
+    }
+    for table in existing_tables(conn, None)? {
+        if table.from_table == second.table() && table.to_table == first.table() {
+            return Ok((table, second_id, first_id, true));
+        }
+    }
+
+    anyhow::bail!("No relation type links {} and {}", first.kind(), second.kind())
+}
+
+fn find_record(table: &RelationTable, from_id: &str, to_id: &str) -> Result<Option<RelationRecord>> {
+    Ok(table.list_for_entity(from_id)?
+        .into_iter()
+        .find(|record| record.to_id == to_id))
+}
+
+/// Timeline sort key of an --at date
+fn at_sort_key(at: Option<&str>) -> Result<Option<i64>> {
+    at.map(period::sort_key).transpose()
+}
+
+/// Header suffix naming the --at date
+fn at_label(at: Option<&str>) -> String {
+    at.map(|date| format!(" at {}", date)).unwrap_or_default()
+}
+
+fn handle_list(entity_type: Option<String>, name: Option<String>, table_filter: Option<String>, at: Option<String>) -> Result<()> {
+    let conn = open_connection()?;
+    let tables = existing_tables(&conn, table_filter.as_deref())?;
+    let at_key = at_sort_key(at.as_deref())?;
+
+    if let (Some(kind), Some(name)) = (entity_type, name) {
+        let entity = EntityType::from_kind(&kind, name)?;
+        let relations = collect_entity_relations(&conn, &entity, &tables, at_key)?;
+
+        say!("🔗 Relations of {} '{}'{}:", entity.kind(), entity.name(), at_label(at.as_deref()));
+        for relation in &relations {
+            say!("   {} {} {} ({})", relation.arrow(), relation.other_kind, relation.other_label, relation.record.describe());
+        }
+
+        if relations.is_empty() {
+            say!("   No relations found");
+        }
+        return Ok(());
+    }
+
+    let mut count = 0;
+    for table in &tables {
+        let records: Vec<RelationRecord> = table.list_all()?
+            .into_iter()
+            .filter(|record| at_key.is_none_or(|key| record.period.is_valid_at(key)))
+            .collect();
+        if records.is_empty() {
+            continue;
+        }
+
+        say!("🔗 {} ({} → {}):", table.name(), kind_for_table(table.from_table), kind_for_table(table.to_table));
+        for record in &records {
+            let source = entity_label(&conn, table.from_table, &record.from_id)?;
+            let target = entity_label(&conn, table.to_table, &record.to_id)?;
+            say!("   {} → {} ({})", source, target, record.describe());
+        }
+        count += records.len();
+    }
+
+    if count == 0 && at.is_some() {
+        say!("🔗 No relations valid{}", at_label(at.as_deref()));
+    } else if count == 0 {
+        say!("🔗 No relations found in this world");
+        say!("   Use '--set <entity_type>=<name>*<role>' on create/update to add one");
+    }
+
+    Ok(())
+}
+
+/// A relation seen from one entity, pointing to the entity on the other side
+#[derive(Debug, Clone)]
+pub struct EntityRelation {
+    pub outgoing: bool,
+    pub table: RelationTable,
+    pub other_kind: &'static str,
+    pub other_label: String,
+    pub record: RelationRecord,
+}
+
+impl EntityRelation {
+    pub fn arrow(&self) -> &'static str {
+        if self.outgoing { "→" } else { "←" }
+    }
+}
+
+/// Collect every outgoing and incoming relation of an entity from the given tables, only those valid at `at_key` when given
+fn collect_entity_relations(conn: &Connection, entity: &EntityType, tables: &[RelationTable], at_key: Option<i64>) -> Result<Vec<EntityRelation>> {
+    let entity_id = entity.resolve_id()?;
+    let mut relations = Vec::new();
+
+    for table in tables {
+        if table.from_table == entity.table() {
+            for record in table.list_for_entity(&entity_id)? {
+                relations.push(EntityRelation {
+                    outgoing: true,
+                    table: *table,
+                    other_kind: kind_for_table(table.to_table),
+                    other_label: entity_label(conn, table.to_table, &record.to_id)?,
+                    record,
+                });
+            }
+        }
+        if table.to_table == entity.table() {
+            for mut record in table.list_for_target(&entity_id)? {
+                // Show self-referential relations from this side when the role has an inverse
+                let inverse = (table.inverse_role)(record.role());
+                let outgoing = table.from_table == table.to_table && inverse.is_some();
+                if let (true, Some(inverse), Some(field)) = (outgoing, inverse, record.fields.first_mut()) {
+                    field.1 = inverse;
+                }
+
+                relations.push(EntityRelation {
+                    outgoing,
+                    table: *table,
+                    other_kind: kind_for_table(table.from_table),
+                    other_label: entity_label(conn, table.from_table, &record.from_id)?,
+                    record,
+                });
+            }
+        }
+    }
+
+    if let Some(key) = at_key {
+        relations.retain(|relation| relation.record.period.is_valid_at(key));
+    }
+    Ok(relations)
+}
+
+/// List every relation of an entity across all relation tables
+pub fn entity_relations(entity: &EntityType, at: Option<&str>) -> Result<Vec<EntityRelation>> {
+    let conn = open_connection()?;
+    let tables = existing_tables(&conn, None)?;
+    collect_entity_relations(&conn, entity, &tables, at_sort_key(at)?)
+}
+
+/// Print the relation graph of an entity grouped by the type of the related entity.
+/// Used by every `info` command.
+pub fn show_entity_relations(entity: &EntityType, at: Option<&str>) -> Result<()> {
+    let relations = entity_relations(entity, at)?;
+
+    if relations.is_empty() {
+        say!("   Relations{}: none", at_label(at));
+        return Ok(());
+    }
+
+    let mut groups: std::collections::BTreeMap<&str, Vec<&EntityRelation>> = std::collections::BTreeMap::new();
+    for relation in &relations {
+        groups.entry(relation.other_kind).or_default().push(relation);
+    }
+
+    say!("   Relations{}:", at_label(at));
+    for (kind, group) in groups {
+        say!("     {} ({}):", kind, group.len());
+        for relation in group {
+            say!("       {} {} ({})", relation.arrow(), relation.other_label, relation.record.describe());
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_show(from_type: String, from: String, to_type: String, to: String) -> Result<()> {
+    let conn = open_connection()?;
+    let first = EntityType::from_kind(&from_type, from)?;
+    let second = EntityType::from_kind(&to_type, to)?;
+
+    let (table, from_id, to_id, _) = locate_relation(&conn, &first, &second)?;
+    let record = find_record(&table, &from_id, &to_id)?
+        .ok_or_else(|| anyhow::anyhow!(
+            "No relation between {} '{}' and {} '{}'",
+            first.kind(), first.name(), second.kind(), second.name()
+        ))?;
+
+    say!("🔗 Relation: {} → {}",
+        entity_label(&conn, table.from_table, &record.from_id)?,
+        entity_label(&conn, table.to_table, &record.to_id)?
+    );
+    say!("   Table: {}", table.table);
+    say!("   From: {} #{}", kind_for_table(table.from_table), record.from_id);
+    say!("   To: {} #{}", kind_for_table(table.to_table), record.to_id);
+    for (column, value) in &record.fields {
+        say!("   {}: {}", column, value);
+    }
+    for (key, value) in &record.metadata {
+        say!("   {}: {}", key, metadata::display_value(value));
+    }
+
+    Ok(())
+}
+
+fn handle_remove(from_type: String, from: String, to_type: String, to: String, force: bool) -> Result<()> {
+    let conn = open_connection()?;
+    let first = EntityType::from_kind(&from_type, from)?;
+    let second = EntityType::from_kind(&to_type, to)?;
+
+    let (table, from_id, to_id, _) = locate_relation(&conn, &first, &second)?;
+    let record = find_record(&table, &from_id, &to_id)?
+        .ok_or_else(|| anyhow::anyhow!(
+            "No relation between {} '{}' and {} '{}'",
+            first.kind(), first.name(), second.kind(), second.name()
+        ))?;
+
+    if !force {
+        say!("⚠️  Are you sure you want to remove the relation between {} '{}' and {} '{}' ({})?",
+            first.kind(), first.name(), second.kind(), second.name(), record.describe());
+        say!("   Use --force to skip this confirmation");
+        return Ok(());
+    }
+
+    table.delete(&from_id, &to_id)?;
+
+    say!("🗑️  Removed relation: {} '{}' <-> {} '{}'", first.kind(), first.name(), second.kind(), second.name());
+
+    Ok(())
+}
+
+fn handle_retype(from_type: String, from: String, to_type: String, to: String, role: String) -> Result<()> {
+    let conn = open_connection()?;
+    let first = EntityType::from_kind(&from_type, from)?;
+    let second = EntityType::from_kind(&to_type, to)?;
+
+    let (table, from_id, to_id, reversed) = locate_relation(&conn, &first, &second)?;
+
+    // The role describes the first entity: store its inverse when the pair is kept the other way round,
+    // unless the stored role already reads as `role` from here (father seen from the child)
+    let stored_role = if reversed && table.from_table == table.to_table {
+        match find_record(&table, &from_id, &to_id)? {
+            Some(record) if (table.inverse_role)(record.role()).as_deref() == Some(role.as_str()) => record.role().to_string(),
+            _ => (table.inverse_role)(&role).unwrap_or_else(|| role.clone()),
+        }
+    } else {
+        role.clone()
+    };
+    table.check_role(&stored_role)?;
+    table.retype(&from_id, &to_id, &stored_role)?;
+
+    say!("🔄 Updated relation: {} '{}' <-> {} '{}' ({}: {})",
+        first.kind(), first.name(), second.kind(), second.name(), table.role_column(), role);
+
+    Ok(())
+}
+
+fn handle_update(from_type: String, from: String, to_type: String, to: String, set: Vec<(String, String)>) -> Result<()> {
+    let conn = open_connection()?;
+    let first = EntityType::from_kind(&from_type, from)?;
+    let second = EntityType::from_kind(&to_type, to)?;
+
+    let (table, from_id, to_id, _) = locate_relation(&conn, &first, &second)?;
+    apply_relation_updates(&table, &from_id, &to_id, &set)?;
+    let record = find_record(&table, &from_id, &to_id)?
+        .ok_or_else(|| anyhow::anyhow!("No relation found to update between {} and {}", from_id, to_id))?;
+
+    say!("🔄 Updated relation: {} '{}' <-> {} '{}' ({})",
+        first.kind(), first.name(), second.kind(), second.name(), record.describe());
+
+    Ok(())
+}
@@ -0,0 +1,196 @@
+//! Referential integrity between entities and relation rows
+//! Relation ids are stored as TEXT, so deletes handle dependent rows explicitly:
+//! character delete aragorn --force --on-relations detach
+
+use anyhow::Result;
+use clap::ValueEnum;
+use rusqlite::Connection;
+use crate::episode::Episode;
+use crate::relations::{EntityType, EntityRelation, entity_relations, relation_tables};
+use crate::output::{CliError, ErrorCode};
+use crate::say;
+
+/// What to do with the relations of an entity being deleted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DeleteMode {
+    /// Abort and list the relations that still exist
+    Refuse,
+    /// Remove the relation rows, keep the related entities
+    Detach,
+    /// Remove the relation rows and the entities owned by this one (a story's episodes)
+    Cascade,
+}
+
+/// Episodes owned by a story entity
+fn owned_episodes(entity: &EntityType) -> Result<Vec<Episode>> {
+    match entity {
+        EntityType::Story(name) => Episode::list_for_story(name),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Relations affected by deleting an entity.
+/// Fails listing them when the mode refuses, or when owned episodes would be left behind.
+pub fn check_entity_delete(entity: &EntityType, mode: DeleteMode) -> Result<Vec<EntityRelation>> {
+    let relations = entity_relations(entity, None)?;
+    let episodes = owned_episodes(entity)?;
+
+    if !episodes.is_empty() && mode != DeleteMode::Cascade {
+        return Err(CliError::error(ErrorCode::Conflict, format!(
+            "{} '{}' still has {} episode(s). Use --on-relations cascade to delete them too",
+            entity.kind(), entity.name(), episodes.len()
+        )));
+    }
+
+    if !relations.is_empty() && mode == DeleteMode::Refuse {
+        let lines: Vec<String> = relations.iter()
+            .map(|relation| format!("   {} {} {} ({})",
+                relation.arrow(), relation.other_kind, relation.other_label, relation.record.describe()))
+            .collect();
+        return Err(CliError::error(ErrorCode::Conflict, format!(
+            "{} '{}' still has {} relation(s):\n{}\nUse --on-relations detach to remove them, or cascade",
+            entity.kind(), entity.name(), relations.len(), lines.join("\n")
+        )));
+    }
+
+    Ok(relations)
+}
+
+/// Remove what depends on an entity about to be deleted: its relation rows,
+/// and in cascade mode the episodes of a story with their own relations
+pub fn release_entity(entity: &EntityType, mode: DeleteMode) -> Result<()> {
+    let relations = check_entity_delete(entity, mode)?;
+
+    if mode == DeleteMode::Cascade {
+        for episode in owned_episodes(entity)? {
+            let episode_ref = EntityType::Episode(format!("{}:{}", episode.story, episode.number));
+            release_entity(&episode_ref, mode)?;
+            episode.delete(true)?;
+        }
+    }
+
+    for relation in &relations {
+        relation.table.delete(&relation.record.from_id, &relation.record.to_id)?;
+    }
+    if !relations.is_empty() {
+        say!("🔗 Removed {} relation(s) of {} '{}'", relations.len(), entity.kind(), entity.name());
+    }
+
+    Ok(())
+}
+
+/// Relation row pointing to an entity that no longer exists
+#[derive(Debug, Clone)]
+pub struct OrphanRelation {
+    pub table: &'static str,
+    pub from_id: String,
+    pub to_id: String,
+    pub missing: &'static str,
+}
+
+/// Find relation rows whose from or to entity is missing
+pub fn find_orphan_relations(conn: &Connection) -> Result<Vec<OrphanRelation>> {
+    let mut orphans = Vec::new();
+
+    for table in relation_tables() {
+        if !crate::database::table_exists(conn, table.table)? {
+            continue;
+        }
+
+        for (column, entity_table) in [("from_id", table.from_table), ("to_id", table.to_table)] {
+            let sql = if crate::database::table_exists(conn, entity_table)? {
+                format!(
+                    "SELECT from_id, to_id FROM {} WHERE {} NOT IN (SELECT CAST(id AS TEXT) FROM {})",
+                    table.table, column, entity_table
+                )
+            } else {
+                format!("SELECT from_id, to_id FROM {}", table.table)
+            };
+
+            let mut stmt = conn.prepare(&sql)?;
+            let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+            for row in rows {
+                let (from_id, to_id) = row?;
+                if orphans.iter().any(|o: &OrphanRelation| o.table == table.table && o.from_id == from_id && o.to_id == to_id) {
+                    continue;
+                }
+                orphans.push(OrphanRelation { table: table.table, from_id, to_id, missing: entity_table });
+            }
+        }
+    }
+
+    Ok(orphans)
+}
+
+/// Episodes whose story no longer exists
+fn find_orphan_episodes(conn: &Connection) -> Result<Vec<(i64, String, i32)>> {
+    if !crate::database::table_exists(conn, "episodes")? || !crate::database::table_exists(conn, "stories")? {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT id, story, number FROM episodes WHERE story NOT IN (SELECT name FROM stories) ORDER BY story, number"
+    )?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+
+    let mut episodes = Vec::new();
+    for row in rows {
+        episodes.push(row?);
+    }
+    Ok(episodes)
+}
+
+/// Check the world for dangling rows; with `fix`, delete them
+pub fn handle_doctor(fix: bool) -> Result<()> {
+    let conn = crate::database::world_connection()?;
+
+    say!("🩺 Checking relation integrity...");
+
+    let orphans = find_orphan_relations(&conn)?;
+    for orphan in &orphans {
+        say!("   ⚠️  {}: {} → {} points to a missing row in {}",
+            orphan.table, orphan.from_id, orphan.to_id, orphan.missing);
+    }
+
+    let episodes = find_orphan_episodes(&conn)?;
+    for (_, story, number) in &episodes {
+        say!("   ⚠️  episodes: {}:{} belongs to missing story '{}'", story, number, story);
+    }
+
+    if orphans.is_empty() && episodes.is_empty() {
+        say!("✅ No orphaned rows found");
+        return Ok(());
+    }
+
+    if !fix {
+        say!("❌ Found {} orphaned relation(s) and {} orphaned episode(s)", orphans.len(), episodes.len());
+        say!("   Run again with --fix to remove them");
+        return Ok(());
+    }
+
+    let tx = crate::database::savepoint(&conn)?;
+    for orphan in &orphans {
+        tx.execute(
+            &format!("DELETE FROM {} WHERE from_id = ? AND to_id = ?", orphan.table),
+            [&orphan.from_id, &orphan.to_id],
+        )?;
+    }
+    for (id, _, _) in &episodes {
+        // Relations of the episode become orphans too
+        for table in relation_tables() {
+            if crate::database::table_exists(&tx, table.table)? {
+                if table.from_table == "episodes" {
+                    tx.execute(&format!("DELETE FROM {} WHERE from_id = ?", table.table), [id.to_string()])?;
+                }
+                if table.to_table == "episodes" {
+                    tx.execute(&format!("DELETE FROM {} WHERE to_id = ?", table.table), [id.to_string()])?;
+                }
+            }
+        }
+        tx.execute("DELETE FROM episodes WHERE id = ?", [id])?;
+    }
+    tx.commit()?;
+
+    say!("✅ Removed {} orphaned relation(s) and {} orphaned episode(s)", orphans.len(), episodes.len());
+    Ok(())
+}
@@ -9,9 +9,13 @@ macro_rules! define_relation {
         table: $table_name:literal,
         from_table: $from_table:literal,
         to_table: $to_table:literal,
+        keys: $from_kind:literal -> $to_kind:literal,
+        default_role: $default_role:literal,
         fields: {
             $($field_name:ident: $field_type:ty),* $(,)?
         }
+        $(, inverse: $inverse:path)?
+        $(, process: $process:path)? $(,)?
     ) => {
         use rusqlite::Connection;
         use anyhow::Result;
@@ -21,6 +25,10 @@ macro_rules! define_relation {
             pub from_id: String,
             pub to_id: String,
             $(pub $field_name: $field_type),*,
+            /// Free-form JSON metadata (since, rank, sworn...)
+            pub metadata: $crate::relations::metadata::RelationMetadata,
+            /// Timeline bounds (from/until) of the relation
+            pub period: $crate::relations::period::RelationPeriod,
         }
 
         impl $relation_name {
@@ -30,13 +38,14 @@ macro_rules! define_relation {
                     from_id,
                     to_id,
                     $($field_name),*,
+                    metadata: Default::default(),
+                    period: Default::default(),
                 }
             }
 
             /// Create this relation in the database
             pub fn create(&self) -> Result<()> {
-                let db_path = crate::world::WorldConfig::get_database_path()?;
-                let conn = crate::database::get_connection(&db_path)?;
+                let conn = $crate::database::world_connection()?;
 
                 Relations::create_relation(&conn, &self.from_id, &self.to_id, $(&self.$field_name),*)?;
 
@@ -47,8 +56,7 @@ macro_rules! define_relation {
 
             /// Update existing relation in database
             pub fn update(&self) -> Result<()> {
-                let db_path = crate::world::WorldConfig::get_database_path()?;
-                let conn = crate::database::get_connection(&db_path)?;
+                let conn = $crate::database::world_connection()?;
 
                 Relations::update_relation(&conn, &self.from_id, &self.to_id, $(&self.$field_name),*)?;
 
@@ -57,8 +65,7 @@ macro_rules! define_relation {
 
             /// Check if relation exists
             pub fn exists(&self) -> Result<bool> {
-                let db_path = crate::world::WorldConfig::get_database_path()?;
-                let conn = crate::database::get_connection(&db_path)?;
+                let conn = $crate::database::world_connection()?;
 
                 Relations::relation_exists(&conn, &self.from_id, &self.to_id)
             }
@@ -76,31 +83,32 @@ macro_rules! define_relation {
 
             /// Delete this relation from the database
             pub fn delete(&self) -> Result<()> {
-                let db_path = crate::world::WorldConfig::get_database_path()?;
-                let conn = crate::database::get_connection(&db_path)?;
+                let conn = $crate::database::world_connection()?;
 
                 Relations::delete_relation(&conn, &self.from_id, &self.to_id)?;
 
-                println!("🗑️ Deleted relation: {} -> {}", self.from_id, self.to_id);
+                // Success message will be handled by the caller
 
                 Ok(())
             }
 
             /// List all relations for a from_entity
             pub fn list_for_entity(from_id: &str) -> Result<Vec<Self>> {
-                let db_path = crate::world::WorldConfig::get_database_path()?;
-                let conn = crate::database::get_connection(&db_path)?;
-                
-                let field_list = concat!("to_id", $(", ", stringify!($field_name))*);
+                let conn = $crate::database::world_connection()?;
+
+                let field_list = concat!("to_id", $(", ", stringify!($field_name),)* ", metadata, from_date, from_sort_key, until_date, until_sort_key");
                 let sql = format!("SELECT {} FROM {} WHERE from_id = ?", field_list, $table_name);
                 let mut stmt = conn.prepare(&sql)?;
 
                 let rows = stmt.query_map([from_id], |row| {
-                    Ok(Self::new(
+                    let mut relation = Self::new(
                         from_id.to_string(),
                         row.get::<_, String>("to_id")?,
                         $(row.get::<_, String>(stringify!($field_name))?),*
-                    ))
+                    );
+                    relation.metadata = $crate::relations::metadata::from_column(row.get("metadata")?);
+                    relation.period = $crate::relations::period::RelationPeriod::from_row(row)?;
+                    Ok(relation)
                 })?;
 
                 let mut results = Vec::new();
@@ -110,12 +118,146 @@ macro_rules! define_relation {
                 
                 Ok(results)
             }
+
+            /// List all relations pointing to a to_entity
+            pub fn list_for_target(to_id: &str) -> Result<Vec<Self>> {
+                let conn = $crate::database::world_connection()?;
+
+                let field_list = concat!("from_id", $(", ", stringify!($field_name),)* ", metadata, from_date, from_sort_key, until_date, until_sort_key");
+                let sql = format!("SELECT {} FROM {} WHERE to_id = ?", field_list, $table_name);
+                let mut stmt = conn.prepare(&sql)?;
+
+                let rows = stmt.query_map([to_id], |row| {
+                    let mut relation = Self::new(
+                        row.get::<_, String>("from_id")?,
+                        to_id.to_string(),
+                        $(row.get::<_, String>(stringify!($field_name))?),*
+                    );
+                    relation.metadata = $crate::relations::metadata::from_column(row.get("metadata")?);
+                    relation.period = $crate::relations::period::RelationPeriod::from_row(row)?;
+                    Ok(relation)
+                })?;
+
+                let mut results = Vec::new();
+                for row in rows {
+                    results.push(row?);
+                }
+
+                Ok(results)
+            }
+
+            /// List every relation in the table
+            pub fn list_all() -> Result<Vec<Self>> {
+                let conn = $crate::database::world_connection()?;
+
+                let field_list = concat!("from_id, to_id", $(", ", stringify!($field_name),)* ", metadata, from_date, from_sort_key, until_date, until_sort_key");
+                let sql = format!("SELECT {} FROM {} ORDER BY from_id, to_id", field_list, $table_name);
+                let mut stmt = conn.prepare(&sql)?;
+
+                let rows = stmt.query_map([], |row| {
+                    let mut relation = Self::new(
+                        row.get::<_, String>("from_id")?,
+                        row.get::<_, String>("to_id")?,
+                        $(row.get::<_, String>(stringify!($field_name))?),*
+                    );
+                    relation.metadata = $crate::relations::metadata::from_column(row.get("metadata")?);
+                    relation.period = $crate::relations::period::RelationPeriod::from_row(row)?;
+                    Ok(relation)
+                })?;
+
+                let mut results = Vec::new();
+                for row in rows {
+                    results.push(row?);
+                }
+
+                Ok(results)
+            }
+
+            /// Convert into a table-independent record
+            pub fn into_record(self) -> $crate::relations::models::RelationRecord {
+                $crate::relations::models::RelationRecord {
+                    table: $table_name,
+                    from_id: self.from_id,
+                    to_id: self.to_id,
+                    fields: vec![$((stringify!($field_name), self.$field_name.to_string())),*],
+                    metadata: self.metadata,
+                    period: self.period,
+                }
+            }
         }
 
 
         pub struct Relations;
 
         impl Relations {
+            /// Table description used by the generic `relation` commands
+            pub const TABLE: $crate::relations::models::RelationTable = $crate::relations::models::RelationTable {
+                table: $table_name,
+                from_table: $from_table,
+                to_table: $to_table,
+                from_kind: $from_kind,
+                to_kind: $to_kind,
+                default_role: $default_role,
+                fields: &[$(stringify!($field_name)),*],
+                roles: &[],
+                init_table: Relations::init_for_table,
+                upsert: Relations::upsert_by_ids,
+                process: $crate::define_relation!(@process $($process)?),
+                list_all: Relations::records_all,
+                list_for_entity: Relations::records_for_entity,
+                list_for_target: Relations::records_for_target,
+                delete: Relations::delete_by_ids,
+                retype: Relations::retype_by_ids,
+                inverse_role: $crate::define_relation!(@inverse $($inverse)?),
+            };
+
+            fn records_all(_table: &$crate::relations::models::RelationTable) -> Result<Vec<$crate::relations::models::RelationRecord>> {
+                Ok($relation_name::list_all()?
+                    .into_iter()
+                    .map($relation_name::into_record)
+                    .collect())
+            }
+
+            fn records_for_entity(_table: &$crate::relations::models::RelationTable, from_id: &str) -> Result<Vec<$crate::relations::models::RelationRecord>> {
+                Ok($relation_name::list_for_entity(from_id)?
+                    .into_iter()
+                    .map($relation_name::into_record)
+                    .collect())
+            }
+
+            fn records_for_target(_table: &$crate::relations::models::RelationTable, to_id: &str) -> Result<Vec<$crate::relations::models::RelationRecord>> {
+                Ok($relation_name::list_for_target(to_id)?
+                    .into_iter()
+                    .map($relation_name::into_record)
+                    .collect())
+            }
+
+            /// Create or update a relation, setting the first field (the role)
+            fn upsert_by_ids(_table: &$crate::relations::models::RelationTable, from_id: &str, to_id: &str, role: &str) -> Result<bool> {
+                let mut relation = $relation_name::new(from_id.to_string(), to_id.to_string(), $(<$field_type>::default()),*);
+                $crate::define_relation!(@set_first relation, role; $($field_name),*);
+                relation.upsert()
+            }
+
+            fn delete_by_ids(_table: &$crate::relations::models::RelationTable, from_id: &str, to_id: &str) -> Result<()> {
+                $relation_name::new(from_id.to_string(), to_id.to_string(), $(<$field_type>::default()),*).delete()
+            }
+
+            /// Set the first field (the role) of an existing relation
+            fn retype_by_ids(_table: &$crate::relations::models::RelationTable, from_id: &str, to_id: &str, role: &str) -> Result<()> {
+                let mut relation = $relation_name::list_for_entity(from_id)?
+                    .into_iter()
+                    .find(|relation| relation.to_id == to_id)
+                    .ok_or_else(|| anyhow::anyhow!("No relation found to update between {} and {}", from_id, to_id))?;
+
+                $crate::define_relation!(@set_first relation, role; $($field_name),*);
+                relation.update()
+            }
+
+            fn init_for_table(_table: &$crate::relations::models::RelationTable, conn: &Connection) -> Result<()> {
+                Self::init_table(conn)
+            }
+
             pub fn init_table(conn: &Connection) -> Result<()> {
                 let field_defs = concat!($(stringify!($field_name), " TEXT, ",)*);
                 let sql = format!(
@@ -123,6 +265,11 @@ macro_rules! define_relation {
                         from_id TEXT NOT NULL,
                         to_id TEXT NOT NULL,
                         {}
+                        metadata TEXT DEFAULT '{{}}',
+                        from_date TEXT,
+                        from_sort_key INTEGER,
+                        until_date TEXT,
+                        until_sort_key INTEGER,
                         created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
                         PRIMARY KEY (from_id, to_id),
                         FOREIGN KEY (from_id) REFERENCES {} (id),
@@ -215,4 +362,24 @@ macro_rules! define_relation {
             }
         }
     };
-}
\ No newline at end of file
+
+    (@inverse $inverse:path) => {
+        $inverse
+    };
+
+    (@inverse) => {
+        $crate::relations::models::no_inverse_role
+    };
+
+    (@process $process:path) => {
+        Some($process)
+    };
+
+    (@process) => {
+        None
+    };
+
+    (@set_first $relation:ident, $value:ident; $first:ident $(, $rest:ident)*) => {
+        $relation.$first = $value.to_string();
+    };
+}
@@ -0,0 +1,104 @@
+//! Merging duplicate entities: metadata, relation rows and aliases move to the survivor
+//! character merge aragorn strider --force
+
+use anyhow::Result;
+use clap::ValueEnum;
+use rusqlite::Connection;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use crate::output::{CliError, ErrorCode};
+use crate::relations::relation_tables;
+use crate::relations::aliases::{ALIASES_KEY, aliases_of, set_aliases};
+
+/// How to resolve metadata keys set differently on both entities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MergePolicy {
+    /// Keep the survivor's value
+    Keep,
+    /// Take the value of the entity being merged in
+    Take,
+    /// Abort the merge listing the conflicting keys
+    Fail,
+}
+
+/// Metadata keys present on both sides with different values
+pub fn metadata_conflicts(keep: &HashMap<String, JsonValue>, drop: &HashMap<String, JsonValue>) -> Vec<String> {
+    let mut conflicts: Vec<String> = drop.iter()
+        .filter(|(key, value)| key.as_str() != ALIASES_KEY && keep.get(*key).is_some_and(|kept| kept != *value))
+        .map(|(key, _)| key.clone())
+        .collect();
+    conflicts.sort();
+    conflicts
+}
+
+/// Combine the dropped entity's metadata into the survivor's and record its name as an alias
+pub fn merge_metadata(
+    keep: &mut HashMap<String, JsonValue>,
+    drop: &HashMap<String, JsonValue>,
+    drop_name: &str,
+    policy: MergePolicy,
+) -> Result<()> {
+    let conflicts = metadata_conflicts(keep, drop);
+    if policy == MergePolicy::Fail && !conflicts.is_empty() {
+        return Err(CliError::error(ErrorCode::Conflict, format!(
+            "Conflicting metadata: {}. Use --on-conflict keep or take to resolve it",
+            conflicts.join(", ")
+        )));
+    }
+
+    for (key, value) in drop {
+        if key == ALIASES_KEY {
+            continue;
+        }
+        if !keep.contains_key(key) || policy == MergePolicy::Take {
+            keep.insert(key.clone(), value.clone());
+        }
+    }
+
+    let mut aliases = aliases_of(keep);
+    for alias in std::iter::once(drop_name.to_string()).chain(aliases_of(drop)) {
+        if !aliases.contains(&alias) {
+            aliases.push(alias);
+        }
+    }
+    set_aliases(keep, aliases);
+
+    Ok(())
+}
+
+/// Move every relation row of `drop_id` to `keep_id` in tables touching `entity_table`.
+/// Rows the survivor already has with the same other side are kept as they are, so the
+/// (from_id, to_id) key stays unique. Returns (moved, duplicates removed).
+pub fn repoint_relations(conn: &Connection, entity_table: &str, drop_id: i32, keep_id: i32) -> Result<(usize, usize)> {
+    let (drop_id, keep_id) = (drop_id.to_string(), keep_id.to_string());
+    let mut moved = 0;
+    let mut duplicates = 0;
+
+    for table in relation_tables() {
+        if !crate::database::table_exists(conn, table.table)? {
+            continue;
+        }
+
+        for (column, other, entity) in [("from_id", "to_id", table.from_table), ("to_id", "from_id", table.to_table)] {
+            if entity != entity_table {
+                continue;
+            }
+
+            // A self-relation between the two would become a loop on the survivor
+            let self_loop = if table.is_self_relation() { format!(" OR {} = ?2", other) } else { String::new() };
+            duplicates += conn.execute(
+                &format!(
+                    "DELETE FROM {t} WHERE {c} = ?1 AND ({o} IN (SELECT {o} FROM {t} WHERE {c} = ?2){l})",
+                    t = table.table, c = column, o = other, l = self_loop
+                ),
+                [&drop_id, &keep_id],
+            )?;
+            moved += conn.execute(
+                &format!("UPDATE {} SET {} = ?1 WHERE {} = ?2", table.table, column, column),
+                [&keep_id, &drop_id],
+            )?;
+        }
+    }
+
+    Ok((moved, duplicates))
+}
@@ -0,0 +1,98 @@
+//! Free-form JSON metadata stored on every relation row
+//! Syntax: character create aragorn --set faction=rangers*captain{since=3019,rank=2}
+
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension};
+use serde_json::{Map, Value as JsonValue};
+use crate::relations::models::RelationTable;
+
+pub type RelationMetadata = Map<String, JsonValue>;
+
+/// Add the metadata column to relation tables created before it existed
+pub fn ensure_metadata_column(conn: &Connection, table: &str) -> Result<()> {
+    crate::database::ensure_column(conn, table, "metadata", "TEXT DEFAULT '{}'")
+}
+
+/// Read a stored metadata value, tolerating NULL and invalid JSON
+pub fn from_column(value: Option<String>) -> RelationMetadata {
+    value
+        .and_then(|text| serde_json::from_str::<RelationMetadata>(&text).ok())
+        .unwrap_or_default()
+}
+
+/// Convert a --set value into JSON: integers, floats and booleans keep their type
+pub fn typed_value(value: &str) -> JsonValue {
+    if let Ok(num) = value.parse::<i64>() {
+        JsonValue::Number(num.into())
+    } else if let Some(num) = value.parse::<f64>().ok().and_then(serde_json::Number::from_f64) {
+        JsonValue::Number(num)
+    } else if let Ok(flag) = value.parse::<bool>() {
+        JsonValue::Bool(flag)
+    } else {
+        JsonValue::String(value.to_string())
+    }
+}
+
+/// Parse key=value pairs from the inside of `{...}`
+pub fn parse_pairs(pairs: &str) -> Result<Vec<(String, String)>> {
+    pairs.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Invalid relation metadata '{}', expected key=value", pair))?;
+            let key = key.trim();
+            if key.is_empty() {
+                anyhow::bail!("Invalid relation metadata '{}', empty key", pair);
+            }
+            Ok((key.to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Apply updates to existing metadata; an empty value removes the key
+pub fn merge(metadata: &mut RelationMetadata, updates: &[(String, String)]) {
+    for (key, value) in updates {
+        if value.is_empty() {
+            metadata.remove(key);
+        } else {
+            metadata.insert(key.clone(), typed_value(value));
+        }
+    }
+}
+
+/// Display a metadata value without JSON quotes around strings
+pub fn display_value(value: &JsonValue) -> String {
+    match value {
+        JsonValue::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Format metadata the way it is written on the command line, e.g. {rank=2, since=3019}
+pub fn format(metadata: &RelationMetadata) -> String {
+    let pairs: Vec<String> = metadata.iter()
+        .map(|(key, value)| format!("{}={}", key, display_value(value)))
+        .collect();
+    format!("{{{}}}", pairs.join(", "))
+}
+
+/// Merge key=value updates into the metadata of an existing relation row
+pub fn update_metadata(conn: &Connection, table: &RelationTable, from_id: &str, to_id: &str, updates: &[(String, String)]) -> Result<RelationMetadata> {
+    let current: Option<Option<String>> = conn.query_row(
+        &format!("SELECT metadata FROM {} WHERE from_id = ? AND to_id = ?", table.table),
+        [from_id, to_id],
+        |row| row.get(0),
+    ).optional()?;
+    let current = current
+        .ok_or_else(|| anyhow::anyhow!("No relation found to update between {} and {}", from_id, to_id))?;
+
+    let mut metadata = from_column(current);
+    merge(&mut metadata, updates);
+
+    conn.execute(
+        &format!("UPDATE {} SET metadata = ? WHERE from_id = ? AND to_id = ?", table.table),
+        [serde_json::to_string(&metadata)?.as_str(), from_id, to_id],
+    )?;
+    Ok(metadata)
+}
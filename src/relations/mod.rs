@@ -1,7 +1,17 @@
 pub mod models;
 pub mod macros;
 pub mod handlers;
+pub mod cli;
+pub mod registry;
+pub mod config;
+pub mod custom;
+pub mod metadata;
+pub mod period;
+pub mod integrity;
+pub mod merge;
+pub mod aliases;
 pub mod character_episode;
+pub mod character_character;
 pub mod character_location;
 pub mod character_faction;
 pub mod character_race;
@@ -14,17 +24,11 @@ pub mod event_character;
 pub mod event_location;
 pub mod event_faction;
 
-// Relations will be auto-generated by macros
-pub use character_episode::process_character_episode_relations;
-pub use character_location::process_character_location_relations;
-pub use character_faction::process_character_faction_relations;
-pub use character_race::process_character_race_relations;
-pub use character_system::process_character_system_relations;
-pub use race_system::process_race_system_relations;
-pub use location_faction::process_location_faction_relations;
-pub use location_location::process_location_location_relations;
-pub use location_system::process_location_system_relations;
-pub use event_character::process_event_character_relations;
-pub use event_location::process_event_location_relations;
-pub use event_faction::process_event_faction_relations;
-pub use handlers::{process_relations, EntityType, separate_relation_fields};
\ No newline at end of file
+pub use character_character::process_character_character_relations;
+pub use handlers::{process_relations, EntityType, separate_relation_fields, handle_relation_command, show_entity_relations, entity_relations, EntityRelation};
+pub use cli::RelationCommands;
+pub use models::{RelationRecord, RelationTable};
+pub use registry::{RELATION_TABLES, relation_tables, find_relation_for_key, relation_keys_for, relation_set_help, init_relation_tables, init_config_relation_tables, create_target_index};
+pub use integrity::{DeleteMode, check_entity_delete, release_entity, handle_doctor};
+pub use merge::{MergePolicy, merge_metadata, metadata_conflicts, repoint_relations};
+pub use aliases::{ALIASES_KEY, aliases_of, parse_aliases, set_aliases, resolve_entity_id, check_aliases_available};
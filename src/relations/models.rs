@@ -1,5 +1,7 @@
 use anyhow::Result;
 use rusqlite::Connection;
+use crate::relations::metadata::RelationMetadata;
+use crate::relations::period::RelationPeriod;
 
 /// Common trait for all relation types
 pub trait Relation {
@@ -8,5 +10,155 @@ pub trait Relation {
     fn delete(&self, conn: &Connection) -> Result<()>;
 }
 
+/// Table-independent view of a single relation row
+#[derive(Debug, Clone)]
+pub struct RelationRecord {
+    pub table: &'static str,
+    pub from_id: String,
+    pub to_id: String,
+    /// Descriptive columns in declaration order (role, heritage, affinity...)
+    pub fields: Vec<(&'static str, String)>,
+    /// Free-form JSON metadata column
+    pub metadata: RelationMetadata,
+    /// Timeline bounds of the relation
+    pub period: RelationPeriod,
+}
+
+impl RelationRecord {
+    /// Role of the relation (the first descriptive column)
+    pub fn role(&self) -> &str {
+        self.fields.first().map(|(_, value)| value.as_str()).unwrap_or("")
+    }
+
+    /// Format the descriptive columns, metadata and period,
+    /// e.g. "role: captain {rank=2} [from Lum 2951 TA]"
+    pub fn describe(&self) -> String {
+        let mut description = self.fields.iter()
+            .map(|(column, value)| format!("{}: {}", column, value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !self.metadata.is_empty() {
+            description.push_str(&format!(" {}", crate::relations::metadata::format(&self.metadata)));
+        }
+        if !self.period.is_empty() {
+            description.push_str(&format!(" [{}]", self.period.describe()));
+        }
+        description
+    }
+}
+
+/// Description of a relation table, generated by `define_relation!` or read from config.toml
+/// The function pointers dispatch to the typed relation struct of that table,
+/// or to the generic implementation in `custom` for config-defined tables
+#[derive(Debug, Clone, Copy)]
+pub struct RelationTable {
+    pub table: &'static str,
+    pub from_table: &'static str,
+    pub to_table: &'static str,
+    /// Entity type keyword of the from side, also the reverse --set key on the to side
+    pub from_kind: &'static str,
+    /// Entity type keyword of the to side, also the forward --set key on the from side
+    pub to_kind: &'static str,
+    /// Role used when a --set value has no `*role` part
+    pub default_role: &'static str,
+    pub fields: &'static [&'static str],
+    /// Allowed role vocabulary, empty when any role is accepted
+    pub roles: &'static [&'static str],
+    pub init_table: fn(&RelationTable, &Connection) -> Result<()>,
+    pub upsert: fn(&RelationTable, &str, &str, &str) -> Result<bool>,
+    /// Custom forward processor (from name, comma-separated specs) replacing the generic one
+    pub process: Option<fn(&str, &str) -> Result<()>>,
+    pub list_all: fn(&RelationTable) -> Result<Vec<RelationRecord>>,
+    pub list_for_entity: fn(&RelationTable, &str) -> Result<Vec<RelationRecord>>,
+    pub list_for_target: fn(&RelationTable, &str) -> Result<Vec<RelationRecord>>,
+    pub delete: fn(&RelationTable, &str, &str) -> Result<()>,
+    pub retype: fn(&RelationTable, &str, &str, &str) -> Result<()>,
+    /// Role as seen from the to_entity side, for tables linking an entity type to itself
+    pub inverse_role: fn(&str) -> Option<String>,
+}
+
+/// Default for relations without an inverse role
+pub fn no_inverse_role(_role: &str) -> Option<String> {
+    None
+}
+
+impl RelationTable {
+    /// Short relation name, e.g. "character_faction"
+    pub fn name(&self) -> &'static str {
+        self.table.strip_suffix("_relations").unwrap_or(self.table)
+    }
+
+    /// Whether the table links an entity type to itself
+    pub fn is_self_relation(&self) -> bool {
+        self.from_table == self.to_table
+    }
+
+    /// Name of the role column
+    pub fn role_column(&self) -> &'static str {
+        self.fields.first().copied().unwrap_or("role")
+    }
+
+    /// Subquery selecting entity ids on one side of the table (the to side when `reverse`),
+    /// optionally limited to the other side's id (?target) and a role (?role)
+    pub fn related_ids_sql(&self, reverse: bool, target_param: Option<usize>, role_param: Option<usize>) -> String {
+        let (entity_column, other_column) = if reverse { ("to_id", "from_id") } else { ("from_id", "to_id") };
+
+        let mut conditions = Vec::new();
+        if let Some(param) = target_param {
+            conditions.push(format!("{} = ?{}", other_column, param));
+        }
+        if let Some(param) = role_param {
+            conditions.push(format!("{} = ?{}", self.role_column(), param));
+        }
+
+        let mut sql = format!("SELECT {} FROM {}", entity_column, self.table);
+        if !conditions.is_empty() {
+            sql.push_str(&format!(" WHERE {}", conditions.join(" AND ")));
+        }
+        sql
+    }
+
+    /// Reject roles outside the table's vocabulary
+    pub fn check_role(&self, role: &str) -> Result<()> {
+        if !self.roles.is_empty() && !self.roles.contains(&role) {
+            anyhow::bail!(
+                "Invalid {} '{}' for {}. Allowed: {}",
+                self.role_column(), role, self.name(), self.roles.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    pub fn init_table(&self, conn: &Connection) -> Result<()> {
+        (self.init_table)(self, conn)
+    }
+
+    /// Create or update a relation by entity ids, returns true when created
+    pub fn upsert(&self, from_id: &str, to_id: &str, role: &str) -> Result<bool> {
+        (self.upsert)(self, from_id, to_id, role)
+    }
+
+    pub fn list_all(&self) -> Result<Vec<RelationRecord>> {
+        (self.list_all)(self)
+    }
+
+    pub fn list_for_entity(&self, from_id: &str) -> Result<Vec<RelationRecord>> {
+        (self.list_for_entity)(self, from_id)
+    }
+
+    pub fn list_for_target(&self, to_id: &str) -> Result<Vec<RelationRecord>> {
+        (self.list_for_target)(self, to_id)
+    }
+
+    pub fn delete(&self, from_id: &str, to_id: &str) -> Result<()> {
+        (self.delete)(self, from_id, to_id)
+    }
+
+    /// Set the role of an existing relation
+    pub fn retype(&self, from_id: &str, to_id: &str, role: &str) -> Result<()> {
+        (self.retype)(self, from_id, to_id, role)
+    }
+}
+
 
 // CharacterEpisode struct removed - now generated by macro in character_episode_macro.rs
@@ -0,0 +1,107 @@
+//! Validity period of a relation row on the world timeline
+//! Syntax: character update aragorn --set "faction=rangers*captain{from=Lum 2951 TA,until=Lum 3019 TA}"
+//!
+//! Dates are parsed by `TimelineDate::parse`; their sort keys are stored next to them,
+//! like `date`/`sort_key` on events, so `--at` filtering is a plain integer comparison.
+
+use anyhow::{Result, Context};
+use rusqlite::{Connection, Row};
+use crate::timeline::TimelineDate;
+use crate::relations::models::RelationTable;
+
+/// Keys of a relation --set value that set the period instead of metadata
+pub const PERIOD_KEYS: &[&str] = &["from", "until"];
+
+/// Period columns in select order
+pub const PERIOD_COLUMNS: &str = "from_date, from_sort_key, until_date, until_sort_key";
+
+#[derive(Debug, Clone, Default)]
+pub struct RelationPeriod {
+    pub from: Option<String>,
+    pub from_key: Option<i64>,
+    pub until: Option<String>,
+    pub until_key: Option<i64>,
+}
+
+impl RelationPeriod {
+    /// Read the period columns of a row selected with `PERIOD_COLUMNS`
+    pub fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            from: row.get("from_date")?,
+            from_key: row.get("from_sort_key")?,
+            until: row.get("until_date")?,
+            until_key: row.get("until_sort_key")?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.from.is_none() && self.until.is_none()
+    }
+
+    /// Whether the relation holds at a timeline sort key (bounds are inclusive)
+    pub fn is_valid_at(&self, key: i64) -> bool {
+        self.from_key.is_none_or(|from| from <= key) && self.until_key.is_none_or(|until| key <= until)
+    }
+
+    /// Format the period, e.g. "from Lum 2951 TA until Lum 3019 TA"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(from) = &self.from {
+            parts.push(format!("from {}", from));
+        }
+        if let Some(until) = &self.until {
+            parts.push(format!("until {}", until));
+        }
+        parts.join(" ")
+    }
+}
+
+/// Timeline sort key of a date
+pub fn sort_key(date: &str) -> Result<i64> {
+    let timeline_date = TimelineDate::parse(date)
+        .with_context(|| format!("Invalid date '{}'", date))?;
+    Ok(timeline_date.sort_key() as i64)
+}
+
+/// Add the period columns to relation tables created before they existed
+pub fn ensure_period_columns(conn: &Connection, table: &str) -> Result<()> {
+    crate::database::ensure_column(conn, table, "from_date", "TEXT")?;
+    crate::database::ensure_column(conn, table, "from_sort_key", "INTEGER")?;
+    crate::database::ensure_column(conn, table, "until_date", "TEXT")?;
+    crate::database::ensure_column(conn, table, "until_sort_key", "INTEGER")
+}
+
+/// Set or clear (empty value) the from/until bounds of an existing relation row
+pub fn update_period(conn: &Connection, table: &RelationTable, from_id: &str, to_id: &str, updates: &[(String, String)]) -> Result<()> {
+    let mut period = conn.query_row(
+        &format!("SELECT {} FROM {} WHERE from_id = ? AND to_id = ?", PERIOD_COLUMNS, table.table),
+        [from_id, to_id],
+        RelationPeriod::from_row,
+    )?;
+
+    for (key, value) in updates {
+        let bound = if value.is_empty() { None } else { Some(value.clone()) };
+        let bound_key = bound.as_deref().map(sort_key).transpose()?;
+        match key.as_str() {
+            "from" => (period.from, period.from_key) = (bound, bound_key),
+            "until" => (period.until, period.until_key) = (bound, bound_key),
+            _ => {}
+        }
+    }
+
+    if let (Some(from), Some(until)) = (period.from_key, period.until_key) {
+        if until < from {
+            anyhow::bail!("Relation ends before it starts: until '{}' is earlier than from '{}'",
+                period.until.unwrap_or_default(), period.from.unwrap_or_default());
+        }
+    }
+
+    conn.execute(
+        &format!(
+            "UPDATE {} SET from_date = ?, from_sort_key = ?, until_date = ?, until_sort_key = ? WHERE from_id = ? AND to_id = ?",
+            table.table
+        ),
+        rusqlite::params![period.from, period.from_key, period.until, period.until_key, from_id, to_id],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Relation registry - the single list of relation tables.
+//! Adding a relation pair means one `define_relation!` module plus one line in `RELATION_TABLES`,
+//! or a `[relations.<name>]` section in config.toml:
+//! --set keys (forward and reverse), default roles, help text and table creation all derive from it.
+
+use anyhow::Result;
+use rusqlite::Connection;
+use std::sync::OnceLock;
+use crate::relations::models::RelationTable;
+use crate::relations::config::config_relation_tables;
+use crate::relations::{
+    character_episode, character_character, character_location, character_faction, character_race,
+    character_system, race_system, location_faction, location_location, location_system,
+    event_character, event_location, event_faction,
+};
+
+/// Every relation table generated by `define_relation!`
+pub const RELATION_TABLES: &[RelationTable] = &[
+    character_episode::Relations::TABLE,
+    character_character::Relations::TABLE,
+    character_location::Relations::TABLE,
+    character_faction::Relations::TABLE,
+    character_race::Relations::TABLE,
+    character_system::Relations::TABLE,
+    race_system::Relations::TABLE,
+    location_faction::Relations::TABLE,
+    location_location::Relations::TABLE,
+    location_system::Relations::TABLE,
+    event_character::Relations::TABLE,
+    event_location::Relations::TABLE,
+    event_faction::Relations::TABLE,
+];
+
+/// Built-in tables followed by the ones declared in config.toml
+pub fn relation_tables() -> &'static [RelationTable] {
+    static TABLES: OnceLock<Vec<RelationTable>> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut tables = RELATION_TABLES.to_vec();
+        tables.extend_from_slice(config_relation_tables(RELATION_TABLES));
+        tables
+    })
+}
+
+/// Relation table handling a --set key on an entity type.
+/// Returns the table and whether the key is a reverse relation (entity on the to side).
+pub fn find_relation_for_key(kind: &str, key: &str) -> Option<(RelationTable, bool)> {
+    relation_tables().iter()
+        .find(|table| table.from_kind == kind && table.to_kind == key)
+        .map(|table| (*table, false))
+        .or_else(|| relation_tables().iter()
+            .find(|table| table.to_kind == kind && table.from_kind == key && !table.is_self_relation())
+            .map(|table| (*table, true)))
+}
+
+/// All --set keys that create relations for an entity type
+pub fn relation_keys_for(kind: &str) -> Vec<&'static str> {
+    let mut keys = Vec::new();
+    for table in relation_tables() {
+        if table.from_kind == kind && !keys.contains(&table.to_kind) {
+            keys.push(table.to_kind);
+        }
+    }
+    for table in relation_tables() {
+        if table.to_kind == kind && !table.is_self_relation() && !keys.contains(&table.from_kind) {
+            keys.push(table.from_kind);
+        }
+    }
+    keys
+}
+
+/// Help text listing the relation --set keys of an entity type
+pub fn relation_set_help(kind: &str) -> String {
+    let keys = relation_keys_for(kind);
+    if keys.is_empty() {
+        return String::new();
+    }
+
+    let mut help = String::from("Relation keys (--set <key>=<name>*<role>, comma-separated for several):");
+    for key in keys {
+        if let Some((table, reverse)) = find_relation_for_key(kind, key) {
+            let direction = if reverse { "reverse of " } else { "" };
+            help.push_str(&format!(
+                "\n  {:<10} {}{} ({}, default: {})",
+                key, direction, table.name(), table.role_column(), table.default_role
+            ));
+            if !table.roles.is_empty() {
+                help.push_str(&format!(" roles: {}", table.roles.join(", ")));
+            }
+        }
+    }
+    help
+}
+
+/// Index the to_id column: the primary key only serves lookups by from_id
+pub fn create_target_index(conn: &Connection, table: &str) -> Result<()> {
+    conn.execute(&format!("CREATE INDEX IF NOT EXISTS idx_{}_to_id ON {} (to_id)", table, table), [])?;
+    Ok(())
+}
+
+/// Create the tables of config-defined relation types added since the database was last
+/// opened, and give older ones the columns and index added since. They follow config.toml,
+/// so this runs on every open rather than as a migration; built-in tables come from those.
+pub fn init_config_relation_tables(conn: &Connection) -> Result<()> {
+    for table in config_relation_tables(RELATION_TABLES) {
+        table.init_table(conn)?;
+    }
+    Ok(())
+}
+
+/// Create every relation table
+pub fn init_relation_tables(conn: &Connection) -> Result<()> {
+    for table in relation_tables() {
+        table.init_table(conn)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,261 @@
+//! Interactive SQL shell: `multiverse query --repl`
+//!
+//! Statements end with ';' and may span several lines; they run on the same read-only
+//! connection as `multiverse query`. Dot-commands inspect the world database:
+//!
+//! ```text
+//! .tables             entity and relation tables, and the query views
+//! .schema characters  columns of a table
+//! .relations          relation tables with their from/to entities and role column
+//! .format csv         switch the renderer (table by default)
+//! .history            numbered history; !N runs entry N again
+//! ```
+//!
+//! On a terminal the line can be edited (see `line_editor`) and ↑/↓ recall earlier
+//! statements; piped input is read as plain lines. The history is kept across sessions
+//! in `.multiverse/query_history`.
+
+use anyhow::Result;
+use clap::ValueEnum;
+use rusqlite::Connection;
+use serde_json::Value as JsonValue;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use crate::line_editor::LineEditor;
+use crate::output::{self, OutputFormat};
+use crate::relations::handlers::kind_for_table;
+use crate::relations::relation_tables;
+use crate::world::WorldConfig;
+
+const HISTORY_FILE: &str = ".multiverse/query_history";
+
+const HELP: &str = "\
+.tables             List entity and relation tables, and the query views
+.schema <table>     Show the columns of a table
+.relations          List relation tables with their from/to entities and role column
+.format [name]      Show or switch the output format (table, csv, tsv, markdown, json, ndjson, yaml, plain)
+.history            Show previous statements, also from earlier sessions; !N runs statement N again
+.help               Show this help
+.quit               Leave the shell (also .exit or Ctrl-D)
+Statements end with ';' and may span several lines. ←/→, Home/End and Backspace edit
+the line, ↑/↓ recall earlier statements, Ctrl-C drops the line.";
+
+enum Flow {
+    Continue,
+    Quit,
+}
+
+struct Shell {
+    conn: Connection,
+    history: Vec<String>,
+    history_path: PathBuf,
+}
+
+pub fn run_repl() -> Result<()> {
+    let db_path = WorldConfig::get_database_path()?;
+    let conn = crate::database::get_readonly_connection(&db_path)?;
+    let history_path = WorldConfig::get_world_root()?.join(HISTORY_FILE);
+    let mut shell = Shell { conn, history: load_history(&history_path), history_path };
+
+    // Results are read by a person here, so they default to a table
+    if output::format().is_none() {
+        output::set_format(Some(OutputFormat::Table));
+    }
+
+    let interactive = std::io::stdin().is_terminal();
+    if interactive {
+        eprintln!("🔍 Multiverse SQL shell (read-only). End statements with ';', .help for commands");
+    }
+
+    let editor = (interactive && LineEditor::is_available()).then(LineEditor::default);
+    let mut buffer = String::new();
+    let mut lines = std::io::stdin().lock().lines();
+    loop {
+        let line = match &editor {
+            Some(editor) => {
+                let prompt = if buffer.is_empty() { "multiverse> " } else { "       ...> " };
+                editor.read_line(prompt, &shell.history)?
+            }
+            None => lines.next().transpose()?,
+        };
+        let Some(line) = line else { break };
+        let trimmed = line.trim();
+
+        if buffer.is_empty() {
+            if trimmed.is_empty() {
+                continue;
+            }
+            if trimmed.starts_with('.') {
+                match shell.dot_command(trimmed) {
+                    Ok(Flow::Quit) => break,
+                    Ok(Flow::Continue) => {}
+                    Err(error) => report(&error),
+                }
+                continue;
+            }
+            if let Some(number) = trimmed.strip_prefix('!') {
+                match shell.history_entry(number) {
+                    Ok(statement) => {
+                        eprintln!("{}", statement);
+                        shell.run(&statement);
+                    }
+                    Err(error) => report(&error),
+                }
+                continue;
+            }
+        }
+
+        buffer.push_str(&line);
+        buffer.push('\n');
+        if trimmed.ends_with(';') {
+            let statement = std::mem::take(&mut buffer);
+            shell.run(&statement);
+        }
+    }
+
+    // Input ended in the middle of a statement: run what was typed
+    if !buffer.trim().is_empty() {
+        shell.run(&buffer);
+    }
+    if interactive {
+        eprintln!();
+    }
+    Ok(())
+}
+
+fn report(error: &anyhow::Error) {
+    eprintln!("❌ {:#}", error);
+}
+
+impl Shell {
+    /// Record a statement in the history and run it; errors are reported and the shell goes on
+    fn run(&mut self, statement: &str) {
+        let entry = statement.split_whitespace().collect::<Vec<_>>().join(" ");
+        if self.history.last() != Some(&entry) {
+            if let Err(error) = append_history(&self.history_path, &entry) {
+                eprintln!("⚠️  Could not save history: {:#}", error);
+            }
+            self.history.push(entry);
+        }
+
+        if let Err(error) = crate::database::run_query(&self.conn, statement.trim(), &[]) {
+            report(&error);
+        }
+    }
+
+    fn history_entry(&self, number: &str) -> Result<String> {
+        number.trim().parse::<usize>().ok()
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|index| self.history.get(index))
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No history entry '{}'. Use .history to list them", number.trim()))
+    }
+
+    fn dot_command(&mut self, line: &str) -> Result<Flow> {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or_default();
+        let argument = words.next();
+
+        match command {
+            ".tables" => self.tables()?,
+            ".schema" => {
+                let table = argument.ok_or_else(|| anyhow::anyhow!("Usage: .schema <table>"))?;
+                self.schema(table)?;
+            }
+            ".relations" => relations()?,
+            ".format" => match argument {
+                Some(name) => {
+                    let format = OutputFormat::from_str(name, true)
+                        .map_err(|_| anyhow::anyhow!("Unknown format '{}'. Use .help for the list", name))?;
+                    output::set_format(Some(format));
+                }
+                None => {
+                    let format = output::format().and_then(|format| format.to_possible_value());
+                    println!("{}", format.map_or("json".to_string(), |value| value.get_name().to_string()));
+                }
+            },
+            ".history" => {
+                for (index, statement) in self.history.iter().enumerate() {
+                    println!("{:>4}  {}", index + 1, statement);
+                }
+            }
+            ".help" => println!("{}", HELP),
+            ".quit" | ".exit" => return Ok(Flow::Quit),
+            other => anyhow::bail!("Unknown command '{}'. Use .help for the list", other),
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// Every table of the database with its role: entity, relation, view or other
+    fn tables(&self) -> Result<()> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, type FROM sqlite_master WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%'
+             ORDER BY name"
+        )?;
+        let names = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .collect::<rusqlite::Result<Vec<(String, String)>>>()?;
+
+        let mut rows: Vec<Vec<JsonValue>> = names.into_iter()
+            .map(|(name, kind)| {
+                let kind = if kind == "view" {
+                    "view"
+                } else if relation_tables().iter().any(|table| table.table == name) {
+                    "relation"
+                } else if kind_for_table(&name) != name {
+                    "entity"
+                } else {
+                    "other"
+                };
+                vec![JsonValue::from(name), JsonValue::from(kind)]
+            })
+            .collect();
+        rows.sort_by_key(|row| match row[1].as_str() {
+            Some("entity") => 0,
+            Some("relation") => 1,
+            Some("view") => 2,
+            _ => 3,
+        });
+        output::emit_query(".tables", &["table".to_string(), "kind".to_string()], rows)
+    }
+
+    /// Columns of a table or view
+    fn schema(&self, table: &str) -> Result<()> {
+        let exists: bool = self.conn.query_row(
+            "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE name = ?1 AND type IN ('table', 'view'))",
+            [table],
+            |row| row.get(0),
+        )?;
+        if !exists || !table.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            anyhow::bail!("No table '{}'. Use .tables to list them", table);
+        }
+        crate::database::run_query(&self.conn, &format!("PRAGMA table_info({})", table), &[])
+    }
+}
+
+/// Relation tables of the registry with the entity types they link
+fn relations() -> Result<()> {
+    let columns: Vec<String> = ["table", "from", "to", "role_column", "default_role"]
+        .iter().map(|column| column.to_string()).collect();
+    let rows = relation_tables().iter()
+        .map(|table| vec![
+            JsonValue::from(table.table),
+            JsonValue::from(table.from_kind),
+            JsonValue::from(table.to_kind),
+            JsonValue::from(table.role_column()),
+            JsonValue::from(table.default_role),
+        ])
+        .collect();
+    output::emit_query(".relations", &columns, rows)
+}
+
+fn load_history(path: &Path) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .map(|text| text.lines().filter(|line| !line.trim().is_empty()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn append_history(path: &Path, statement: &str) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", statement)?;
+    Ok(())
+}
@@ -0,0 +1,259 @@
+//! Typed field schemas from `[schema.<entity>]` sections of .multiverse/config.toml
+//!
+//! ```toml
+//! [schema.character]
+//! age = { type = "int", required = true }
+//! born = "date"                                   # shorthand for { type = "date" }
+//! house = { type = "enum", values = ["stark", "lannister"] }
+//! titles = "list"                                 # comma-separated --set value
+//! mentor = { type = "entity-ref", entity = "character" }
+//! ```
+//!
+//! Declared fields are validated and coerced on create/update; other keys keep
+//! the free-form behaviour.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use crate::relations::EntityType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FieldType {
+    String,
+    Int,
+    Float,
+    Bool,
+    Date,
+    Enum,
+    List,
+    EntityRef,
+}
+
+impl FieldType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldType::String => "string",
+            FieldType::Int => "int",
+            FieldType::Float => "float",
+            FieldType::Bool => "bool",
+            FieldType::Date => "date",
+            FieldType::Enum => "enum",
+            FieldType::List => "list",
+            FieldType::EntityRef => "entity-ref",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FieldSchema {
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+    #[serde(default)]
+    pub required: bool,
+    /// Allowed values of an enum field
+    #[serde(default)]
+    pub values: Vec<String>,
+    /// Entity type an entity-ref field points to
+    #[serde(default)]
+    pub entity: Option<String>,
+}
+
+/// A field is either a bare type name or a full table
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum FieldSpec {
+    Type(FieldType),
+    Full(FieldSchema),
+}
+
+impl From<FieldSpec> for FieldSchema {
+    fn from(spec: FieldSpec) -> Self {
+        match spec {
+            FieldSpec::Type(field_type) => FieldSchema { field_type, required: false, values: Vec::new(), entity: None },
+            FieldSpec::Full(schema) => schema,
+        }
+    }
+}
+
+/// Declared fields of one entity type, by field name
+pub type EntitySchema = BTreeMap<String, FieldSchema>;
+
+/// Read the `[schema.*]` sections of the current world's config.toml
+fn load_schemas() -> BTreeMap<String, EntitySchema> {
+    let sections = crate::config_sections::load_section::<BTreeMap<String, FieldSpec>>("schema", |entity, _| {
+        EntityType::from_kind(entity, String::new()).map(|_| ())
+    });
+
+    let mut schemas = BTreeMap::new();
+    for (entity, fields) in sections {
+        let mut schema = EntitySchema::new();
+        for (name, spec) in fields {
+            let field = FieldSchema::from(spec);
+            match field.validate() {
+                Ok(()) => {
+                    schema.insert(name, field);
+                }
+                Err(e) => eprintln!("Warning: ignoring [schema.{}] field '{}': {}", entity, name, e),
+            }
+        }
+        schemas.insert(entity, schema);
+    }
+    schemas
+}
+
+/// Schema declared for an entity kind (e.g. "character"), loaded once per process
+pub fn entity_schema(kind: &str) -> Option<&'static EntitySchema> {
+    static SCHEMAS: OnceLock<BTreeMap<String, EntitySchema>> = OnceLock::new();
+    SCHEMAS.get_or_init(load_schemas).get(kind)
+}
+
+impl FieldSchema {
+    fn validate(&self) -> Result<()> {
+        match self.field_type {
+            FieldType::Enum if self.values.is_empty() => anyhow::bail!("enum fields need a 'values' list"),
+            FieldType::EntityRef => {
+                let entity = self.entity.as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("entity-ref fields need an 'entity' type"))?;
+                EntityType::from_kind(entity, String::new())?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Human-readable type, e.g. "enum [stark, lannister]" or "entity-ref → character"
+    pub fn describe(&self) -> String {
+        let mut description = match self.field_type {
+            FieldType::Enum => format!("enum [{}]", self.values.join(", ")),
+            FieldType::EntityRef => format!("entity-ref → {}", self.entity.as_deref().unwrap_or("?")),
+            other => other.name().to_string(),
+        };
+        if self.required {
+            description.push_str(" (required)");
+        }
+        description
+    }
+
+    /// Validate a --set value and convert it to the declared type
+    pub fn coerce(&self, key: &str, value: &str) -> Result<JsonValue> {
+        let invalid = |expected: &str| anyhow::anyhow!("Field '{}' expects {}, got '{}'", key, expected, value);
+
+        Ok(match self.field_type {
+            FieldType::String => JsonValue::String(value.to_string()),
+            FieldType::Int => JsonValue::from(value.trim().parse::<i64>().map_err(|_| invalid("an integer"))?),
+            FieldType::Float => {
+                let number = value.trim().parse::<f64>().ok()
+                    .and_then(serde_json::Number::from_f64)
+                    .ok_or_else(|| invalid("a number"))?;
+                JsonValue::Number(number)
+            }
+            FieldType::Bool => JsonValue::Bool(match value.trim().to_lowercase().as_str() {
+                "true" | "yes" | "1" => true,
+                "false" | "no" | "0" => false,
+                _ => return Err(invalid("true or false")),
+            }),
+            FieldType::Date => {
+                validate_date(value).map_err(|e| anyhow::anyhow!("Field '{}' expects a date: {}", key, e))?;
+                JsonValue::String(value.trim().to_string())
+            }
+            FieldType::Enum => {
+                if !self.values.iter().any(|allowed| allowed == value) {
+                    return Err(invalid(&format!("one of {}", self.values.join(", "))));
+                }
+                JsonValue::String(value.to_string())
+            }
+            FieldType::List => JsonValue::from(
+                value.split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            ),
+            FieldType::EntityRef => {
+                let kind = self.entity.as_deref().unwrap_or_default();
+                EntityType::from_kind(kind, value.to_string())?.resolve_id()
+                    .with_context(|| format!("Field '{}' must name an existing {}", key, kind))?;
+                JsonValue::String(value.to_string())
+            }
+        })
+    }
+}
+
+/// Dates follow the world's timeline when it has one, ISO dates otherwise
+fn validate_date(value: &str) -> Result<()> {
+    if crate::timeline::timeline_config_exists() {
+        crate::relations::period::sort_key(value)?;
+        return Ok(());
+    }
+
+    let value = value.trim();
+    if chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+        || chrono::DateTime::parse_from_rfc3339(value).is_ok()
+    {
+        return Ok(());
+    }
+    anyhow::bail!("'{}' is not a YYYY-MM-DD date", value)
+}
+
+/// Coerce a --set value if the entity's schema declares the key
+pub fn coerce_field(kind: &str, key: &str, value: &str) -> Result<Option<JsonValue>> {
+    match entity_schema(kind).and_then(|schema| schema.get(key)) {
+        Some(field) => field.coerce(key, value).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Fail when required schema fields are missing from a new entity
+pub fn check_required(kind: &str, provided: &[&str]) -> Result<()> {
+    let Some(schema) = entity_schema(kind) else {
+        return Ok(());
+    };
+
+    let missing: Vec<&str> = schema.iter()
+        .filter(|(name, field)| field.required && !provided.contains(&name.as_str()))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "Missing required field(s) for {}: {}. Use --set {}=<value>",
+            kind, missing.join(", "), missing[0]
+        );
+    }
+    Ok(())
+}
+
+/// Print built-in columns, status values and declared fields of an entity type
+pub fn show_entity_schema(kind: &str, builtin: &[(&str, &str)], statuses: &[String]) -> Result<()> {
+    println!("📐 Schema for {}:", kind);
+
+    println!("   Built-in fields:");
+    for (name, field_type) in builtin {
+        println!("     {}: {}", name, field_type);
+    }
+    println!("     status: {}", statuses.join(" | "));
+    for transition in crate::status::describe_transitions(kind) {
+        println!("       {}", transition);
+    }
+
+    match entity_schema(kind) {
+        Some(schema) if !schema.is_empty() => {
+            println!("   Declared in [schema.{}]:", kind);
+            for (name, field) in schema {
+                println!("     {}: {}", name, field.describe());
+            }
+        }
+        _ => {
+            println!("   No [schema.{}] section: other --set keys are stored as text", kind);
+        }
+    }
+
+    let relation_keys = crate::relations::relation_keys_for(kind);
+    if !relation_keys.is_empty() {
+        println!("   Relation keys: {}", relation_keys.join(", "));
+    }
+
+    Ok(())
+}
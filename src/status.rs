@@ -0,0 +1,131 @@
+//! Status workflows from `[status.<entity>]` sections of .multiverse/config.toml
+//!
+//! ```toml
+//! [status.story]
+//! values = ["Draft", "Outlined", "InProgress", "Beta", "Published", "Archived"]
+//! initial = "Outlined"                      # optional, status of new entities
+//!
+//! [status.story.transitions]                # states without an entry may move anywhere
+//! Outlined = ["Draft", "InProgress"]
+//! Beta = ["InProgress", "Published"]
+//!
+//! [status.story.emoji]                      # list icons, overriding the built-in ones
+//! Outlined = "🗒️"
+//! Beta = "🧪"
+//! ```
+//!
+//! Without a section an entity keeps its compiled status variants and free transitions.
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use crate::relations::EntityType;
+
+/// Icon for states that have no emoji configured
+pub const CUSTOM_STATUS_EMOJI: &str = "⚪";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StatusWorkflow {
+    /// Status vocabulary; replaces the built-in variants when not empty
+    #[serde(default)]
+    pub values: Vec<String>,
+    #[serde(default)]
+    pub initial: Option<String>,
+    /// Allowed next states, by current state
+    #[serde(default)]
+    pub transitions: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub emoji: BTreeMap<String, String>,
+}
+
+impl StatusWorkflow {
+    /// Every state named by initial/transitions/emoji must be part of the vocabulary
+    fn validate(&self) -> Result<()> {
+        if self.values.is_empty() {
+            return Ok(());
+        }
+
+        let named = self.initial.iter()
+            .chain(self.transitions.keys())
+            .chain(self.transitions.values().flatten())
+            .chain(self.emoji.keys());
+        for state in named {
+            if !self.values.contains(state) {
+                anyhow::bail!("state '{}' is not listed in values", state);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the `[status.*]` sections of the current world's config.toml
+fn load_workflows() -> BTreeMap<String, StatusWorkflow> {
+    crate::config_sections::load_section("status", |entity, workflow: &StatusWorkflow| {
+        EntityType::from_kind(entity, String::new())?;
+        workflow.validate()
+    })
+}
+
+/// Workflow declared for an entity kind (e.g. "story"), loaded once per process
+pub fn status_workflow(kind: &str) -> Option<&'static StatusWorkflow> {
+    static WORKFLOWS: OnceLock<BTreeMap<String, StatusWorkflow>> = OnceLock::new();
+    WORKFLOWS.get_or_init(load_workflows).get(kind)
+}
+
+/// Effective status vocabulary: the configured values, or the compiled variants
+pub fn allowed_statuses(kind: &str, builtin: &[&str]) -> Vec<String> {
+    match status_workflow(kind) {
+        Some(workflow) if !workflow.values.is_empty() => workflow.values.clone(),
+        _ => builtin.iter().map(|status| status.to_string()).collect(),
+    }
+}
+
+/// Status given to new entities when the workflow sets one
+pub fn initial_status(kind: &str) -> Option<&'static str> {
+    status_workflow(kind).and_then(|workflow| workflow.initial.as_deref())
+}
+
+/// Validate `--set status=`: the value must be in the vocabulary and, for an existing
+/// entity (`current` set), reachable from its current state
+pub fn check_status(kind: &str, builtin: &[&str], current: Option<&str>, new: &str) -> Result<()> {
+    let allowed = allowed_statuses(kind, builtin);
+    if !allowed.iter().any(|status| status == new) {
+        anyhow::bail!("Invalid status '{}' for {}. Allowed: {}", new, kind, allowed.join(", "));
+    }
+
+    let (Some(current), Some(workflow)) = (current, status_workflow(kind)) else {
+        return Ok(());
+    };
+    if current == new {
+        return Ok(());
+    }
+    if let Some(next) = workflow.transitions.get(current) {
+        if !next.iter().any(|status| status == new) {
+            anyhow::bail!(
+                "Cannot move {} from '{}' to '{}'. Allowed from '{}': {}",
+                kind, current, new, current,
+                if next.is_empty() { "none".to_string() } else { next.join(", ") }
+            );
+        }
+    }
+    Ok(())
+}
+
+/// List icon for a status: the configured emoji, else the entity's built-in one
+pub fn status_emoji(kind: &str, status: &str, builtin: &str) -> String {
+    status_workflow(kind)
+        .and_then(|workflow| workflow.emoji.get(status))
+        .map(String::as_str)
+        .unwrap_or(builtin)
+        .to_string()
+}
+
+/// Transition lines for `<entity> schema`, e.g. "Beta → InProgress, Published"
+pub fn describe_transitions(kind: &str) -> Vec<String> {
+    status_workflow(kind)
+        .map(|workflow| workflow.transitions.iter()
+            .map(|(from, next)| format!("{} → {}", from, if next.is_empty() { "(final)".to_string() } else { next.join(", ") }))
+            .collect())
+        .unwrap_or_default()
+}
@@ -9,18 +9,27 @@ pub enum StoryCommands {
         /// Set metadata field (can be used multiple times: --set title="Story Title" --set type=fantasy --set author=John)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
     },
     
     /// List available story types with their required fields
     Types,
     
     /// List stories in current world
-    List,
+    List {
+        #[command(flatten)]
+        options: crate::listing::ListArgs,
+    },
     
     /// Show story details
     Info {
         /// Story name
         name: String,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
     },
     
     /// Delete a story
@@ -30,6 +39,9 @@ pub enum StoryCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+        /// What to do with existing relations: refuse, detach or cascade
+        #[arg(long, value_enum, default_value_t = crate::relations::DeleteMode::Refuse)]
+        on_relations: crate::relations::DeleteMode,
     },
 
     /// Update an existing story
@@ -39,7 +51,35 @@ pub enum StoryCommands {
         /// Set metadata field (can be used multiple times: --set title="New Title" --set type=fantasy --set author=John)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
+        /// Remove a metadata field (--unset age --unset stats.strength)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+    /// Rename a story, moving its directory and episodes along
+    Rename {
+        /// Current story name
+        name: String,
+        /// New story name
+        new_name: String,
+    },
+    /// Merge a duplicate story into another one, moving its relations
+    Merge {
+        /// Story to keep
+        keep: String,
+        /// Duplicate story to merge in and delete (its name becomes an alias)
+        drop: String,
+        /// Metadata set differently on both: keep the survivor's value, take the duplicate's, or fail
+        #[arg(long, value_enum, default_value_t = crate::relations::MergePolicy::Keep)]
+        on_conflict: crate::relations::MergePolicy,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
     },
+    /// Show the field schema of stories (built-in fields and [schema.story] in config.toml)
+    Schema,
 }
 
 /// Parse a single key-value pair for --set flag
@@ -1,37 +1,49 @@
 use super::cli::StoryCommands;
 use super::models::Story;
-use crate::relations::{process_relations, EntityType};
+use crate::relations::{process_relations, EntityType, DeleteMode, check_entity_delete, release_entity, entity_relations, MergePolicy, metadata_conflicts};
+use crate::episode::Episode;
 use anyhow::Result;
+use crate::say;
 
 pub fn handle_story_command(command: StoryCommands) -> Result<()> {
     match command {
-        StoryCommands::Create { name, set } => {
-            handle_create(name, set)
+        StoryCommands::Create { name, set, set_json } => {
+            handle_create(name, set, set_json)
         }
         StoryCommands::Types => handle_types(),
-        StoryCommands::List => handle_list(),
-        StoryCommands::Info { name } => handle_info(name),
-        StoryCommands::Delete { name, force } => handle_delete(name, force),
-        StoryCommands::Update { name, set } => handle_update(name, set),
+        StoryCommands::List { options } => handle_list(options),
+        StoryCommands::Schema => Story::show_schema(),
+        StoryCommands::Info { name, at } => handle_info(name, at),
+        StoryCommands::Delete { name, force, on_relations } => handle_delete(name, force, on_relations),
+        StoryCommands::Rename { name, new_name } => handle_rename(name, new_name),
+        StoryCommands::Merge { keep, drop, on_conflict, force } => handle_merge(keep, drop, on_conflict, force),
+        StoryCommands::Update { name, set, set_json, unset } => handle_update(name, set, set_json, unset),
     }
 }
 
-fn handle_update(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("🔄 Updating story '{name}'");
+fn handle_update(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>, unset: Vec<String>) -> Result<()> {
+    say!("🔄 Updating story '{name}'");
 
     let mut story = Story::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Story '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Story", &name))?;
 
     let regular_fields = process_relations(EntityType::Story(name.clone()), set_args)?;
+    story.unset_fields(&unset)?;
+    story.process_json_args(set_json)?;
     story.update(regular_fields)?;
 
-    println!("✅ Story '{}' updated!", name);
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Story(story.name.clone()), None)?;
+        return crate::output::emit_entity("story", Some("updated"), &story, Some(relations));
+    }
+
+    say!("✅ Story '{}' updated!", name);
     show_created_story(&story)?;
 
     Ok(())
 }
 
-fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()> {
+fn handle_create(name: String, mut set_args: Vec<(String, String)>, set_json: Vec<(String, String)>) -> Result<()> {
     // Validate required fields
     if !set_args.iter().any(|(k, _)| k == "type" || k == "story_type") {
         return Err(anyhow::anyhow!("Missing required field 'type'. Use --set type=<story_type>"));
@@ -42,7 +54,7 @@ fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()
         .map(|(_, v)| v.as_str())
         .unwrap_or(&name);
     
-    println!("📖 Creating story '{name}' ({})", title);
+    say!("📖 Creating story '{name}' ({})", title);
 
     // Normalize field names: type -> story_type, title -> display_name
     for (key, _) in &mut set_args {
@@ -53,16 +65,72 @@ fn handle_create(name: String, mut set_args: Vec<(String, String)>) -> Result<()
         }
     }
 
+    apply_story_type(&mut set_args)?;
+
     // Use Story factory method with built-in validation
-    let mut story = Story::create_new(name.clone(), set_args)?;
+    let mut story = Story::create_new(name.clone(), set_args, set_json)?;
     story.create_with_directory()?;
     
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Story(story.name.clone()), None)?;
+        return crate::output::emit_entity("story", Some("created"), &story, Some(relations));
+    }
+    
     // Display success information
     show_created_story(&story)?;
     
     Ok(())
 }
 
+/// Check the story type's required_fields and fill in its defaults (set_args are already normalized)
+fn apply_story_type(set_args: &mut Vec<(String, String)>) -> Result<()> {
+    use crate::world::WorldConfig;
+    use anyhow::Context;
+
+    let story_type = set_args.iter()
+        .find(|(k, _)| k == "story_type")
+        .map(|(_, v)| v.clone())
+        .unwrap_or_default();
+
+    let config = WorldConfig::load()
+        .context("Failed to load world configuration")?;
+    let type_config = config.get_story_type(&story_type)
+        .with_context(|| format!("Unknown story type '{}'. Run 'multiverse story types' to list them", story_type))?;
+
+    // Config may use the user-facing names (title, type) for the normalized fields
+    let canonical = |field: &str| match field {
+        "title" => "display_name".to_string(),
+        "type" => "story_type".to_string(),
+        other => other.to_string(),
+    };
+    let has_field = |set_args: &[(String, String)], field: &str| {
+        let field = canonical(field);
+        set_args.iter().any(|(k, v)| *k == field && !v.is_empty())
+    };
+
+    let mut defaults: Vec<(&String, &String)> = type_config.defaults.iter().collect();
+    defaults.sort();
+    for (key, value) in defaults {
+        if !has_field(set_args, key) {
+            say!("   Default {}: {}", key, value);
+            set_args.push((canonical(key), value.clone()));
+        }
+    }
+
+    let missing: Vec<&str> = type_config.required_fields.iter()
+        .filter(|field| !has_field(set_args, field))
+        .map(String::as_str)
+        .collect();
+    if !missing.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Story type '{}' requires: {}. Use --set {}=<value>",
+            story_type, missing.join(", "), missing[0]
+        ));
+    }
+
+    Ok(())
+}
+
 fn show_created_story(story: &Story) -> Result<()> {
     use crate::world::WorldConfig;
     use anyhow::Context;
@@ -75,16 +143,16 @@ fn show_created_story(story: &Story) -> Result<()> {
     let config = WorldConfig::load()?;
     let type_config = config.get_story_type(&story.story_type)?;
     
-    println!("✅ Story '{}' created!", story.name);
-    println!("   Location: {}", story_path.display());
-    println!("   Title: {}", story.display_name);
-    println!("   Type: {} ({})", story.story_type, type_config.display_name);
+    say!("✅ Story '{}' created!", story.name);
+    say!("   Location: {}", story_path.display());
+    say!("   Title: {}", story.display_name);
+    say!("   Type: {} ({})", story.story_type, type_config.display_name);
     
     // Show metadata
     if !story.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &story.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
@@ -100,63 +168,72 @@ fn handle_types() -> Result<()> {
 
     let story_types = config.list_story_types();
     if story_types.is_empty() {
-        println!("📚 No story types configured");
-        println!("   Add story types to .multiverse/config.toml");
+        say!("📚 No story types configured");
+        say!("   Add story types to .multiverse/config.toml");
         return Ok(());
     }
 
-    println!("📚 Available story types:");
+    say!("📚 Available story types:");
 
     for (type_name, type_config) in story_types {
-        println!("\n   {} ({})", type_name, type_config.display_name);
+        say!("\n   {} ({})", type_name, type_config.display_name);
         
         if !type_config.required_fields.is_empty() {
-            println!("      Required: {}", type_config.required_fields.join(", "));
+            say!("      Required: {}", type_config.required_fields.join(", "));
         }
         
         if !type_config.optional_fields.is_empty() {
-            println!("      Optional: {}", type_config.optional_fields.join(", "));
+            say!("      Optional: {}", type_config.optional_fields.join(", "));
         }
         
         if !type_config.defaults.is_empty() {
-            println!("      Defaults:");
+            say!("      Defaults:");
             for (key, value) in &type_config.defaults {
-                println!("        {}: {}", key, value);
+                say!("        {}: {}", key, value);
             }
         }
         
-        println!("      Numbering: {}", type_config.numbering_format);
+        say!("      Numbering: {}", type_config.numbering_format);
     }
     
-    println!("\n📖 Example usage:");
+    say!("\n📖 Example usage:");
     let first_type = config.world.global_config.story_types.keys().next().unwrap();
-    println!("   multiverse story create my_story --set title=\"My Story\" --set type={} --set <field>=<value>", first_type);
+    say!("   multiverse story create my_story --set title=\"My Story\" --set type={} --set <field>=<value>", first_type);
 
     Ok(())
 }
 
-fn handle_list() -> Result<()> {
-    let stories = Story::list()?;
+fn handle_list(options: crate::listing::ListArgs) -> Result<()> {
+    let stories = Story::list_with(&options)?;
+    
+    if crate::output::is_structured() {
+        return crate::output::emit_list(&stories, &options.fields);
+    }
+    
+    if !options.fields.is_empty() {
+        return crate::listing::print_fields(&stories, &options.fields);
+    }
     
     if stories.is_empty() {
-        println!("📖 No stories found in this world");
-        println!("   Use 'multiverse story types' to see available story types");
-        println!("   Use 'multiverse story create <name> --set title=\"<title>\" --set type=<type>' to create one");
+        say!("📖 No stories found in this world");
+        say!("   Use 'multiverse story types' to see available story types");
+        say!("   Use 'multiverse story create <name> --set title=\"<title>\" --set type=<type>' to create one");
         return Ok(());
     }
     
-    println!("📖 Stories in current world:");
+    say!("📖 Stories in current world:");
     
     for story in stories {
-        let status_emoji = match story.status {
+        let status_emoji = crate::status::status_emoji("story", story.status.as_str(), match story.status {
             crate::story::models::StoryStatus::Draft => "📝",
             crate::story::models::StoryStatus::InProgress => "🟢",
             crate::story::models::StoryStatus::Review => "🟡", 
             crate::story::models::StoryStatus::Published => "✅",
             crate::story::models::StoryStatus::Archived => "📦",
-        };
+            crate::story::models::StoryStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
         
-        println!("   {} {} - \"{}\" ({})", 
+        say!("   {} {} - \"{}\" ({})", 
             status_emoji, 
             story.name, 
             story.display_name,
@@ -165,64 +242,159 @@ fn handle_list() -> Result<()> {
         
         // Show key metadata fields
         if let Some(narrator) = story.metadata.get("narrator") {
-            println!("      by {}", narrator.as_str().unwrap_or("Unknown"));
+            say!("      by {}", narrator.as_str().unwrap_or("Unknown"));
         }
         if let Some(author) = story.metadata.get("author") {
-            println!("      by {}", author.as_str().unwrap_or("Unknown"));
+            say!("      by {}", author.as_str().unwrap_or("Unknown"));
         }
         
         if let Some(desc) = story.metadata.get("description") {
-            println!("      {}", desc.as_str().unwrap_or(""));
+            say!("      {}", desc.as_str().unwrap_or(""));
         }
     }
     
     Ok(())
 }
 
-fn handle_info(name: String) -> Result<()> {
+fn handle_info(name: String, at: Option<String>) -> Result<()> {
     let story = Story::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Story '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Story", &name))?;
+    
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Story(story.name.clone()), at.as_deref())?;
+        return crate::output::emit_entity("story", None, &story, Some(relations));
+    }
     
-    println!("📖 Story: {} - \"{}\"", story.name, story.display_name);
-    println!("   Type: {}", story.story_type);
-    println!("   Status: {:?}", story.status);
-    println!("   Word Count: {}", story.word_count);
-    println!("   Created: {}", story.created_at.format("%Y-%m-%d %H:%M"));
+    say!("📖 Story: {} - \"{}\"", story.name, story.display_name);
+    say!("   Type: {}", story.story_type);
+    say!("   Status: {:?}", story.status);
+    say!("   Word Count: {}", story.word_count);
+    say!("   Created: {}", story.created_at.format("%Y-%m-%d %H:%M"));
     
     if let Some(desc) = story.metadata.get("description") {
-        println!("   Description: {}", desc.as_str().unwrap_or(""));
+        say!("   Description: {}", desc.as_str().unwrap_or(""));
     }
     
     // Show metadata
     if !story.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &story.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
     
-    // TODO: Show episode count and stats
-    println!("   Episodes: (to be implemented)");
+    // Stories have no relation tables: their graph is the union of their episodes' relations
+    let episodes = Episode::list_for_story(&story.name)?;
+    if episodes.is_empty() {
+        say!("   Episodes: none");
+    } else {
+        say!("   Episodes ({}):", episodes.len());
+        let numbering = story.numbering_format()?;
+        for episode in &episodes {
+            say!("     {}", episode.display_title(&numbering)?);
+            let episode_ref = EntityType::Episode(format!("{}:{}", episode.story, episode.number));
+            for relation in crate::relations::entity_relations(&episode_ref, at.as_deref())? {
+                say!("       {} {} {} ({})",
+                    relation.arrow(), relation.other_kind, relation.other_label, relation.record.describe());
+            }
+        }
+    }
     
     Ok(())
 }
 
-fn handle_delete(name: String, force: bool) -> Result<()> {
+fn handle_delete(name: String, force: bool, on_relations: DeleteMode) -> Result<()> {
     let story = Story::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("Story '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("Story", &name))?;
     
+    let entity = EntityType::Story(name.clone());
+    let relations = check_entity_delete(&entity, on_relations)?;
+
     if !force {
-        println!("⚠️  Are you sure you want to delete story '{name}'?");
-        println!("   This will permanently delete the story directory and all episodes");
-        println!("   Use --force to skip this confirmation");
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("delete story '{}'", name)));
+        }
+        say!("⚠️  Are you sure you want to delete story '{name}'?");
+        say!("   This will permanently delete the story directory and all episodes");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will be removed as well", relations.len());
+        }
+        say!("   Use --force to skip this confirmation");
         return Ok(());
     }
     
-    println!("🗑️  Deleting story '{name}'...");
+    say!("🗑️  Deleting story '{name}'...");
     
+    release_entity(&entity, on_relations)?;
     story.delete_with_directory(force)?;
     
-    println!("✅ Story '{name}' deleted!");
+    say!("✅ Story '{name}' deleted!");
+
+    if crate::output::is_structured() {
+        return crate::output::emit_entity("story", Some("deleted"), &story, Some(relations));
+    }
     
     Ok(())
 }
+
+fn handle_rename(name: String, new_name: String) -> Result<()> {
+    let mut story = Story::get(&name)?
+        .ok_or_else(|| crate::output::not_found("Story", &name))?;
+
+    say!("✏️  Renaming story '{name}' to '{new_name}'...");
+
+    story.rename_with_directory(new_name.clone())?;
+
+    say!("✅ Story '{name}' is now '{new_name}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Story(story.name.clone()), None)?;
+        return crate::output::emit_entity("story", Some("renamed"), &story, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_merge(keep: String, drop: String, on_conflict: MergePolicy, force: bool) -> Result<()> {
+    let mut survivor = Story::get(&keep)?
+        .ok_or_else(|| crate::output::not_found("Story", &keep))?;
+    let duplicate = Story::get(&drop)?
+        .ok_or_else(|| crate::output::not_found("Story", &drop))?;
+
+    let episodes = Episode::count_for_story(&drop)?;
+    if episodes > 0 {
+        anyhow::bail!("Story '{}' still has {} episode(s); delete them or merge them by hand first", drop, episodes);
+    }
+
+    if !force {
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("merge story '{}' into '{}'", drop, keep)));
+        }
+        let relations = entity_relations(&EntityType::Story(drop.clone()), None)?;
+        let conflicts = metadata_conflicts(&survivor.metadata, &duplicate.metadata);
+        say!("⚠️  Are you sure you want to merge story '{drop}' into '{keep}'?");
+        say!("   '{drop}' will be deleted and kept as an alias of '{keep}'");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will move to '{keep}'", relations.len());
+        }
+        if !conflicts.is_empty() {
+            say!("   Conflicting metadata ({:?}): {}", on_conflict, conflicts.join(", "));
+        }
+        say!("   Use --force to skip this confirmation");
+        return Ok(());
+    }
+
+    say!("🔀 Merging story '{drop}' into '{keep}'...");
+
+    survivor.merge(duplicate.clone(), on_conflict)?;
+    duplicate.remove_directory()?;
+
+    say!("✅ Story '{drop}' merged into '{keep}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::Story(survivor.name.clone()), None)?;
+        return crate::output::emit_entity("story", Some("merged"), &survivor, Some(relations));
+    }
+
+    Ok(())
+}
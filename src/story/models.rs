@@ -2,6 +2,7 @@
 
 use crate::define_complete_entity;
 use serde::{Deserialize, Serialize};
+use crate::say;
 
 // Generate complete Story entity
 define_complete_entity!(
@@ -30,8 +31,48 @@ define_complete_entity!(
     )"
 );
 
+/// Episode numbering used when the story type does not configure one
+pub const DEFAULT_NUMBERING: &str = "{:03}";
+
+/// Format an episode number with a story type's `numbering_format`.
+/// Supports `{}` and zero-padded `{:0N}` with optional text around the placeholder; the
+/// result names episode files, so space padding (`{:3}`) and path separators are refused.
+pub fn format_episode_number(format: &str, number: i32) -> anyhow::Result<String> {
+    if format.contains('/') || format.contains('\\') {
+        anyhow::bail!("Invalid numbering_format '{}': it names episode files and cannot contain '/'", format);
+    }
+    let (start, end) = match (format.find('{'), format.find('}')) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => return Ok(format!("{}{}", format, number)),
+    };
+
+    let spec = &format[start + 1..end];
+    let digits = match spec.strip_prefix(':') {
+        None if spec.is_empty() => number.to_string(),
+        Some(spec) => match spec.strip_prefix('0').map(str::parse::<usize>) {
+            Some(Ok(width)) if width > 0 => format!("{:0width$}", number, width = width),
+            _ => anyhow::bail!(
+                "Invalid numbering_format '{}': use {{}} or a zero-padded width like {{:03}}", format
+            ),
+        },
+        None => anyhow::bail!("Invalid numbering_format '{}': use {{}} or a zero-padded width like {{:03}}", format),
+    };
+
+    Ok(format!("{}{}{}", &format[..start], digits, &format[end + 1..]))
+}
+
 // Custom implementations for Story
 impl Story {
+    /// Episode numbering format of this story's type, `{:03}` for types without a config
+    pub fn numbering_format(&self) -> anyhow::Result<String> {
+        let config = crate::world::WorldConfig::load()?;
+        let format = config.get_story_type(&self.story_type).ok()
+            .map(|type_config| type_config.numbering_format.clone())
+            .unwrap_or_else(|| DEFAULT_NUMBERING.to_string());
+        format_episode_number(&format, 1)?;
+        Ok(format)
+    }
+
     /// Display name for UI
     pub fn display_name(&self) -> &str {
         if !self.display_name.is_empty() {
@@ -41,13 +82,10 @@ impl Story {
         }
     }
 
-    /// Resolve story name to database ID
+    /// Resolve story name or alias to database ID
     pub fn resolve_id(name: &str) -> anyhow::Result<String> {
         let conn = Self::get_database_connection()?;
-        let mut stmt = conn.prepare("SELECT id FROM stories WHERE name = ?")?;
-        let id: i32 = stmt.query_row([name], |row| {
-            row.get(0)
-        }).map_err(|_| anyhow::anyhow!("Story not found: '{}'", name))?;
+        let id = crate::relations::resolve_entity_id(&conn, StoryDb::TABLE, "Story", name)?;
         Ok(id.to_string())
     }
 
@@ -63,22 +101,20 @@ impl Story {
         // Create the story in database first
         self.create()?;
         
-        // Create story directory structure
+        // Create story directory structure, with an initial README.md, once the command commits
         let story_path = self.get_story_path(&world_root);
-        std::fs::create_dir_all(&story_path)?;
-        
-        // Create initial README.md file
-        let readme_path = story_path.join("README.md");
         let readme_content = format!(
             "# {}\n\n**Type:** {}\n**Status:** {:?}\n\n## Synopsis\n\n[Story synopsis goes here]\n\n## Episodes\n\n[Episodes will be listed here]\n",
             self.display_name(),
             self.story_type,
             self.status
         );
-        std::fs::write(&readme_path, readme_content)?;
-        
-        println!("📁 Created story directory: {}", story_path.display());
-        Ok(())
+        crate::database::after_commit(move || {
+            std::fs::create_dir_all(&story_path)?;
+            std::fs::write(story_path.join("README.md"), readme_content)?;
+            say!("📁 Created story directory: {}", story_path.display());
+            Ok(())
+        })
     }
 
     /// Delete story with directory from filesystem
@@ -87,19 +123,61 @@ impl Story {
             anyhow::bail!("Use --force to confirm deletion");
         }
         
-        let world_root = Self::ensure_world_context()?;
-        let story_path = self.get_story_path(&world_root);
-        
         // Delete from database first
         self.delete(force)?;
-        
-        // Delete story directory if it exists
-        if story_path.exists() {
-            std::fs::remove_dir_all(&story_path)?;
-            println!("🗑️ Deleted story directory: {}", story_path.display());
+        self.remove_directory()
+    }
+
+    /// Delete the story directory if it exists, once the command commits
+    pub fn remove_directory(&self) -> anyhow::Result<()> {
+        let world_root = Self::ensure_world_context()?;
+        let story_path = self.get_story_path(&world_root);
+
+        crate::database::after_commit(move || {
+            if story_path.exists() {
+                std::fs::remove_dir_all(&story_path)?;
+                say!("🗑️ Deleted story directory: {}", story_path.display());
+            }
+            Ok(())
+        })
+    }
+
+    /// Rename story, moving its directory and re-pointing its episodes
+    pub fn rename_with_directory(&mut self, new_name: String) -> anyhow::Result<()> {
+        let world_root = Self::ensure_world_context()?;
+        let old_path = self.get_story_path(&world_root);
+        let new_path = world_root.join("stories").join(&new_name);
+        if new_path.exists() {
+            anyhow::bail!("Story directory already exists: {}", new_path.display());
         }
-        
-        Ok(())
+
+        let conn = Self::get_database_connection()?;
+        Self::check_key_available(&conn, &new_name)?;
+
+        // Episodes reference their story by name, so they move in the same transaction
+        let tx = crate::database::savepoint(&conn)?;
+        StoryDb::update_key(&tx, self.id, &new_name)?;
+        let episodes = tx.execute(
+            "UPDATE episodes SET story = ?1 WHERE story = ?2",
+            [&new_name, &self.name],
+        )?;
+
+        tx.commit()?;
+
+        say!("✅ Renamed Story '{}' → '{}'", self.name, new_name);
+        if episodes > 0 {
+            say!("📄 Moved {} episode(s) to '{}'", episodes, new_name);
+        }
+        self.name = new_name;
+
+        // The directory follows the database once the command commits
+        crate::database::after_commit(move || {
+            if old_path.exists() {
+                std::fs::rename(&old_path, &new_path)?;
+                say!("📁 Moved story directory: {}", new_path.display());
+            }
+            Ok(())
+        })
     }
 
     /// Get total word count including all episodes
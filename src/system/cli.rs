@@ -3,21 +3,31 @@ use clap::Subcommand;
 #[derive(Subcommand)]
 pub enum SystemCommands {
     /// Create a new system with unified --set for all fields
+    #[command(after_help = crate::relations::relation_set_help("system"))]
     Create {
         /// System name (unique identifier)
         name: String,
         /// Set any field (--set display_name="Name" --set system_type="magic" --set status="Active" --set complexity=high)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
     },
     
     /// List systems in current world
-    List,
+    List {
+        #[command(flatten)]
+        options: crate::listing::ListArgs,
+    },
     
     /// Show system details
     Info {
         /// System name
         name: String,
+        /// Only show relations valid at this timeline date
+        #[arg(long)]
+        at: Option<String>,
     },
     
     /// Delete a system
@@ -27,16 +37,48 @@ pub enum SystemCommands {
         /// Skip confirmation prompt
         #[arg(long)]
         force: bool,
+        /// What to do with existing relations: refuse, detach or cascade
+        #[arg(long, value_enum, default_value_t = crate::relations::DeleteMode::Refuse)]
+        on_relations: crate::relations::DeleteMode,
     },
 
     /// Update an existing system
+    #[command(after_help = crate::relations::relation_set_help("system"))]
     Update {
         /// System name
         name: String,
         /// Set any field (--set display_name="Name" --set system_type="magic" --set status="Active" --set complexity=high)
         #[arg(long, value_parser = parse_key_val)]
         set: Vec<(String, String)>,
+        /// Set a field to a JSON value (--set-json appearance='{"eyes":"grey"}' --set-json stats.hp=12)
+        #[arg(long = "set-json", value_parser = parse_key_val)]
+        set_json: Vec<(String, String)>,
+        /// Remove a metadata field (--unset age --unset stats.strength)
+        #[arg(long)]
+        unset: Vec<String>,
+    },
+    /// Rename a system, keeping its relations
+    Rename {
+        /// Current system name
+        name: String,
+        /// New system name
+        new_name: String,
+    },
+    /// Merge a duplicate system into another one, moving its relations
+    Merge {
+        /// System to keep
+        keep: String,
+        /// Duplicate system to merge in and delete (its name becomes an alias)
+        drop: String,
+        /// Metadata set differently on both: keep the survivor's value, take the duplicate's, or fail
+        #[arg(long, value_enum, default_value_t = crate::relations::MergePolicy::Keep)]
+        on_conflict: crate::relations::MergePolicy,
+        /// Skip confirmation prompt
+        #[arg(long)]
+        force: bool,
     },
+    /// Show the field schema of systems (built-in fields and [schema.system] in config.toml)
+    Schema,
 }
 
 /// Parse a single key-value pair for --set flag
@@ -1,44 +1,55 @@
 use super::cli::SystemCommands;
 use super::models::{System, SystemStatus};
-use crate::relations::{process_relations, EntityType, separate_relation_fields};
+use crate::relations::{process_relations, EntityType, separate_relation_fields, relation_keys_for, show_entity_relations, DeleteMode, check_entity_delete, release_entity, entity_relations, MergePolicy, metadata_conflicts};
 use anyhow::Result;
+use crate::say;
 
 pub fn handle_system_command(command: SystemCommands) -> Result<()> {
     match command {
-        SystemCommands::Create { name, set } => {
-            handle_create(name, set)
+        SystemCommands::Create { name, set, set_json } => {
+            handle_create(name, set, set_json)
         }
-        SystemCommands::List => handle_list(),
-        SystemCommands::Info { name } => handle_info(name),
-        SystemCommands::Delete { name, force } => handle_delete(name, force),
-        SystemCommands::Update { name, set } => handle_update(name, set),
+        SystemCommands::List { options } => handle_list(options),
+        SystemCommands::Schema => System::show_schema(),
+        SystemCommands::Info { name, at } => handle_info(name, at),
+        SystemCommands::Delete { name, force, on_relations } => handle_delete(name, force, on_relations),
+        SystemCommands::Rename { name, new_name } => handle_rename(name, new_name),
+        SystemCommands::Merge { keep, drop, on_conflict, force } => handle_merge(keep, drop, on_conflict, force),
+        SystemCommands::Update { name, set, set_json, unset } => handle_update(name, set, set_json, unset),
     }
 }
 
-fn handle_update(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("🔄 Updating system '{name}'");
+fn handle_update(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>, unset: Vec<String>) -> Result<()> {
+    say!("🔄 Updating system '{name}'");
 
     let mut system = System::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("System '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("System", &name))?;
 
     let regular_fields = process_relations(EntityType::System(name.clone()), set_args)?;
+    system.unset_fields(&unset)?;
+    system.process_json_args(set_json)?;
     system.update(regular_fields)?;
 
-    println!("✅ System '{}' updated!", name);
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::System(system.name.clone()), None)?;
+        return crate::output::emit_entity("system", Some("updated"), &system, Some(relations));
+    }
+
+    say!("✅ System '{}' updated!", name);
     show_created_system(&system)?;
 
     Ok(())
 }
 
-fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
-    println!("⚙️  Creating system '{name}'");
+fn handle_create(name: String, set_args: Vec<(String, String)>, set_json: Vec<(String, String)>) -> Result<()> {
+    say!("⚙️  Creating system '{name}'");
     
     // Separate relation fields from regular fields  
-    let relation_keys = ["character", "location", "race"];
+    let relation_keys = relation_keys_for("system");
     let (relation_fields, regular_fields) = separate_relation_fields(set_args, &relation_keys);
     
     // Create system with regular fields FIRST
-    let mut system = System::create_new(name.clone(), regular_fields)?;
+    let mut system = System::create_new(name.clone(), regular_fields, set_json)?;
     system.create()?;
     
     // THEN process relations after system exists in database
@@ -46,25 +57,30 @@ fn handle_create(name: String, set_args: Vec<(String, String)>) -> Result<()> {
         process_relations(EntityType::System(name.clone()), relation_fields)?;
     }
     
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::System(system.name.clone()), None)?;
+        return crate::output::emit_entity("system", Some("created"), &system, Some(relations));
+    }
+    
     show_created_system(&system)?;
     Ok(())
 }
 
 fn show_created_system(system: &System) -> Result<()> {
-    println!("   Display name: {}", system.display_name);
-    println!("   Type: {}", system.system_type);
-    println!("   Status: {:?}", system.status);
+    say!("   Display name: {}", system.display_name);
+    say!("   Type: {}", system.system_type);
+    say!("   Status: {:?}", system.status);
 
     if let Some(desc) = system.metadata.get("description") {
-        println!("   Description: {}", desc.as_str().unwrap_or(""));
+        say!("   Description: {}", desc.as_str().unwrap_or(""));
     }
 
     // Show metadata
     if !system.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &system.metadata {
             if key != "description" {
-                println!("     {}: {}", key, value);
+                say!("     {}: {}", key, value);
             }
         }
     }
@@ -72,96 +88,179 @@ fn show_created_system(system: &System) -> Result<()> {
     Ok(())
 }
 
-fn handle_list() -> Result<()> {
-    let systems = System::list()?;
+fn handle_list(options: crate::listing::ListArgs) -> Result<()> {
+    let systems = System::list_with(&options)?;
+    
+    if crate::output::is_structured() {
+        return crate::output::emit_list(&systems, &options.fields);
+    }
+    
+    if !options.fields.is_empty() {
+        return crate::listing::print_fields(&systems, &options.fields);
+    }
 
     if systems.is_empty() {
-        println!("⚙️  No systems found in this world");
-        println!("   Use 'multiverse system create <name> --set display_name=\\\"<name>\\\" --set system_type=<type>' to create one");
+        say!("⚙️  No systems found in this world");
+        say!("   Use 'multiverse system create <name> --set display_name=\\\"<name>\\\" --set system_type=<type>' to create one");
         return Ok(());
     }
 
-    println!("⚙️  Systems in current world:");
+    say!("⚙️  Systems in current world:");
 
     for system in systems {
-        let status_emoji = match system.status {
+        let status_emoji = crate::status::status_emoji("system", system.status.as_str(), match system.status {
             SystemStatus::Active => "🟢",
             SystemStatus::Inactive => "🟡",
             SystemStatus::Deprecated => "🔴",
             SystemStatus::Archived => "📦",
-        };
+            SystemStatus::Custom(_) => crate::status::CUSTOM_STATUS_EMOJI,
+        });
 
-        println!(
+        say!(
             "   {} {} - \"{}\" ({})",
             status_emoji, system.name, system.display_name, system.system_type
         );
 
         // Show key metadata fields
         if let Some(complexity) = system.metadata.get("complexity") {
-            println!(
+            say!(
                 "      Complexity: {}",
                 complexity.as_str().unwrap_or("Unknown")
             );
         }
         if let Some(origin) = system.metadata.get("origin") {
-            println!("      Origin: {}", origin.as_str().unwrap_or("Unknown"));
+            say!("      Origin: {}", origin.as_str().unwrap_or("Unknown"));
         }
 
         if let Some(desc) = system.metadata.get("description") {
-            println!("      {}", desc.as_str().unwrap_or(""));
+            say!("      {}", desc.as_str().unwrap_or(""));
         }
     }
 
     Ok(())
 }
 
-fn handle_info(name: String) -> Result<()> {
+fn handle_info(name: String, at: Option<String>) -> Result<()> {
     let system =
-        System::get(&name)?.ok_or_else(|| anyhow::anyhow!("System '{}' not found", name))?;
+        System::get(&name)?.ok_or_else(|| crate::output::not_found("System", &name))?;
+    
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::System(system.name.clone()), at.as_deref())?;
+        return crate::output::emit_entity("system", None, &system, Some(relations));
+    }
 
-    println!("⚙️  System: {} - \"{}\"", system.name, system.display_name);
-    println!("   Type: {}", system.system_type);
-    println!("   Status: {:?}", system.status);
-    println!("   Created: {}", system.created_at.format("%Y-%m-%d %H:%M"));
+    say!("⚙️  System: {} - \"{}\"", system.name, system.display_name);
+    say!("   Type: {}", system.system_type);
+    say!("   Status: {:?}", system.status);
+    say!("   Created: {}", system.created_at.format("%Y-%m-%d %H:%M"));
 
     if let Some(desc) = system.metadata.get("description") {
-        println!("   Description: {}", desc.as_str().unwrap_or(""));
+        say!("   Description: {}", desc.as_str().unwrap_or(""));
     }
 
     // Show metadata
     if !system.metadata.is_empty() {
-        println!("   Metadata:");
+        say!("   Metadata:");
         for (key, value) in &system.metadata {
-            println!("     {}: {}", key, value);
+            say!("     {}: {}", key, value);
         }
     }
 
-    // TODO: Show episodes/characters where system is used
-    println!("   Usage: (to be implemented)");
+    show_entity_relations(&EntityType::System(system.name.clone()), at.as_deref())?;
 
     Ok(())
 }
 
-fn handle_delete(name: String, force: bool) -> Result<()> {
+fn handle_delete(name: String, force: bool, on_relations: DeleteMode) -> Result<()> {
     let _system =
-        System::get(&name)?.ok_or_else(|| anyhow::anyhow!("System '{}' not found", name))?;
+        System::get(&name)?.ok_or_else(|| crate::output::not_found("System", &name))?;
+
+    let entity = EntityType::System(name.clone());
+    let relations = check_entity_delete(&entity, on_relations)?;
 
     if !force {
-        println!("⚠️  Are you sure you want to delete system '{name}'?");
-        println!(
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("delete system '{}'", name)));
+        }
+        say!("⚠️  Are you sure you want to delete system '{name}'?");
+        say!(
             "   This will permanently delete the system and remove it from all usage references"
         );
-        println!("   Use --force to skip this confirmation");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will be removed as well", relations.len());
+        }
+        say!("   Use --force to skip this confirmation");
         return Ok(());
     }
 
-    println!("🗑️  Deleting system '{name}'...");
+    say!("🗑️  Deleting system '{name}'...");
 
     let system = System::get(&name)?
-        .ok_or_else(|| anyhow::anyhow!("System '{}' not found", name))?;
+        .ok_or_else(|| crate::output::not_found("System", &name))?;
+    release_entity(&entity, on_relations)?;
     system.delete(force)?;
 
-    println!("✅ System '{name}' deleted!");
+    say!("✅ System '{name}' deleted!");
+
+    if crate::output::is_structured() {
+        return crate::output::emit_entity("system", Some("deleted"), &system, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_rename(name: String, new_name: String) -> Result<()> {
+    let mut system = System::get(&name)?
+        .ok_or_else(|| crate::output::not_found("System", &name))?;
+
+    say!("✏️  Renaming system '{name}' to '{new_name}'...");
+
+    system.rename(new_name.clone())?;
+
+    say!("✅ System '{name}' is now '{new_name}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::System(system.name.clone()), None)?;
+        return crate::output::emit_entity("system", Some("renamed"), &system, Some(relations));
+    }
+
+    Ok(())
+}
+
+fn handle_merge(keep: String, drop: String, on_conflict: MergePolicy, force: bool) -> Result<()> {
+    let mut survivor = System::get(&keep)?
+        .ok_or_else(|| crate::output::not_found("System", &keep))?;
+    let duplicate = System::get(&drop)?
+        .ok_or_else(|| crate::output::not_found("System", &drop))?;
+
+    if !force {
+        if crate::output::is_structured() {
+            return Err(crate::output::confirmation_required(&format!("merge system '{}' into '{}'", drop, keep)));
+        }
+        let relations = entity_relations(&EntityType::System(drop.clone()), None)?;
+        let conflicts = metadata_conflicts(&survivor.metadata, &duplicate.metadata);
+        say!("⚠️  Are you sure you want to merge system '{drop}' into '{keep}'?");
+        say!("   '{drop}' will be deleted and kept as an alias of '{keep}'");
+        if !relations.is_empty() {
+            say!("   {} relation(s) will move to '{keep}'", relations.len());
+        }
+        if !conflicts.is_empty() {
+            say!("   Conflicting metadata ({:?}): {}", on_conflict, conflicts.join(", "));
+        }
+        say!("   Use --force to skip this confirmation");
+        return Ok(());
+    }
+
+    say!("🔀 Merging system '{drop}' into '{keep}'...");
+
+    survivor.merge(duplicate, on_conflict)?;
+
+    say!("✅ System '{drop}' merged into '{keep}'!");
+
+    if crate::output::is_structured() {
+        let relations = entity_relations(&EntityType::System(survivor.name.clone()), None)?;
+        return crate::output::emit_entity("system", Some("merged"), &survivor, Some(relations));
+    }
 
     Ok(())
 }
@@ -21,7 +21,7 @@ define_complete_entity!(
         system_type TEXT NOT NULL,
         status TEXT NOT NULL DEFAULT 'Active',
         metadata TEXT NOT NULL DEFAULT '{}',
-        created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+        created_at TEXT NOT NULL
     )"
 );
 
@@ -36,13 +36,10 @@ impl System {
         }
     }
     
-    /// Resolve system name to database ID
+    /// Resolve system name or alias to database ID
     pub fn resolve_id(name: &str) -> anyhow::Result<String> {
         let conn = Self::get_database_connection()?;
-        let mut stmt = conn.prepare("SELECT id FROM systems WHERE name = ?")?;
-        let id: i32 = stmt.query_row([name], |row| {
-            row.get(0)
-        }).map_err(|_| anyhow::anyhow!("System not found: '{}'", name))?;
+        let id = crate::relations::resolve_entity_id(&conn, SystemDb::TABLE, "System", name)?;
         Ok(id.to_string())
     }
 }
\ No newline at end of file
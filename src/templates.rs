@@ -640,8 +640,25 @@ CREATE TABLE event_character_relations (
 -- ... e tutte le altre combinazioni secondo necessità
 ```
 
+#### Viste per Query Semplici (preferirle ai JOIN manuali)
+```sql
+-- v_relations: tutte le relazioni con tipo e nome delle entità, senza JOIN né CAST degli id
+-- colonne: relation, from_type, from_id, from_name, to_type, to_id, to_name, role,
+--          metadata, from_date, until_date, created_at
+SELECT from_name, role, to_name FROM v_relations WHERE to_type = 'faction' AND to_name = 'stark';
+
+-- v_characters, v_locations, v_factions, ...: colonne della tabella + una colonna per ogni chiave metadata
+SELECT name, age, house FROM v_characters WHERE house = 'stark';
+```
+
 ### Query Avanzate per Controlli Narrativi
 
+Queste query sono già salvate nel mondo in `.multiverse/queries/*.sql`:
+```bash
+multiverse query list                                          # Nome, parametri e descrizione
+multiverse query run orphaned_characters --param faction=stark # Parametri :nome via --param
+```
+
 #### Controlli di Consistenza Worldbuilding
 ```bash
 # 1. Personaggi senza casa/fazione
@@ -0,0 +1,169 @@
+//! Query views, so `multiverse query` needs no relation JOINs or metadata json_extract
+//!
+//! ```text
+//! v_relations   one row per relation of every table: relation, from_type, from_id, from_name,
+//!               to_type, to_id, to_name, role, metadata, from_date, until_date, created_at
+//! v_characters  characters.* plus one column per metadata key (v_locations, v_factions, ...)
+//! ```
+//!
+//! They are views of the world database itself, so other connections and external SQLite
+//! tools see them too. They follow the relation registry (config-defined relations
+//! included), the declared [schema.<kind>] fields and the metadata keys in use: every
+//! connection brings them up to date when it opens, and every command before it commits.
+
+use anyhow::Result;
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::BTreeSet;
+use crate::database::table_exists;
+use crate::relations::{relation_tables, EntityType, RelationTable};
+use crate::relations::handlers::kind_for_table;
+
+const ENTITY_KINDS: [&str; 8] = ["character", "location", "faction", "race", "system", "event", "story", "episode"];
+
+/// Create the views, replacing those whose definition changed since they were written
+pub fn refresh_query_views(conn: &Connection) -> Result<()> {
+    for kind in ENTITY_KINDS {
+        let table = EntityType::from_kind(kind, String::new())?.table();
+        if table_exists(conn, table)? {
+            create_entity_view(conn, kind, table)?;
+        }
+    }
+    create_relations_view(conn)
+}
+
+/// Drop every view, so migrations may rebuild the tables they read
+pub fn drop_query_views(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare("SELECT name FROM sqlite_master WHERE type = 'view' AND name LIKE 'v\\_%' ESCAPE '\\'")?;
+    let views = stmt.query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    for view in views {
+        conn.execute(&format!("DROP VIEW IF EXISTS {}", quote_identifier(&view)), [])?;
+    }
+    Ok(())
+}
+
+/// Write a view unless it already has exactly this definition, which keeps commands
+/// that change nothing from rewriting the schema
+fn replace_view(conn: &Connection, name: &str, body: &str) -> Result<()> {
+    let create = format!("CREATE VIEW {} AS {}", quote_identifier(name), body);
+    let current: Option<String> = conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'view' AND name = ?1",
+        [name],
+        |row| row.get(0),
+    ).optional()?;
+    if current.as_deref() == Some(create.as_str()) {
+        return Ok(());
+    }
+
+    conn.execute(&format!("DROP VIEW IF EXISTS {}", quote_identifier(name)), [])?;
+    conn.execute(&create, [])?;
+    Ok(())
+}
+
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+fn quote_literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+fn table_columns(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", quote_identifier(table)))?;
+    let columns = stmt.query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(columns)
+}
+
+/// v_<table>: the entity columns plus declared schema fields and every metadata key in use
+fn create_entity_view(conn: &Connection, kind: &str, table: &str) -> Result<()> {
+    let columns = table_columns(conn, table)?;
+
+    let mut keys: BTreeSet<String> = crate::schema::entity_schema(kind)
+        .map(|schema| schema.keys().cloned().collect())
+        .unwrap_or_default();
+    let mut stmt = conn.prepare(&format!(
+        "SELECT DISTINCT key FROM {}, json_each({}.metadata) WHERE json_valid({}.metadata)",
+        table, table, table
+    ))?;
+    keys.extend(stmt.query_map([], |row| row.get::<_, String>(0))?.collect::<rusqlite::Result<Vec<String>>>()?);
+
+    let mut select = vec!["e.*".to_string()];
+    for key in keys.iter().filter(|key| !columns.iter().any(|column| column.eq_ignore_ascii_case(key))) {
+        let path = format!("$.\"{}\"", key.replace('"', "\\\""));
+        select.push(format!("json_extract(e.metadata, {}) AS {}", quote_literal(&path), quote_identifier(key)));
+    }
+
+    replace_view(conn, &format!("v_{}", table), &format!("SELECT {} FROM {} e", select.join(", "), table))
+}
+
+/// Name of an entity row as used on the command line (story:number for episodes)
+fn name_expression(table: &str, alias: &str) -> String {
+    if table == "episodes" {
+        format!("{a}.story || ':' || {a}.number", a = alias)
+    } else {
+        format!("{}.name", alias)
+    }
+}
+
+/// v_relations: every existing relation table of the registry, with entity types and names
+fn create_relations_view(conn: &Connection) -> Result<()> {
+    let mut selects = Vec::new();
+    for table in relation_tables() {
+        if table_exists(conn, table.table)? {
+            selects.push(relation_select(conn, table)?);
+        }
+    }
+
+    let body = if selects.is_empty() {
+        // No relation table yet: keep the columns so queries still prepare
+        "SELECT NULL AS relation, NULL AS from_type, NULL AS from_id, NULL AS from_name, NULL AS to_type, \
+         NULL AS to_id, NULL AS to_name, NULL AS role, NULL AS metadata, NULL AS from_date, \
+         NULL AS until_date, NULL AS created_at WHERE 0".to_string()
+    } else {
+        selects.join("\nUNION ALL\n")
+    };
+    replace_view(conn, "v_relations", &body)
+}
+
+fn relation_select(conn: &Connection, table: &RelationTable) -> Result<String> {
+    let columns = table_columns(conn, table.table)?;
+    let column = |name: &str| if columns.iter().any(|column| column == name) {
+        format!("r.{}", name)
+    } else {
+        "NULL".to_string()
+    };
+
+    // An entity table that was never created leaves its side's names NULL
+    let mut joins = String::new();
+    let mut side = |entity_table: &str, alias: &str, id_column: &str| -> Result<String> {
+        if !table_exists(conn, entity_table)? {
+            return Ok("NULL".to_string());
+        }
+        joins.push_str(&format!(" LEFT JOIN {t} {a} ON {a}.id = CAST(r.{c} AS INTEGER)", t = entity_table, a = alias, c = id_column));
+        Ok(name_expression(entity_table, alias))
+    };
+    let from_name = side(table.from_table, "f", "from_id")?;
+    let to_name = side(table.to_table, "t", "to_id")?;
+
+    Ok(format!(
+        "SELECT {relation} AS relation, \
+         {from_kind} AS from_type, CAST(r.from_id AS INTEGER) AS from_id, {from_name} AS from_name, \
+         {to_kind} AS to_type, CAST(r.to_id AS INTEGER) AS to_id, {to_name} AS to_name, \
+         {role} AS role, {metadata} AS metadata, {from_date} AS from_date, {until_date} AS until_date, \
+         {created_at} AS created_at \
+         FROM {table} r{joins}",
+        relation = quote_literal(table.name()),
+        from_kind = quote_literal(kind_for_table(table.from_table)),
+        to_kind = quote_literal(kind_for_table(table.to_table)),
+        from_name = from_name,
+        to_name = to_name,
+        role = column(table.role_column()),
+        metadata = column("metadata"),
+        from_date = column("from_date"),
+        until_date = column("until_date"),
+        created_at = column("created_at"),
+        table = table.table,
+        joins = joins,
+    ))
+}
@@ -162,4 +162,288 @@ fn test_character_delete() -> Result<()> {
     assert!(!test.entity_exists("characters", "temp_hero")?);
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_character_rename() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("CharacterRenameTest")?;
+    
+    test.run_command_assert_success(&["faction", "create", "rangers"])?;
+    test.run_command_assert_success(&[
+        "character", "create", "aragron",
+        "--set", "faction=rangers*captain"
+    ])?;
+    test.run_command_assert_success(&["character", "create", "legolas"])?;
+    
+    // Rename keeps the id, so the relation follows the new name
+    test.run_command_assert_success(&["character", "rename", "aragron", "aragorn"])?;
+    assert!(!test.run_command(&["character", "info", "aragron"])?.status.success());
+    
+    let output = test.run_command_assert_success(&["relation", "list", "character", "aragorn"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("faction rangers (role: captain)"));
+    
+    // Names already in use are refused
+    let output = test.run_command(&["character", "rename", "aragorn", "legolas"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already exists"));
+    
+    Ok(())
+}
+
+#[test]
+fn test_character_merge() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("CharacterMergeTest")?;
+    
+    test.run_command_assert_success(&["faction", "create", "rangers"])?;
+    test.run_command_assert_success(&["location", "create", "bree"])?;
+    test.run_command_assert_success(&[
+        "character", "create", "aragorn",
+        "--set", "faction=rangers*captain",
+        "--set", "title=King"
+    ])?;
+    test.run_command_assert_success(&[
+        "character", "create", "strider",
+        "--set", "faction=rangers*scout",
+        "--set", "location=bree*resident",
+        "--set", "title=Ranger",
+        "--set", "age=87"
+    ])?;
+    
+    // Conflicting metadata aborts the merge when asked to
+    let output = test.run_command(&[
+        "character", "merge", "aragorn", "strider", "--on-conflict", "fail", "--force"
+    ])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("title"));
+    
+    test.run_command_assert_success(&["character", "merge", "aragorn", "strider", "--force"])?;
+    assert!(!test.entity_exists("characters", "strider")?);
+    // The merged name now finds the survivor
+    let output = test.run_command_assert_success(&["character", "info", "strider"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Character: aragorn"));
+    
+    // The survivor keeps its own values, gains the missing ones and remembers the alias
+    let output = test.run_command_assert_success(&["character", "info", "aragorn"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("King"));
+    assert!(stdout.contains("87"));
+    assert!(stdout.contains("aliases: [\"strider\"]"));
+    
+    // Relations move over; the duplicate faction row keeps the survivor's role
+    assert!(stdout.contains("rangers (role: captain)"));
+    assert!(stdout.contains("bree (relationship_type: resident)"));
+    assert!(!stdout.contains("scout"));
+    
+    Ok(())
+}
+
+#[test]
+fn test_character_aliases() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("CharacterAliasTest")?;
+    
+    test.run_command_assert_success(&[
+        "character", "create", "aragorn",
+        "--set", "alias=strider,elessar"
+    ])?;
+    
+    // Relations resolve aliases to the entity
+    test.run_command_assert_success(&[
+        "character", "create", "frodo",
+        "--set", "character=strider*friend"
+    ])?;
+    let output = test.run_command_assert_success(&["relation", "list", "character", "elessar"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("frodo"));
+    
+    // Typos get a suggestion
+    let output = test.run_command(&[
+        "character", "update", "frodo",
+        "--set", "character=aragron*friend"
+    ])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("did you mean aragorn?"));
+    
+    // An alias may not be another character's name or alias
+    let output = test.run_command(&["character", "update", "frodo", "--set", "alias=aragorn"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already the name of Character 'aragorn'"));
+    let output = test.run_command(&["character", "create", "gandalf", "--set", "alias=strider"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already used by Character 'aragorn'"));
+    test.run_command_assert_success(&["character", "update", "aragorn", "--set", "alias+=dunadan"])?;
+    
+    // Every command taking a character name accepts an alias
+    test.run_command_assert_success(&["character", "update", "strider", "--set", "age=87"])?;
+    let output = test.run_command_assert_success(&["character", "info", "elessar"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Character: aragorn"));
+    assert!(stdout.contains("87"));
+    let output = test.run_command(&["character", "create", "elessar"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("already an alias of Character 'aragorn'"));
+    test.run_command_assert_success(&["character", "rename", "dunadan", "aragorn_ii"])?;
+    assert!(test.entity_exists("characters", "aragorn_ii")?);
+    test.run_command_assert_success(&["faction", "create", "fellowship", "--set", "alias=company"])?;
+    test.run_command_assert_success(&["faction", "info", "company"])?;
+    test.run_command_assert_success(&["faction", "delete", "company", "--force"])?;
+    assert!(!test.entity_exists("factions", "fellowship")?);
+    
+    Ok(())
+}
+#[test]
+fn test_character_structured_metadata() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("CharacterMetadataTest")?;
+    
+    test.run_command_assert_success(&[
+        "character", "create", "boromir",
+        "--set", "themes+=power",
+        "--set", "stats.strength=12",
+        "--set-json", r#"appearance={"eyes":"grey","height":1.9}"#,
+        "--set", "age=41"
+    ])?;
+    test.run_command_assert_success(&[
+        "character", "update", "boromir",
+        "--set", "themes+=betrayal",
+        "--set", "themes+=redemption",
+        "--set", "themes-=power",
+        "--unset", "age"
+    ])?;
+    
+    let result: serde_json::Value = serde_json::from_str(
+        &test.query("SELECT metadata FROM characters WHERE name = 'boromir'")?
+    )?;
+    let metadata = &result["rows"][0]["metadata"];
+    assert_eq!(metadata["themes"], serde_json::json!(["betrayal", "redemption"]));
+    assert_eq!(metadata["stats"]["strength"], "12");
+    assert_eq!(metadata["appearance"]["height"], 1.9);
+    assert!(metadata.get("age").is_none());
+    
+    // Built-in fields are not lists
+    let output = test.run_command(&["character", "update", "boromir", "--set", "status+=Dead"])?;
+    assert!(!output.status.success());
+    
+    Ok(())
+}
+#[test]
+fn test_character_list_filters() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("CharacterListTest")?;
+    
+    test.run_command_assert_success(&["character", "create", "aragorn", "--set", "profession=ranger", "--set-json", "age=87"])?;
+    test.run_command_assert_success(&["character", "create", "halbarad", "--set", "profession=ranger", "--set-json", "age=60"])?;
+    test.run_command_assert_success(&["character", "create", "frodo", "--set", "profession=hobbit", "--set-json", "age=50"])?;
+    test.run_command_assert_success(&["character", "create", "boromir", "--set", "profession=ranger", "--set", "status=Deceased"])?;
+    
+    let output = test.run_command_assert_success(&[
+        "character", "list",
+        "--where", "status=Active",
+        "--where", "metadata.profession=ranger",
+        "--sort=-age",
+        "--fields", "name,age",
+    ])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 3, "{}", stdout);
+    assert!(lines[0].starts_with("name") && lines[0].contains("age"));
+    assert!(lines[1].starts_with("aragorn") && lines[1].contains("87"));
+    assert!(lines[2].starts_with("halbarad") && lines[2].contains("60"));
+    
+    let output = test.run_command_assert_success(&["character", "list", "--sort", "age", "--limit", "1", "--fields", "name"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().nth(1), Some("frodo"));
+    
+    Ok(())
+}
+#[test]
+fn test_character_character_relations() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("CharacterFamilyTest")?;
+
+    for name in ["ned_stark", "jon_snow", "arya_stark"] {
+        test.run_command_assert_success(&["character", "create", name])?;
+    }
+
+    test.run_command_assert_success(&[
+        "character", "update", "ned_stark",
+        "--set", "character=jon_snow*father"
+    ])?;
+
+    // Symmetric relation set from both sides is stored once
+    test.run_command_assert_success(&[
+        "character", "update", "jon_snow",
+        "--set", "character=arya_stark*sibling"
+    ])?;
+    test.run_command_assert_success(&[
+        "character", "update", "arya_stark",
+        "--set", "character=jon_snow*sibling"
+    ])?;
+
+    let output = test.run_command_assert_success(&["relation", "list", "--table", "character_character"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.matches("sibling").count(), 1);
+
+    // Inverse type is shown from the other side
+    let output = test.run_command_assert_success(&["character", "info", "jon_snow"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("→ ned_stark (relationship_type: child)"));
+    assert!(stdout.contains("→ arya_stark (relationship_type: sibling)"));
+
+    let output = test.run_command_assert_success(&["character", "info", "arya_stark"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("→ jon_snow (relationship_type: sibling)"));
+
+    // Setting or retyping from the child's side keeps the parent's specific type
+    test.run_command_assert_success(&[
+        "character", "update", "jon_snow",
+        "--set", "character=ned_stark*child"
+    ])?;
+    test.run_command_assert_success(&[
+        "relation", "retype", "character", "jon_snow", "character", "ned_stark", "child"
+    ])?;
+    let output = test.run_command_assert_success(&["character", "info", "ned_stark"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("→ jon_snow (relationship_type: father)"));
+
+    // A type without inverse set from the other side turns the pair around instead of adding a row
+    let pairs = test.query_count("character_character_relations")?;
+    test.run_command_assert_success(&[
+        "character", "update", "jon_snow",
+        "--set", "character=ned_stark*bannerman"
+    ])?;
+    assert_eq!(test.query_count("character_character_relations")?, pairs);
+    let output = test.run_command_assert_success(&["character", "info", "jon_snow"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("→ ned_stark (relationship_type: bannerman)"));
+
+    Ok(())
+}
+
+#[test]
+fn test_failed_command_is_rolled_back() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RollbackTest")?;
+
+    test.run_command_assert_success(&["faction", "create", "stark"])?;
+
+    // The faction relation is stored before the unknown location fails the command
+    let output = test.run_command(&[
+        "character", "create", "arya",
+        "--set", "faction=stark",
+        "--set", "location=nowhere"
+    ])?;
+    assert!(!output.status.success());
+    assert!(!test.entity_exists("characters", "arya")?);
+    assert_eq!(test.query_count("character_faction_relations")?, 0);
+
+    // The same update without the bad relation goes through as a whole
+    test.run_command_assert_success(&["location", "create", "winterfell"])?;
+    test.run_command_assert_success(&[
+        "character", "create", "arya",
+        "--set", "faction=stark",
+        "--set", "location=winterfell"
+    ])?;
+    assert_eq!(test.query_count("character_faction_relations")?, 1);
+    assert_eq!(test.query_count("character_location_relations")?, 1);
+
+    Ok(())
+}
@@ -28,8 +28,22 @@ impl MultiverseTest {
         Ok(output)
     }
     
+    /// Run a command with `input` piped to its stdin
+    pub fn run_command_with_input(&self, args: &[&str], input: &str) -> Result<std::process::Output> {
+        use std::io::Write;
+        let mut child = Command::new(&self.binary_path)
+            .args(args)
+            .current_dir(self.temp_dir.path())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()?;
+        child.stdin.take().expect("stdin is piped").write_all(input.as_bytes())?;
+        Ok(child.wait_with_output()?)
+    }
+    
     pub fn query(&self, sql: &str) -> Result<String> {
-        let output = self.run_command(&["query", sql])?;
+        let output = self.run_command(&["query", sql, "--format", "json"])?;
         Ok(String::from_utf8_lossy(&output.stdout).to_string())
     }
     
@@ -41,49 +55,54 @@ impl MultiverseTest {
         Ok(())
     }
     
-    /// Parse formatted query output to extract numeric values
+    /// Append raw TOML to the world's .multiverse/config.toml
+    pub fn append_config(&self, toml: &str) -> Result<()> {
+        use std::io::Write;
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(self.temp_dir.path().join(".multiverse/config.toml"))?;
+        writeln!(file, "\n{}", toml)?;
+        Ok(())
+    }
+    
+    /// Write a file relative to the world root (e.g. .multiverse/timeline.toml)
+    pub fn write_file(&self, path: &str, content: &str) -> Result<()> {
+        std::fs::write(self.temp_dir.path().join(path), content)?;
+        Ok(())
+    }
+    
+    /// Absolute path of a file relative to the world root
+    pub fn world_path(&self, path: &str) -> PathBuf {
+        self.temp_dir.path().join(path)
+    }
+    
+    /// Check whether a file or directory exists relative to the world root
+    pub fn file_exists(&self, path: &str) -> bool {
+        self.temp_dir.path().join(path).exists()
+    }
+    
+    /// First value of the first row of `query --format json` output
+    fn first_value(&self, query_output: &str) -> Option<serde_json::Value> {
+        let result: serde_json::Value = serde_json::from_str(query_output).ok()?;
+        let first_row = result["rows"].as_array()?.first()?.clone();
+        let column = result["columns"].as_array()?.first()?.as_str()?.to_string();
+        first_row.get(column).cloned()
+    }
+    
+    /// Parse query output to extract the numeric value of the first row
     fn parse_query_number(&self, query_output: &str) -> Result<i32> {
-        let lines: Vec<&str> = query_output.lines().collect();
-        for line in lines {
-            if line.contains('│') && line.chars().any(|c| c.is_numeric()) {
-                let numbers: String = line.chars().filter(|c| c.is_numeric()).collect();
-                if let Ok(count) = numbers.parse::<i32>() {
-                    return Ok(count);
-                }
-            }
-        }
-        Ok(0)
+        Ok(self.first_value(query_output)
+            .and_then(|value| value.as_i64())
+            .unwrap_or(0) as i32)
     }
     
-    /// Parse formatted query output to extract string values from first data row
+    /// Parse query output to extract the first value of the first row as text
     fn parse_query_string(&self, query_output: &str) -> String {
-        let lines: Vec<&str> = query_output.lines().collect();
-        let mut in_data_section = false;
-        
-        for line in lines {
-            // Skip header separators
-            if line.contains("┌") || line.contains("├") || line.contains("└") {
-                continue;
-            }
-            
-            // Check for header row (contains column names in uppercase)
-            if line.contains('│') && (line.contains("metadata") || line.contains("METADATA")) {
-                in_data_section = true;
-                continue;
-            }
-            
-            // Extract actual data row
-            if in_data_section && line.contains('│') {
-                let parts: Vec<&str> = line.split('│').collect();
-                if parts.len() > 1 {
-                    let content = parts[1].trim();
-                    if !content.is_empty() && content != "metadata" {
-                        return content.to_string();
-                    }
-                }
-            }
+        match self.first_value(query_output) {
+            Some(serde_json::Value::String(text)) => text,
+            Some(serde_json::Value::Null) | None => String::new(),
+            Some(other) => other.to_string(),
         }
-        String::new()
     }
     
     pub fn query_count(&self, table: &str) -> Result<i32> {
@@ -0,0 +1,172 @@
+mod common;
+use common::MultiverseTest;
+use anyhow::Result;
+
+#[test]
+fn test_new_world_is_fully_migrated() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("MigrationTest")?;
+
+    let output = test.run_command_assert_success(&["migrate", "--status", "--format", "json"])?;
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let migrations = status.as_array().expect("status is a list");
+    assert!(!migrations.is_empty());
+    assert!(migrations.iter().all(|migration| migration["applied_at"].is_string()), "{}", status);
+    assert_eq!(test.query_count("schema_migrations")?, migrations.len() as i32);
+
+    // Tables exist before anything is created in them
+    assert_eq!(test.query_count("races")?, 0);
+    assert_eq!(test.query_count("character_faction_relations")?, 0);
+
+    let output = test.run_command_assert_success(&["migrate"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("up to date"));
+
+    Ok(())
+}
+
+#[test]
+fn test_old_database_is_migrated_on_open() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("LegacyTest")?;
+
+    // A world.db as written before migrations were recorded: DATETIME created_at on races,
+    // a relation table without metadata or period columns, an empty schema_migrations
+    let db_path = test.world_path(".multiverse/world.db");
+    std::fs::remove_file(&db_path)?;
+    let conn = rusqlite::Connection::open(&db_path)?;
+    conn.execute_batch(
+        "CREATE TABLE schema_migrations (version INTEGER PRIMARY KEY, applied_at DATETIME DEFAULT CURRENT_TIMESTAMP);
+         CREATE TABLE races (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             name TEXT UNIQUE NOT NULL,
+             display_name TEXT NOT NULL,
+             status TEXT NOT NULL DEFAULT 'Active',
+             metadata TEXT NOT NULL DEFAULT '{}',
+             created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+         );
+         INSERT INTO races (name, display_name) VALUES ('elf', 'Elf');
+         CREATE TABLE characters (
+             id INTEGER PRIMARY KEY AUTOINCREMENT,
+             name TEXT UNIQUE NOT NULL,
+             display_name TEXT NOT NULL,
+             status TEXT NOT NULL DEFAULT 'Active',
+             metadata TEXT NOT NULL DEFAULT '{}',
+             created_at TEXT NOT NULL
+         );
+         INSERT INTO characters (name, display_name, created_at) VALUES ('legolas', 'Legolas', '2024-01-01T00:00:00+00:00');
+         CREATE TABLE character_race_relations (
+             from_id TEXT NOT NULL,
+             to_id TEXT NOT NULL,
+             heritage TEXT,
+             created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+             PRIMARY KEY (from_id, to_id),
+             FOREIGN KEY (from_id) REFERENCES characters (id),
+             FOREIGN KEY (to_id) REFERENCES races (id)
+         );
+         INSERT INTO character_race_relations (from_id, to_id, heritage) VALUES ('1', '1', 'full');",
+    )?;
+    drop(conn);
+
+    let output = test.run_command_assert_success(&["migrate", "--status"])?;
+    let status = String::from_utf8_lossy(&output.stdout);
+    assert!(status.contains("0 of"), "{}", status);
+    assert!(status.contains("pending"), "{}", status);
+
+    // Any command brings the database up to date; the old timestamp now loads
+    test.run_command_assert_success(&["race", "info", "elf"])?;
+    let result = test.query("SELECT created_at FROM races WHERE name = 'elf'")?;
+    assert!(result.contains("T") && result.contains("+00:00"), "{}", result);
+
+    // The relation table kept its row and gained the columns added since
+    let result = test.query("SELECT heritage, metadata, from_date, until_date FROM character_race_relations")?;
+    assert!(result.contains("full"), "{}", result);
+    test.run_command_assert_success(&["character", "info", "legolas"])?;
+
+    let result = test.query("SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = 'idx_character_race_relations_to_id'")?;
+    assert!(result.contains("\"COUNT(*)\": 1"), "{}", result);
+
+    let output = test.run_command_assert_success(&["migrate", "--status"])?;
+    let status = String::from_utf8_lossy(&output.stdout);
+    assert!(!status.contains("pending"), "{}", status);
+
+    Ok(())
+}
+
+#[test]
+fn test_migrate_status_lists_applied_and_pending() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("MigrateStatusTest")?;
+
+    // Forget the last two steps, as if the database predates them
+    let conn = rusqlite::Connection::open(test.world_path(".multiverse/world.db"))?;
+    let total: i64 = conn.query_row("SELECT MAX(version) FROM schema_migrations", [], |row| row.get(0))?;
+    conn.execute("DELETE FROM schema_migrations WHERE version > ?1", [total - 2])?;
+    drop(conn);
+
+    let output = test.run_command_assert_success(&["migrate", "--status", "--format", "json"])?;
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    for migration in status.as_array().expect("status is a list") {
+        let version = migration["version"].as_i64().expect("version");
+        assert_eq!(migration["applied_at"].is_string(), version <= total - 2, "{}", migration);
+    }
+
+    let output = test.run_command_assert_success(&["migrate", "--status"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("{} of {} migrations applied", total - 2, total)), "{}", stdout);
+    assert!(stdout.contains(&format!("⏳ {:>3}", total)), "{}", stdout);
+    assert!(stdout.contains("✅   1"), "{}", stdout);
+
+    let output = test.run_command_assert_success(&["migrate"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains(&format!("Applied migration {}:", total - 1)), "{}", stdout);
+    assert!(stdout.contains(&format!("Applied migration {}:", total)), "{}", stdout);
+    assert!(!stdout.contains("Applied migration 1:"), "{}", stdout);
+
+    let output = test.run_command_assert_success(&["migrate", "--status"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains(&format!("{} of {} migrations applied", total, total)));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_relation_tables_are_upgraded_on_open() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("ConfigUpgradeTest")?;
+    test.run_command_assert_success(&["faction", "create", "stark"])?;
+    test.run_command_assert_success(&["faction", "create", "tully"])?;
+
+    // A config-defined table created before metadata and period columns existed, in a
+    // database whose migrations are all recorded
+    let conn = rusqlite::Connection::open(test.world_path(".multiverse/world.db"))?;
+    conn.execute_batch(
+        "CREATE TABLE faction_alliance_relations (
+             from_id TEXT NOT NULL,
+             to_id TEXT NOT NULL,
+             pact TEXT,
+             created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+             PRIMARY KEY (from_id, to_id)
+         );
+         INSERT INTO faction_alliance_relations (from_id, to_id, pact) VALUES ('1', '2', 'ally');",
+    )?;
+    drop(conn);
+    let output = test.run_command_assert_success(&["migrate"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("up to date"));
+
+    // Migrations never read config.toml: the table is brought up to date when the world is opened
+    test.append_config(r#"
+[relations.faction_alliance]
+from = "faction"
+to = "faction"
+role_column = "pact"
+"#)?;
+    test.run_command_assert_success(&["faction", "info", "stark"])?;
+    let result = test.query("SELECT pact, metadata, from_date, until_sort_key FROM faction_alliance_relations")?;
+    assert!(result.contains("ally"), "{}", result);
+    let result = test.query("SELECT COUNT(*) FROM sqlite_master WHERE type = 'index' AND name = 'idx_faction_alliance_relations_to_id'")?;
+    assert!(result.contains("\"COUNT(*)\": 1"), "{}", result);
+
+    let output = test.run_command_assert_success(&["faction", "info", "stark"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("tully"));
+
+    Ok(())
+}
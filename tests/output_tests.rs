@@ -0,0 +1,112 @@
+mod common;
+use common::MultiverseTest;
+use anyhow::Result;
+use serde_json::Value;
+
+fn json_stdout(output: &std::process::Output) -> Result<Value> {
+    Ok(serde_json::from_slice(&output.stdout)?)
+}
+
+#[test]
+fn test_json_output_for_entity_commands() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("JsonOutputTest")?;
+
+    test.run_command_assert_success(&["faction", "create", "nights_watch"])?;
+
+    // Prose moves to stderr, stdout holds only the result
+    let output = test.run_command_assert_success(&[
+        "--format", "json", "character", "create", "jon",
+        "--set", "display_name=Jon Snow", "--set", "faction=nights_watch*member"
+    ])?;
+    let created = json_stdout(&output)?;
+    assert_eq!(created["kind"], "character");
+    assert_eq!(created["action"], "created");
+    assert_eq!(created["name"], "jon");
+    assert_eq!(created["relations"][0]["name"], "nights_watch");
+    assert_eq!(created["relations"][0]["fields"]["role"], "member");
+
+    let output = test.run_command_assert_success(&["character", "info", "jon", "--format", "json"])?;
+    let info = json_stdout(&output)?;
+    assert_eq!(info["display_name"], "Jon Snow");
+    assert!(info.get("action").is_none());
+
+    let output = test.run_command_assert_success(&["character", "list", "--format", "json"])?;
+    let list = json_stdout(&output)?;
+    assert_eq!(list.as_array().map(Vec::len), Some(1));
+    assert_eq!(list[0]["status"], "Active");
+
+    let output = test.run_command_assert_success(&["character", "list", "--format", "plain", "--fields", "name,status"])?;
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "jon\tActive");
+
+    let output = test.run_command_assert_success(&["--format", "yaml", "faction", "info", "nights_watch"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("name: nights_watch"));
+
+    // Lists of objects start on the dash line
+    let output = test.run_command_assert_success(&["--format", "yaml", "character", "info", "jon"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("relations:\n  - direction: outgoing\n"));
+    assert!(stdout.contains("    fields:\n      role: member\n"));
+    assert!(stdout.contains("display_name: Jon Snow\n"));
+
+    Ok(())
+}
+
+#[test]
+fn test_json_error_codes() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("JsonErrorTest")?;
+
+    test.run_command_assert_success(&["faction", "create", "nights_watch"])?;
+    test.run_command_assert_success(&["character", "create", "jon", "--set", "faction=nights_watch"])?;
+    test.run_command_assert_success(&["character", "create", "sam"])?;
+
+    let cases: [(&[&str], &str); 5] = [
+        (&["--format", "json", "character", "info", "ghost"], "not_found"),
+        (&["--format", "json", "character", "create", "jon"], "already_exists"),
+        (&["--format", "json", "character", "delete", "sam"], "confirmation_required"),
+        (&["--format", "json", "character", "merge", "jon", "sam"], "confirmation_required"),
+        (&["--format", "json", "character", "delete", "jon", "--force"], "conflict"),
+    ];
+    for (args, code) in cases {
+        let output = test.run_command(args)?;
+        assert!(!output.status.success(), "{:?} should fail", args);
+        let error = json_stdout(&output)?;
+        assert_eq!(error["error"]["code"], code, "{:?}", args);
+        assert!(error["error"]["message"].is_string());
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_json_output_for_rename_and_merge() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("JsonRenameMergeTest")?;
+
+    test.run_command_assert_success(&["faction", "create", "nights_watch"])?;
+    test.run_command_assert_success(&["character", "create", "jon"])?;
+    test.run_command_assert_success(&["character", "create", "snow", "--set", "faction=nights_watch"])?;
+
+    let output = test.run_command_assert_success(&["--format", "json", "character", "rename", "jon", "jon_snow"])?;
+    let renamed = json_stdout(&output)?;
+    assert_eq!(renamed["kind"], "character");
+    assert_eq!(renamed["action"], "renamed");
+    assert_eq!(renamed["name"], "jon_snow");
+
+    let output = test.run_command_assert_success(&["--format", "json", "character", "merge", "jon_snow", "snow", "--force"])?;
+    let merged = json_stdout(&output)?;
+    assert_eq!(merged["action"], "merged");
+    assert_eq!(merged["name"], "jon_snow");
+    assert_eq!(merged["metadata"]["aliases"][0], "snow");
+    assert_eq!(merged["relations"][0]["name"], "nights_watch");
+
+    test.run_command_assert_success(&["story", "create", "tale", "--set", "type=novel"])?;
+    test.run_command_assert_success(&["episode", "create", "--story", "tale"])?;
+    let output = test.run_command_assert_success(&["--format", "json", "episode", "rename", "--story", "tale", "--number", "1", "2"])?;
+    let renumbered = json_stdout(&output)?;
+    assert_eq!(renumbered["action"], "renamed");
+    assert_eq!(renumbered["number"], 2);
+
+    Ok(())
+}
@@ -1,6 +1,8 @@
 mod common;
 use common::MultiverseTest;
 use anyhow::Result;
+use console::Key;
+use multiverse::line_editor::{Edit, LineBuffer};
 
 #[test]
 fn test_basic_query() -> Result<()> {
@@ -244,3 +246,280 @@ fn test_query_table_structure() -> Result<()> {
     
     Ok(())
 }
+
+#[test]
+fn test_query_output_formats() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("QueryFormatTest")?;
+    
+    test.run_command_assert_success(&[
+        "location", "create", "castle",
+        "--set", "display_name=The Castle, \"Old\""
+    ])?;
+    test.run_command_assert_success(&[
+        "character", "create", "knight",
+        "--set", "display_name=Sir | Knight",
+        "--set", "location=castle*resident"
+    ])?;
+    
+    let sql = "SELECT c.name, l.name, l.display_name FROM character_location_relations r
+               JOIN characters c ON r.from_id = c.id JOIN locations l ON r.to_id = l.id";
+    let run = |format: &str| -> Result<String> {
+        let output = test.run_command_assert_success(&["query", sql, "--format", format])?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+    
+    // CSV quotes separators and doubles quotes
+    assert_eq!(run("csv")?, "name,name,display_name\nknight,castle,\"The Castle, \"\"Old\"\"\"\n");
+    assert_eq!(run("tsv")?, "name\tname\tdisplay_name\nknight\tcastle\tThe Castle, \"Old\"\n");
+    
+    let markdown = run("markdown")?;
+    assert!(markdown.starts_with("| name | name | display_name |\n|---|---|---|\n"));
+    
+    let pipes = test.run_command_assert_success(&[
+        "query", "SELECT display_name FROM characters", "--format", "markdown"
+    ])?;
+    assert!(String::from_utf8_lossy(&pipes.stdout).contains("| Sir \\| Knight |"));
+    
+    let table = run("table")?;
+    assert_eq!(table.lines().nth(1), Some("knight  castle  The Castle, \"Old\""));
+    
+    // Repeated column names stay distinct in JSON rows
+    let ndjson = run("ndjson")?;
+    let row: serde_json::Value = serde_json::from_str(ndjson.trim())?;
+    assert_eq!(row["name"], "knight");
+    assert_eq!(row["name:2"], "castle");
+    
+    Ok(())
+}
+
+#[test]
+fn test_query_read_only() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("QueryReadOnlyTest")?;
+    
+    test.run_command_assert_success(&[
+        "character", "create", "scribe",
+        "--set", "note=created the update log"
+    ])?;
+    
+    // Keywords inside read-only queries are fine
+    let like = test.query("SELECT name FROM characters WHERE metadata LIKE '%created%update%'")?;
+    assert!(like.contains("scribe"));
+    
+    let cte = test.query("WITH named AS (SELECT name FROM characters) SELECT name FROM named")?;
+    assert!(cte.contains("scribe"));
+    
+    let plan = test.query("EXPLAIN QUERY PLAN SELECT * FROM characters WHERE name = 'scribe'")?;
+    assert!(plan.contains("characters"));
+    
+    let columns = test.query("PRAGMA table_info(characters)")?;
+    assert!(columns.contains("display_name"));
+    
+    // Writes are refused, whatever their shape
+    for sql in [
+        "DELETE FROM characters",
+        "UPDATE characters SET name = 'x'",
+        "CREATE TABLE notes (text TEXT)",
+        "WITH doomed AS (SELECT id FROM characters) DELETE FROM characters WHERE id IN doomed",
+        "SELECT 1; DROP TABLE characters",
+        "PRAGMA user_version = 5",
+    ] {
+        let output = test.run_command(&["query", sql])?;
+        assert!(!output.status.success(), "{} should be refused", sql);
+    }
+    assert_eq!(test.query_count("characters")?, 1);
+    
+    Ok(())
+}
+
+#[test]
+fn test_query_repl() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("QueryReplTest")?;
+    
+    test.run_command_assert_success(&["location", "create", "castle"])?;
+    test.run_command_assert_success(&[
+        "character", "create", "knight",
+        "--set", "location=castle*resident"
+    ])?;
+    
+    let input = "\
+.tables
+.schema characters
+.relations
+SELECT name,
+       status
+FROM characters;
+.format csv
+SELECT name FROM locations;
+DELETE FROM characters;
+.history
+!2
+.quit
+SELECT 'not reached';
+";
+    let output = test.run_command_with_input(&["query", "--repl"], input)?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    
+    // .tables and .relations classify and describe the tables
+    assert!(stdout.lines().any(|line| line.split_whitespace().eq(["characters", "entity"])));
+    assert!(stdout.lines().any(|line| line.split_whitespace().eq(["character_location_relations", "relation"])));
+    assert!(stdout.lines().any(|line| line.split_whitespace()
+        .eq(["character_faction_relations", "character", "faction", "role", "member"])));
+    
+    // .schema lists columns; the multi-line statement runs as a table
+    assert!(stdout.contains("display_name"));
+    assert!(stdout.lines().any(|line| line.split_whitespace().eq(["knight", "Active"])));
+    
+    // .format switches the renderer for the following statements
+    assert!(stdout.contains("name\ncastle\n"));
+    
+    // Writes are refused without leaving the shell, and history can be replayed
+    assert!(stderr.contains("Only read-only queries are allowed"));
+    assert!(stdout.contains("   1  SELECT name, status FROM characters;"));
+    assert!(stdout.ends_with("name\ncastle\n"));
+    assert!(!stdout.contains("not reached"));
+    assert_eq!(test.query_count("characters")?, 1);
+    
+    Ok(())
+}
+
+#[test]
+fn test_query_repl_line_editing() {
+    let history = vec!["SELECT name,\n       status\nFROM characters;\n".to_string(), "SELECT 1;\n".to_string()];
+    let mut line = LineBuffer::new(&history);
+    let type_text = |line: &mut LineBuffer, text: &str| text.chars().for_each(|c| { line.handle(Key::Char(c)); });
+
+    // Editing in the middle of the line
+    type_text(&mut line, "SELEC 2;");
+    for _ in 0..3 {
+        line.handle(Key::ArrowLeft);
+    }
+    line.handle(Key::Char('T'));
+    assert_eq!(line.text(), "SELECT 2;");
+    line.handle(Key::Home);
+    line.handle(Key::Del);
+    line.handle(Key::End);
+    line.handle(Key::Backspace);
+    assert_eq!(line.text(), "ELECT 2");
+    assert_eq!(line.handle(Key::ArrowRight), Edit::Ignored);
+
+    // Up walks back through the history, joining multi-line statements; down returns to the draft
+    line.handle(Key::ArrowUp);
+    assert_eq!(line.text(), "SELECT 1;");
+    line.handle(Key::ArrowUp);
+    assert_eq!(line.text(), "SELECT name,        status FROM characters;");
+    assert_eq!(line.handle(Key::ArrowUp), Edit::Ignored);
+    line.handle(Key::ArrowDown);
+    line.handle(Key::ArrowDown);
+    assert_eq!(line.text(), "ELECT 2");
+    assert_eq!(line.handle(Key::Enter), Edit::Submit("ELECT 2".to_string()));
+
+    // Ctrl-D ends input only on an empty line
+    let mut line = LineBuffer::new(&history);
+    assert_eq!(line.handle(Key::Char('\u{4}')), Edit::Eof);
+    type_text(&mut line, "x");
+    assert_eq!(line.handle(Key::Char('\u{4}')), Edit::Ignored);
+    assert_eq!(line.handle(Key::CtrlC), Edit::Cancel);
+}
+
+#[test]
+fn test_saved_queries() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("SavedQueryTest")?;
+    
+    test.run_command_assert_success(&["faction", "create", "stark"])?;
+    test.run_command_assert_success(&["faction", "create", "lannister"])?;
+    test.run_command_assert_success(&["character", "create", "jon", "--set", "faction=stark"])?;
+    test.run_command_assert_success(&["character", "create", "tyrion", "--set", "faction=lannister"])?;
+    test.run_command_assert_success(&["character", "create", "hodor"])?;
+    
+    // The built-in library is seeded with descriptions and parameters
+    let output = test.run_command_assert_success(&["query", "list", "--format", "csv"])?;
+    let list = String::from_utf8_lossy(&output.stdout);
+    assert!(list.contains("orphaned_characters,:faction,"));
+    assert!(list.contains("empty_events,,Events without participating characters"));
+    assert!(test.file_exists(".multiverse/queries/orphaned_characters.sql"));
+    
+    let run = |args: &[&str]| -> Result<String> {
+        let output = test.run_command_assert_success(args)?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+    assert_eq!(run(&["query", "run", "orphaned_characters", "--format", "csv"])?, "name,display_name\nhodor,\n");
+    assert_eq!(
+        run(&["query", "run", "orphaned_characters", "--param", "faction=stark", "--format", "csv"])?,
+        "name,display_name\nhodor,\ntyrion,\n"
+    );
+    
+    // User queries live next to the built-in ones
+    test.write_file(".multiverse/queries/members.sql", "\
+-- Members of :faction
+SELECT c.name FROM characters c
+JOIN character_faction_relations r ON r.from_id = c.id
+JOIN factions f ON f.id = r.to_id
+WHERE f.name = :faction;
+")?;
+    assert_eq!(run(&["query", "run", "members", "--param", "faction=lannister", "--format", "plain"])?, "tyrion\n");
+    let list = run(&["query", "list", "--format", "csv"])?;
+    assert!(list.contains("members,:faction,Members of :faction"));
+    
+    assert!(!test.run_command(&["query", "run", "members", "--param", "house=stark"])?.status.success());
+    assert!(!test.run_command(&["query", "run", "missing"])?.status.success());
+    
+    Ok(())
+}
+
+#[test]
+fn test_query_views() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("QueryViewTest")?;
+    test.append_config("[schema.character]\nage = \"int\"")?;
+    
+    test.run_command_assert_success(&["faction", "create", "stark"])?;
+    test.run_command_assert_success(&["location", "create", "winterfell"])?;
+    test.run_command_assert_success(&[
+        "character", "create", "jon",
+        "--set", "age=17", "--set", "house=stark",
+        "--set", "faction=stark*member", "--set", "location=winterfell*resident"
+    ])?;
+    test.run_command_assert_success(&["character", "create", "ned", "--set", "age=35", "--set", "character=jon*father"])?;
+    
+    let run = |sql: &str| -> Result<String> {
+        let output = test.run_command_assert_success(&["query", sql, "--format", "csv"])?;
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    };
+    
+    // v_relations resolves types and names of both sides
+    assert_eq!(
+        run("SELECT relation, from_type, from_name, to_type, to_name, role FROM v_relations ORDER BY relation")?,
+        "relation,from_type,from_name,to_type,to_name,role\n\
+         character_character,character,ned,character,jon,father\n\
+         character_faction,character,jon,faction,stark,member\n\
+         character_location,character,jon,location,winterfell,resident\n"
+    );
+    assert_eq!(run("SELECT typeof(from_id) FROM v_relations LIMIT 1")?, "typeof(from_id)\ninteger\n");
+    
+    // v_characters exposes metadata keys as columns
+    assert_eq!(run("SELECT name, house FROM v_characters WHERE age > 20 OR house = 'stark' ORDER BY name")?, "name,house\njon,stark\nned,\n");
+    assert_eq!(run("SELECT name FROM v_characters WHERE age > 20")?, "name\nned\n");
+    
+    // The views live in the database: other connections see them, including the
+    // metadata keys added by the last command
+    test.run_command_assert_success(&["character", "update", "ned", "--set", "title=Lord"])?;
+    let conn = rusqlite::Connection::open(test.world_path(".multiverse/world.db"))?;
+    let title: String = conn.query_row("SELECT title FROM v_characters WHERE name = 'ned'", [], |row| row.get(0))?;
+    assert_eq!(title, "Lord");
+    let relations: i64 = conn.query_row("SELECT COUNT(*) FROM v_relations", [], |row| row.get(0))?;
+    assert_eq!(relations, 3);
+    
+    // Views exist before any relation is created
+    let fresh = MultiverseTest::new()?;
+    fresh.init_world("EmptyViewTest")?;
+    let output = fresh.run_command_assert_success(&["query", "SELECT COUNT(*) FROM v_relations"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("\"count\": 1"));
+    
+    Ok(())
+}
@@ -0,0 +1,383 @@
+mod common;
+use common::MultiverseTest;
+use anyhow::Result;
+
+const TEST_TIMELINE: &str = r#"
+creation_year = 0
+
+[calendar]
+name = "Test Calendar"
+year_name = "year"
+year_days = 360
+months_per_year = 2
+days_per_month = 180
+weeks_per_month = 30
+week_name = "penta"
+days_per_week = 6
+day_name = "alba"
+
+[day_structure]
+blocks_per_day = 2
+candles_per_block = 12
+
+[day_blocks]
+names = ["Light", "Dark"]
+abbrevs = ["L", "D"]
+meanings = ["day", "night"]
+
+[months]
+names = ["Lumen", "Umbra"]
+abbrevs = ["Lum", "Umb"]
+meanings = ["light", "shadow"]
+
+[era_events.founding]
+name = "Founding"
+abbrev = "DF"
+year = 1000
+
+[date_formats]
+full = "{year}"
+abbreviated = "{year}"
+"#;
+
+fn setup_relations(test: &MultiverseTest) -> Result<()> {
+    test.run_command_assert_success(&[
+        "character", "create", "aragorn",
+        "--set", "display_name=Aragorn"
+    ])?;
+
+    test.run_command_assert_success(&[
+        "faction", "create", "rangers",
+        "--set", "display_name=Rangers of the North"
+    ])?;
+
+    test.run_command_assert_success(&[
+        "character", "update", "aragorn",
+        "--set", "faction=rangers*captain"
+    ])?;
+
+    Ok(())
+}
+
+#[test]
+fn test_relation_list() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RelationListTest")?;
+    setup_relations(&test)?;
+
+    // All relations resolve ids back to names
+    let output = test.run_command_assert_success(&["relation", "list"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("character_faction"));
+    assert!(stdout.contains("aragorn → rangers"));
+    assert!(stdout.contains("captain"));
+
+    // Relations of a single entity, seen from the target side
+    let output = test.run_command_assert_success(&["relation", "list", "faction", "rangers"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("← character aragorn"));
+
+    Ok(())
+}
+
+#[test]
+fn test_relation_retype_and_remove() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RelationRemoveTest")?;
+    setup_relations(&test)?;
+
+    test.run_command_assert_success(&[
+        "relation", "retype", "character", "aragorn", "faction", "rangers", "chieftain"
+    ])?;
+
+    // Entity order does not matter
+    let output = test.run_command_assert_success(&[
+        "relation", "show", "faction", "rangers", "character", "aragorn"
+    ])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("chieftain"));
+
+    test.run_command_assert_success(&[
+        "relation", "remove", "character", "aragorn", "faction", "rangers", "--force"
+    ])?;
+
+    let output = test.run_command_assert_success(&["relation", "list", "character", "aragorn"])?;
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("rangers"));
+
+    Ok(())
+}
+
+#[test]
+fn test_info_shows_relations() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RelationInfoTest")?;
+    setup_relations(&test)?;
+
+    let output = test.run_command_assert_success(&["character", "info", "aragorn"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("faction (1)"));
+    assert!(stdout.contains("→ rangers (role: captain)"));
+
+    // Incoming side is shown on the target entity
+    let output = test.run_command_assert_success(&["faction", "info", "rangers"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("← aragorn (role: captain)"));
+
+    Ok(())
+}
+
+#[test]
+fn test_reverse_relation_keys_from_registry() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RelationRegistryTest")?;
+
+    test.run_command_assert_success(&["event", "create", "battle-of-pelennor"])?;
+
+    // event_character is declared once; the character side gets the reverse key automatically
+    test.run_command_assert_success(&[
+        "character", "create", "eowyn",
+        "--set", "event=battle-of-pelennor*slayer"
+    ])?;
+
+    let output = test.run_command_assert_success(&[
+        "relation", "show", "event", "battle-of-pelennor", "character", "eowyn"
+    ])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("slayer"));
+
+    // Help text lists the relation keys of the entity
+    let output = test.run_command_assert_success(&["character", "create", "--help"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("reverse of event_character"));
+
+    Ok(())
+}
+
+#[test]
+fn test_config_defined_relations() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("ConfigRelationTest")?;
+    test.append_config(r#"
+[relations.faction_alliance]
+from = "faction"
+to = "faction"
+role_column = "pact"
+default_role = "ally"
+roles = ["ally", "vassal"]
+
+[relations.race_homeland]
+from = "race"
+to = "location"
+default_role = "homeland"
+
+[relations.event_omen]
+from = "event"
+to = "system"
+role_column = "from_date"
+"#)?;
+
+    // Columns every relation table has cannot be a role column
+    let output = test.run_command_assert_success(&["relation", "list", "--table", "faction_alliance"])?;
+    assert!(String::from_utf8_lossy(&output.stderr).contains("ignoring [relations.event_omen] in config.toml: invalid role_column 'from_date'"));
+
+    // Config tables exist as soon as the world is opened
+    let result = test.query("SELECT COUNT(*) FROM sqlite_master WHERE name IN ('faction_alliance_relations', 'race_homeland_relations')")?;
+    assert!(result.contains("\"COUNT(*)\": 2"), "{}", result);
+
+    test.run_command_assert_success(&["faction", "create", "gondor"])?;
+    test.run_command_assert_success(&["faction", "create", "rohan", "--set", "faction=gondor"])?;
+    test.run_command_assert_success(&["race", "create", "hobbit"])?;
+
+    // Reverse key on the to side
+    test.run_command_assert_success(&["location", "create", "shire", "--set", "race=hobbit"])?;
+
+    let output = test.run_command_assert_success(&["relation", "list", "faction", "rohan"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("→ faction gondor (pact: ally)"));
+
+    let output = test.run_command_assert_success(&["relation", "list", "race", "hobbit"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("→ location shire (role: homeland)"));
+
+    // Roles outside the configured vocabulary are rejected
+    let output = test.run_command(&["faction", "update", "rohan", "--set", "faction=gondor*enemy"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Allowed: ally, vassal"));
+
+    test.run_command_assert_success(&[
+        "relation", "retype", "faction", "rohan", "faction", "gondor", "vassal"
+    ])?;
+    let output = test.run_command_assert_success(&["relation", "list", "--table", "faction_alliance"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("pact: vassal"));
+
+    // The stored v_relations view picked up the tables added to config.toml after init
+    let conn = rusqlite::Connection::open(test.world_path(".multiverse/world.db"))?;
+    let role: String = conn.query_row(
+        "SELECT role FROM v_relations WHERE relation = 'faction_alliance' AND from_name = 'rohan'",
+        [],
+        |row| row.get(0),
+    )?;
+    assert_eq!(role, "vassal");
+
+    Ok(())
+}
+
+#[test]
+fn test_relation_metadata() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RelationMetadataTest")?;
+
+    test.run_command_assert_success(&["faction", "create", "rangers"])?;
+    test.run_command_assert_success(&["faction", "create", "dunedain"])?;
+
+    // Commas inside {...} do not split relations
+    test.run_command_assert_success(&[
+        "character", "create", "aragorn",
+        "--set", "faction=rangers*captain{since=3019,rank=2},dunedain{heir=true}"
+    ])?;
+
+    let output = test.run_command_assert_success(&["relation", "list", "character", "aragorn"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("rangers (role: captain {rank=2, since=3019})"));
+    assert!(stdout.contains("dunedain (role: member {heir=true})"));
+
+    // Merge, add and remove keys on an existing relation
+    test.run_command_assert_success(&[
+        "relation", "update", "character", "aragorn", "faction", "rangers",
+        "--set", "rank=3", "--set", "since=", "--set", "sworn=yes"
+    ])?;
+
+    let output = test.run_command_assert_success(&[
+        "relation", "show", "character", "aragorn", "faction", "rangers"
+    ])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("rank: 3"));
+    assert!(stdout.contains("sworn: yes"));
+    assert!(!stdout.contains("since"));
+
+    Ok(())
+}
+
+#[test]
+fn test_time_bounded_relations() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RelationPeriodTest")?;
+    test.write_file(".multiverse/timeline.toml", TEST_TIMELINE)?;
+
+    test.run_command_assert_success(&["faction", "create", "rangers"])?;
+    test.run_command_assert_success(&["location", "create", "bree"])?;
+    test.run_command_assert_success(&[
+        "character", "create", "aragorn",
+        "--set", "faction=rangers*captain{from=Lum 100 DF,until=Lum 130 DF}",
+        "--set", "location=bree*resident{from=Lum 131 DF}"
+    ])?;
+
+    let output = test.run_command_assert_success(&[
+        "relation", "list", "character", "aragorn", "--at", "Lum 120 DF"
+    ])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("rangers (role: captain [from Lum 100 DF until Lum 130 DF])"));
+    assert!(!stdout.contains("bree"));
+
+    let output = test.run_command_assert_success(&["character", "info", "aragorn", "--at", "Umb 140 DF"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Relations at Umb 140 DF:"));
+    assert!(stdout.contains("bree"));
+    assert!(!stdout.contains("rangers"));
+
+    // A relation cannot end before it starts
+    let output = test.run_command(&[
+        "relation", "update", "character", "aragorn", "faction", "rangers", "--set", "until=Lum 90 DF"
+    ])?;
+    assert!(!output.status.success());
+
+    // Clearing the end bound keeps the relation valid afterwards
+    test.run_command_assert_success(&[
+        "relation", "update", "character", "aragorn", "faction", "rangers", "--set", "until="
+    ])?;
+    let output = test.run_command_assert_success(&["relation", "list", "--at", "Umb 140 DF"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("aragorn → rangers"));
+
+    Ok(())
+}
+
+#[test]
+fn test_delete_with_relations() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RelationDeleteTest")?;
+
+    test.run_command_assert_success(&["faction", "create", "rangers"])?;
+    test.run_command_assert_success(&["character", "create", "aragorn", "--set", "faction=rangers*captain"])?;
+
+    // Refuse is the default: the faction is kept and the blocking relation is listed
+    let output = test.run_command(&["faction", "delete", "rangers", "--force"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("aragorn"));
+    test.run_command_assert_success(&["faction", "info", "rangers"])?;
+
+    // Detach removes the relation rows and then the entity
+    test.run_command_assert_success(&["faction", "delete", "rangers", "--force", "--on-relations", "detach"])?;
+    let output = test.run_command_assert_success(&["relation", "list", "character", "aragorn"])?;
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("rangers"));
+
+    // A story with episodes can only go together with them
+    test.run_command_assert_success(&["story", "create", "tale", "--set", "type=diary", "--set", "narrator=Frodo"])?;
+    test.run_command_assert_success(&["episode", "create", "--story", "tale", "--set", "title=One"])?;
+    test.run_command_assert_success(&["character", "update", "aragorn", "--set", "episode=tale:1"])?;
+
+    let output = test.run_command(&["story", "delete", "tale", "--force", "--on-relations", "detach"])?;
+    assert!(!output.status.success());
+    test.run_command_assert_success(&["story", "delete", "tale", "--force", "--on-relations", "cascade"])?;
+
+    let output = test.run_command_assert_success(&["relation", "doctor"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No orphaned rows found"));
+    let output = test.run_command_assert_success(&["world", "doctor"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No orphaned rows found"));
+
+    Ok(())
+}
+
+#[test]
+fn test_list_related_filters() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("RelatedListTest")?;
+
+    test.run_command_assert_success(&["faction", "create", "nights_watch"])?;
+    test.run_command_assert_success(&["faction", "create", "wildlings"])?;
+    test.run_command_assert_success(&["character", "create", "jon", "--set", "faction=nights_watch*member"])?;
+    test.run_command_assert_success(&["character", "create", "mormont", "--set", "faction=nights_watch*leader"])?;
+    test.run_command_assert_success(&["character", "create", "tormund", "--set", "faction=wildlings*leader"])?;
+    test.run_command_assert_success(&["character", "create", "arya"])?;
+
+    let names = |args: &[&str]| -> Result<Vec<String>> {
+        let output = test.run_command_assert_success(args)?;
+        let mut names: Vec<String> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .skip(1)
+            .map(|line| line.trim().to_string())
+            .collect();
+        names.sort();
+        Ok(names)
+    };
+
+    assert_eq!(
+        names(&["character", "list", "--related", "faction=nights_watch", "--fields", "name"])?,
+        ["jon", "mormont"]
+    );
+    assert_eq!(
+        names(&["character", "list", "--related", "faction=nights_watch", "--related-role", "leader", "--fields", "name"])?,
+        ["mormont"]
+    );
+    assert_eq!(names(&["character", "list", "--not-related", "faction", "--fields", "name"])?, ["arya"]);
+    // Reverse keys work from the other side
+    assert_eq!(names(&["faction", "list", "--related", "character=tormund", "--fields", "name"])?, ["wildlings"]);
+
+    // Self-relations match on either side, with the role read from the entity's side
+    test.run_command_assert_success(&["character", "update", "mormont", "--set", "character=jon*mentor"])?;
+    assert_eq!(names(&["character", "list", "--related", "character=jon", "--fields", "name"])?, ["mormont"]);
+    assert_eq!(names(&["character", "list", "--related", "character=mormont", "--fields", "name"])?, ["jon"]);
+    assert_eq!(names(&["character", "list", "--related", "character=mormont*student", "--fields", "name"])?, ["jon"]);
+    assert_eq!(names(&["character", "list", "--related", "character=jon*mentor", "--fields", "name"])?, ["mormont"]);
+    assert!(names(&["character", "list", "--related", "character=mormont*mentor", "--fields", "name"])?.is_empty());
+    assert_eq!(names(&["character", "list", "--not-related", "character", "--fields", "name"])?, ["arya", "tormund"]);
+
+    let output = test.run_command(&["character", "list", "--related", "house=stark"])?;
+    assert!(!output.status.success());
+
+    Ok(())
+}
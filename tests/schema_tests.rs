@@ -0,0 +1,77 @@
+mod common;
+use common::MultiverseTest;
+use anyhow::Result;
+
+const CHARACTER_SCHEMA: &str = r#"
+[schema.character]
+age = { type = "int", required = true }
+code = "string"
+house = { type = "enum", values = ["stark", "lannister"] }
+titles = "list"
+alive = "bool"
+mentor = { type = "entity-ref", entity = "character" }
+"#;
+
+#[test]
+fn test_schema_coerces_declared_fields() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("SchemaTest")?;
+    test.append_config(CHARACTER_SCHEMA)?;
+    
+    let output = test.run_command_assert_success(&[
+        "character", "create", "ned",
+        "--set", "age=45",
+        "--set", "code=025",
+        "--set", "house=stark",
+        "--set", "titles=Lord, Warden",
+        "--set", "alive=yes"
+    ])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("age: 45"));
+    assert!(stdout.contains("code: \"025\""));
+    assert!(stdout.contains("titles: [\"Lord\",\"Warden\"]"));
+    assert!(stdout.contains("alive: true"));
+    
+    Ok(())
+}
+
+#[test]
+fn test_schema_rejects_invalid_values() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("SchemaErrorTest")?;
+    test.append_config(CHARACTER_SCHEMA)?;
+    test.run_command_assert_success(&["character", "create", "ned", "--set", "age=45"])?;
+    
+    let cases: [(&[&str], &str); 4] = [
+        (&["character", "create", "jon"], "Missing required field(s) for character: age"),
+        (&["character", "create", "jon", "--set", "age=x"], "Field 'age' expects an integer"),
+        (&["character", "create", "jon", "--set", "age=17", "--set", "house=snow"], "one of stark, lannister"),
+        (&["character", "update", "ned", "--set", "mentor=nedd"], "did you mean ned?"),
+    ];
+    for (args, message) in cases {
+        let output = test.run_command(args)?;
+        assert!(!output.status.success());
+        assert!(String::from_utf8_lossy(&output.stderr).contains(message), "expected '{}'", message);
+    }
+    
+    Ok(())
+}
+
+#[test]
+fn test_entity_schema_command() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("SchemaShowTest")?;
+    test.append_config(CHARACTER_SCHEMA)?;
+    
+    let output = test.run_command_assert_success(&["character", "schema"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("display_name: String"));
+    assert!(stdout.contains("age: int (required)"));
+    assert!(stdout.contains("mentor: entity-ref → character"));
+    
+    // Entities without a section only list built-in fields
+    let output = test.run_command_assert_success(&["faction", "schema"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("No [schema.faction] section"));
+    
+    Ok(())
+}
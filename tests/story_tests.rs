@@ -113,6 +113,91 @@ fn test_story_delete() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_story_rename() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("StoryRenameTest")?;
+    
+    test.run_command_assert_success(&[
+        "story", "create", "old_tale",
+        "--set", "type=diary",
+        "--set", "narrator=Bilbo"
+    ])?;
+    test.run_command_assert_success(&[
+        "episode", "create", "--story", "old_tale",
+        "--set", "title=First"
+    ])?;
+    
+    test.run_command_assert_success(&["story", "rename", "old_tale", "new_tale"])?;
+    
+    // Directory and episodes move with the story
+    assert!(!test.file_exists("stories/old_tale"));
+    assert!(test.file_exists("stories/new_tale/001.md"));
+    let output = test.run_command_assert_success(&["episode", "list", "--story", "new_tale"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("First"));
+    
+    // Episodes are renumbered together with their file
+    test.run_command_assert_success(&[
+        "episode", "rename", "--story", "new_tale", "--number", "1", "3"
+    ])?;
+    assert!(test.file_exists("stories/new_tale/003.md"));
+    test.run_command_assert_success(&[
+        "episode", "info", "--story", "new_tale", "--number", "3"
+    ])?;
+    
+    Ok(())
+}
+
+#[test]
+fn test_failed_commands_leave_files_untouched() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("FileRollbackTest")?;
+
+    test.run_command_assert_success(&["story", "create", "epic", "--set", "type=novel"])?;
+    test.run_command_assert_success(&["episode", "create", "--story", "epic"])?;
+
+    // A relation that fails after the episode row exists
+    let output = test.run_command(&["episode", "create", "--story", "epic", "--set", "character=nobody"])?;
+    assert!(!output.status.success());
+    assert!(!test.file_exists("stories/epic/002.md"));
+    assert_eq!(test.query_count("episodes")?, 1);
+
+    // From here on every write to stories or episodes breaks a deferred foreign key, so the
+    // commands get through their work and fail only at COMMIT
+    let conn = rusqlite::Connection::open(test.world_path(".multiverse/world.db"))?;
+    conn.execute_batch(
+        "CREATE TABLE commit_blocker (story TEXT REFERENCES stories (name) DEFERRABLE INITIALLY DEFERRED);
+         CREATE TRIGGER block_story_insert AFTER INSERT ON stories BEGIN INSERT INTO commit_blocker VALUES ('missing'); END;
+         CREATE TRIGGER block_story_update AFTER UPDATE ON stories BEGIN INSERT INTO commit_blocker VALUES ('missing'); END;
+         CREATE TRIGGER block_story_delete AFTER DELETE ON stories BEGIN INSERT INTO commit_blocker VALUES ('missing'); END;
+         CREATE TRIGGER block_episode_update AFTER UPDATE ON episodes BEGIN INSERT INTO commit_blocker VALUES ('missing'); END;
+         CREATE TRIGGER block_episode_delete AFTER DELETE ON episodes BEGIN INSERT INTO commit_blocker VALUES ('missing'); END;",
+    )?;
+    drop(conn);
+
+    let failing: &[&[&str]] = &[
+        &["story", "create", "saga", "--set", "type=novel"],
+        &["story", "rename", "epic", "saga"],
+        &["episode", "rename", "--story", "epic", "--number", "1", "5"],
+        &["episode", "delete", "--story", "epic", "--number", "1", "--force"],
+        &["story", "delete", "epic", "--force", "--on-relations", "cascade"],
+    ];
+    for args in failing {
+        let output = test.run_command(args)?;
+        assert!(!output.status.success(), "{:?} succeeded", args);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to commit"), "{:?}", args);
+    }
+
+    // No directory created, moved or deleted, no episode file moved or deleted
+    assert!(!test.file_exists("stories/saga"));
+    assert!(test.file_exists("stories/epic/README.md"));
+    assert!(test.file_exists("stories/epic/001.md"));
+    assert!(!test.file_exists("stories/epic/005.md"));
+    assert!(test.entity_exists("stories", "epic")?);
+
+    Ok(())
+}
+
 #[test]
 fn test_book_story_type() -> Result<()> {
     let test = MultiverseTest::new()?;
@@ -137,4 +222,134 @@ fn test_book_story_type() -> Result<()> {
     assert!(metadata.contains("fantasy"));
     
     Ok(())
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_story_type_required_fields_and_defaults() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("StoryTypeRulesTest")?;
+    test.append_config(r#"
+[world.global_config.story_types.serial]
+display_name = "Serial"
+required_fields = ["author"]
+optional_fields = ["genre"]
+numbering_format = "part_{:02}"
+
+[world.global_config.story_types.serial.defaults]
+genre = "mystery"
+"#)?;
+    
+    // Diaries need a narrator
+    let output = test.run_command(&["story", "create", "journal", "--set", "type=diary"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires: narrator"));
+    
+    let output = test.run_command(&["story", "create", "tale", "--set", "type=serial"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("requires: author"));
+    
+    // Defaults fill in what was not set
+    let output = test.run_command_assert_success(&[
+        "story", "create", "tale",
+        "--set", "type=serial",
+        "--set", "author=Poe"
+    ])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("genre: \"mystery\""));
+    
+    // Episodes follow the type's numbering_format
+    test.run_command_assert_success(&[
+        "episode", "create", "--story", "tale",
+        "--set", "title=Opening"
+    ])?;
+    assert!(test.file_exists("stories/tale/part_01.md"));
+    let output = test.run_command_assert_success(&["episode", "list", "--story", "tale"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("part_01. Opening"));
+    
+    Ok(())
+}
+
+#[test]
+fn test_episode_files_survive_numbering_changes() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("NumberingChangeTest")?;
+    test.append_config(r#"
+[world.global_config.story_types.serial]
+display_name = "Serial"
+required_fields = []
+optional_fields = []
+numbering_format = "part_{:02}"
+"#)?;
+    
+    test.run_command_assert_success(&["story", "create", "tale", "--set", "type=serial"])?;
+    test.run_command_assert_success(&["episode", "create", "--story", "tale"])?;
+    assert!(test.file_exists("stories/tale/part_01.md"));
+    
+    let set_numbering = |format: &str| -> Result<()> {
+        let config = std::fs::read_to_string(test.world_path(".multiverse/config.toml"))?;
+        // The serial type was appended last
+        let start = config.rfind("numbering_format = ").expect("serial numbering");
+        let end = start + config[start..].find('\n').expect("line end");
+        let config = format!("{}numbering_format = \"{}\"{}", &config[..start], format, &config[end..]);
+        test.write_file(".multiverse/config.toml", &config)
+    };
+    
+    // Files written under the old numbering are still found, and move to the new one
+    set_numbering("chapter-{:03}")?;
+    test.run_command_assert_success(&["episode", "rename", "--story", "tale", "--number", "1", "2"])?;
+    assert!(test.file_exists("stories/tale/chapter-002.md"));
+    assert!(!test.file_exists("stories/tale/part_01.md"));
+    
+    // Formats that would make odd or nested file names are refused
+    for format in ["{:3}", "{:0x}", "part/{:02}"] {
+        set_numbering(format)?;
+        let output = test.run_command(&["episode", "list", "--story", "tale"])?;
+        assert!(!output.status.success(), "{}", format);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid numbering_format"), "{}", format);
+    }
+    
+    Ok(())
+}
+
+#[test]
+fn test_story_status_workflow() -> Result<()> {
+    let test = MultiverseTest::new()?;
+    test.init_world("StoryWorkflowTest")?;
+    test.append_config(r#"
+[status.story]
+values = ["Draft", "Outlined", "InProgress", "Beta", "Published"]
+initial = "Outlined"
+
+[status.story.transitions]
+Outlined = ["Draft", "InProgress"]
+InProgress = ["Beta"]
+
+[status.story.emoji]
+Beta = "🧪"
+"#)?;
+    
+    test.run_command_assert_success(&[
+        "story", "create", "saga",
+        "--set", "type=diary",
+        "--set", "narrator=Sam"
+    ])?;
+    let output = test.run_command_assert_success(&["story", "info", "saga"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("Status: Outlined"));
+    
+    // Unknown states and skipped transitions are refused
+    let output = test.run_command(&["story", "update", "saga", "--set", "status=Review"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid status 'Review'"));
+    
+    let output = test.run_command(&["story", "update", "saga", "--set", "status=Published"])?;
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Allowed from 'Outlined': Draft, InProgress"));
+    
+    test.run_command_assert_success(&["story", "update", "saga", "--set", "status=InProgress"])?;
+    test.run_command_assert_success(&["story", "update", "saga", "--set", "status=Beta"])?;
+    
+    // Custom states render with their configured emoji
+    let output = test.run_command_assert_success(&["story", "list"])?;
+    assert!(String::from_utf8_lossy(&output.stdout).contains("🧪 saga"));
+    
+    Ok(())
+}